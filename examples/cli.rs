@@ -58,6 +58,7 @@ $ cargo run --example cli - [--dialectname]
         "--clickhouse" => Box::new(ClickHouseDialect {}),
         "--duckdb" => Box::new(DuckDbDialect {}),
         "--sqlite" => Box::new(SQLiteDialect {}),
+        "--cypher" => Box::new(CypherDialect {}),
         "--generic" | "" => Box::new(GenericDialect {}),
         s => panic!("Unexpected parameter: {s}"),
     };
@@ -85,11 +86,17 @@ $ cargo run --example cli - [--dialectname]
     match parse_result {
         Ok(statements) => {
             // desugar
-            let statements: Vec<_> = statements
+            let statements: Vec<_> = match statements
                 .iter()
-                .map(|stmt| stmt.desugar_cypher_to_sql())
-                .collect();
-                
+                .map(|stmt| stmt.desugar_cypher_to_sql(&*dialect))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(statements) => statements.into_iter().flatten().collect(),
+                Err(e) => {
+                    println!("Error during desugaring: {e}");
+                    std::process::exit(1);
+                }
+            };
 
             println!(
                 "{}",
@@ -1,20 +1,28 @@
-use sqlparser::parser::Parser;
-use sqlparser::dialect::GenericDialect;
-
-fn main() {
-    let input = "MATCH (x)-[rel:EATS]->(y) DELETE rel";
-    
-    let dialect = GenericDialect {};
-    let mut parser = Parser::new(&dialect).try_with_sql(input).expect("Failed to parse");
-    
-    match parser.parse_statements() {
-        Ok(statements) => {
-            for stmt in statements {
-                let desugared = stmt.desugar_cypher_to_sql();
-                println!("Input:  {}", input);
-                println!("Output: {}", desugared);
-            }
-        }
-        Err(e) => println!("Error: {}", e),
-    }
-}
+use sqlparser::dialect::{CypherDialect, SQLiteDialect};
+use sqlparser::parser::Parser;
+
+fn main() {
+    let input = "MATCH (x)-[rel:EATS]->(y) DELETE rel";
+
+    let dialect = CypherDialect {};
+    let mut parser = Parser::new(&dialect)
+        .try_with_sql(input)
+        .expect("Failed to parse");
+
+    match parser.parse_statements() {
+        Ok(statements) => {
+            for stmt in statements {
+                println!("Input:  {}", input);
+                match stmt.desugar_cypher_to_sql(&SQLiteDialect {}) {
+                    Ok(desugared) => {
+                        for statement in desugared {
+                            println!("Output: {}", statement);
+                        }
+                    }
+                    Err(e) => println!("Error: {}", e),
+                }
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+}
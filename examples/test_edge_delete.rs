@@ -1,21 +1,29 @@
-use sqlparser::parser::Parser;
-use sqlparser::dialect::GenericDialect;
-
-fn main() {
-    let input = "MATCH ()-[r]->() DELETE r";
-    
-    let dialect = GenericDialect {};
-    let mut parser = Parser::new(&dialect).try_with_sql(input).expect("Failed to parse");
-    
-    match parser.parse_statements() {
-        Ok(statements) => {
-            for stmt in statements {
-                println!("Original statement: {:?}", stmt);
-                let desugared = stmt.desugar_cypher_to_sql();
-                println!("Desugared statement: {:?}", desugared);
-                println!("Desugared display: {}", desugared);
-            }
-        }
-        Err(e) => println!("Parse error: {}", e),
-    }
-}
+use sqlparser::dialect::{CypherDialect, SQLiteDialect};
+use sqlparser::parser::Parser;
+
+fn main() {
+    let input = "MATCH ()-[r]->() DELETE r";
+
+    let dialect = CypherDialect {};
+    let mut parser = Parser::new(&dialect)
+        .try_with_sql(input)
+        .expect("Failed to parse");
+
+    match parser.parse_statements() {
+        Ok(statements) => {
+            for stmt in statements {
+                println!("Original statement: {:?}", stmt);
+                match stmt.desugar_cypher_to_sql(&SQLiteDialect {}) {
+                    Ok(desugared) => {
+                        for statement in desugared {
+                            println!("Desugared statement: {:?}", statement);
+                            println!("Desugared display: {}", statement);
+                        }
+                    }
+                    Err(e) => println!("Desugar error: {}", e),
+                }
+            }
+        }
+        Err(e) => println!("Parse error: {}", e),
+    }
+}
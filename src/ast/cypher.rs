@@ -0,0 +1,691 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! AST types specific to [openCypher] graph queries, as parsed by
+//! [`CypherDialect`](crate::dialect::CypherDialect).
+//!
+//! [openCypher]: https://opencypher.org/
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use core::fmt::{self, Display};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "visitor")]
+use sqlparser_derive::{Visit, VisitMut};
+
+use super::{display_comma_separated, display_separated, Expr, Ident, OrderByExpr};
+
+/// A Cypher query: an ordered list of clauses such as
+/// `MATCH (a:Person) RETURN a`, optionally combined with other queries by
+/// `UNION`.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct CypherQuery {
+    pub clauses: Vec<CypherClause>,
+    /// The queries following `UNION` or `UNION ALL`, in order
+    pub unions: Vec<CypherUnion>,
+}
+
+impl Display for CypherQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", display_separated(&self.clauses, " "))?;
+        for union in &self.unions {
+            write!(f, " {union}")?;
+        }
+        Ok(())
+    }
+}
+
+/// `UNION [ALL] <clause> ...`, a query whose rows are added to those of the
+/// query before it
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct CypherUnion {
+    /// `UNION ALL` keeps duplicate rows
+    pub all: bool,
+    pub clauses: Vec<CypherClause>,
+}
+
+impl Display for CypherUnion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UNION ")?;
+        if self.all {
+            write!(f, "ALL ")?;
+        }
+        write!(f, "{}", display_separated(&self.clauses, " "))
+    }
+}
+
+/// A single clause of a [`CypherQuery`].
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub enum CypherClause {
+    /// `[OPTIONAL] MATCH (a)-[r]->(b)`
+    Match(MatchClause),
+    /// `CREATE (a:Person {name: 'Ann'})`
+    Create(CreateClause),
+    /// `MERGE (a:Person {name: 'Ann'}) ON CREATE SET a.new = true`
+    Merge(CypherMergeClause),
+    /// `UNWIND [1, 2, 3] AS x`
+    Unwind(UnwindClause),
+    /// `[DETACH] DELETE a, r`
+    Delete(DeleteClause),
+    /// `SET a.name = 'Ann', a:Person`
+    Set(SetClause),
+    /// `REMOVE a.name, a:Person`
+    Remove(RemoveClause),
+    /// `WITH a, count(*) AS c WHERE c > 2`
+    With(WithClause),
+    /// `RETURN a, b.name AS name`
+    Return(ReturnClause),
+}
+
+impl Display for CypherClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CypherClause::Match(c) => write!(f, "{c}"),
+            CypherClause::Create(c) => write!(f, "{c}"),
+            CypherClause::Merge(c) => write!(f, "{c}"),
+            CypherClause::Unwind(c) => write!(f, "{c}"),
+            CypherClause::Delete(c) => write!(f, "{c}"),
+            CypherClause::Set(c) => write!(f, "{c}"),
+            CypherClause::Remove(c) => write!(f, "{c}"),
+            CypherClause::With(c) => write!(f, "{c}"),
+            CypherClause::Return(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+/// `[OPTIONAL] MATCH <pattern>, ... [WHERE <expr>]`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct MatchClause {
+    /// `OPTIONAL MATCH` produces `NULL`s instead of dropping the rows for
+    /// which the pattern has no match
+    pub optional: bool,
+    pub pattern: Vec<PathPattern>,
+    /// The `WHERE` predicate filtering the matched paths
+    pub selection: Option<Expr>,
+}
+
+impl Display for MatchClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.optional {
+            write!(f, "OPTIONAL ")?;
+        }
+        write!(f, "MATCH {}", display_comma_separated(&self.pattern))?;
+        if let Some(selection) = &self.selection {
+            write!(f, " WHERE {selection}")?;
+        }
+        Ok(())
+    }
+}
+
+/// `CREATE <pattern>, ...`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct CreateClause {
+    pub pattern: Vec<PathPattern>,
+}
+
+impl Display for CreateClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CREATE {}", display_comma_separated(&self.pattern))
+    }
+}
+
+/// `MERGE <pattern> [ON CREATE SET <item>, ...] [ON MATCH SET <item>, ...]`
+///
+/// Matches the pattern, or creates it if it has no match.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct CypherMergeClause {
+    pub pattern: PathPattern,
+    /// The `ON CREATE SET` items, applied if the pattern is created
+    pub on_create: Vec<SetItem>,
+    /// The `ON MATCH SET` items, applied if the pattern is matched
+    pub on_match: Vec<SetItem>,
+}
+
+impl Display for CypherMergeClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MERGE {}", self.pattern)?;
+        if !self.on_create.is_empty() {
+            write!(
+                f,
+                " ON CREATE SET {}",
+                display_comma_separated(&self.on_create)
+            )?;
+        }
+        if !self.on_match.is_empty() {
+            write!(
+                f,
+                " ON MATCH SET {}",
+                display_comma_separated(&self.on_match)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// `UNWIND <list> AS <variable>`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct UnwindClause {
+    /// A list literal such as `[{id: 1}, {id: 2}]`, or a parameter
+    pub expr: Expr,
+    /// The variable bound to each element of the list in turn
+    pub alias: Ident,
+}
+
+impl Display for UnwindClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UNWIND {} AS {}", self.expr, self.alias)
+    }
+}
+
+/// `[DETACH] DELETE <expr>, ...`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct DeleteClause {
+    /// `DETACH DELETE` also removes the relationships of deleted nodes
+    pub detach: bool,
+    pub items: Vec<Expr>,
+}
+
+impl Display for DeleteClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.detach {
+            write!(f, "DETACH ")?;
+        }
+        write!(f, "DELETE {}", display_comma_separated(&self.items))
+    }
+}
+
+/// `SET <item>, ...`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct SetClause {
+    pub items: Vec<SetItem>,
+}
+
+impl Display for SetClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SET {}", display_comma_separated(&self.items))
+    }
+}
+
+/// An item of a [`SetClause`]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub enum SetItem {
+    /// `n.key = <expr>`. Setting a property to `null` removes it.
+    Property {
+        variable: Ident,
+        key: Ident,
+        value: Expr,
+    },
+    /// `n = <map>`, replacing all properties of `n`, or `n += <map>`,
+    /// adding to them
+    Properties {
+        variable: Ident,
+        value: Expr,
+        merge: bool,
+    },
+    /// `n:Label`
+    Label { variable: Ident, label: Ident },
+}
+
+impl Display for SetItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetItem::Property {
+                variable,
+                key,
+                value,
+            } => write!(f, "{variable}.{key} = {value}"),
+            SetItem::Properties {
+                variable,
+                value,
+                merge,
+            } => {
+                let op = if *merge { "+=" } else { "=" };
+                write!(f, "{variable} {op} {value}")
+            }
+            SetItem::Label { variable, label } => write!(f, "{variable}:{label}"),
+        }
+    }
+}
+
+/// `REMOVE <item>, ...`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct RemoveClause {
+    pub items: Vec<RemoveItem>,
+}
+
+impl Display for RemoveClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "REMOVE {}", display_comma_separated(&self.items))
+    }
+}
+
+/// An item of a [`RemoveClause`]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub enum RemoveItem {
+    /// `n.key`
+    Property { variable: Ident, key: Ident },
+    /// `n:Label`
+    Label { variable: Ident, label: Ident },
+}
+
+impl Display for RemoveItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoveItem::Property { variable, key } => write!(f, "{variable}.{key}"),
+            RemoveItem::Label { variable, label } => write!(f, "{variable}:{label}"),
+        }
+    }
+}
+
+/// `RETURN [DISTINCT] <item>, ... [ORDER BY ...] [SKIP <n>] [LIMIT <n>]`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct ReturnClause {
+    pub distinct: bool,
+    pub items: Vec<ProjectionItem>,
+    /// `ORDER BY <expr> [ASC | DESC], ...`
+    pub order_by: Vec<OrderByExpr>,
+    /// `SKIP <expr>`
+    pub skip: Option<Expr>,
+    /// `LIMIT <expr>`
+    pub limit: Option<Expr>,
+}
+
+impl Display for ReturnClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RETURN ")?;
+        display_projection_body(
+            f,
+            self.distinct,
+            &self.items,
+            &self.order_by,
+            &self.skip,
+            &self.limit,
+        )
+    }
+}
+
+/// `WITH [DISTINCT] <item>, ... [ORDER BY ...] [SKIP <n>] [LIMIT <n>] [WHERE <expr>]`
+///
+/// Ends one part of a multi-part query: only the variables it projects are
+/// visible to the clauses that follow.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct WithClause {
+    pub distinct: bool,
+    pub items: Vec<ProjectionItem>,
+    /// `ORDER BY <expr> [ASC | DESC], ...`
+    pub order_by: Vec<OrderByExpr>,
+    /// `SKIP <expr>`
+    pub skip: Option<Expr>,
+    /// `LIMIT <expr>`
+    pub limit: Option<Expr>,
+    /// The `WHERE` predicate filtering the projected rows
+    pub selection: Option<Expr>,
+}
+
+impl Display for WithClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WITH ")?;
+        display_projection_body(
+            f,
+            self.distinct,
+            &self.items,
+            &self.order_by,
+            &self.skip,
+            &self.limit,
+        )?;
+        if let Some(selection) = &self.selection {
+            write!(f, " WHERE {selection}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The part of `RETURN` and `WITH` following the keyword
+fn display_projection_body(
+    f: &mut fmt::Formatter<'_>,
+    distinct: bool,
+    items: &[ProjectionItem],
+    order_by: &[OrderByExpr],
+    skip: &Option<Expr>,
+    limit: &Option<Expr>,
+) -> fmt::Result {
+    if distinct {
+        write!(f, "DISTINCT ")?;
+    }
+    write!(f, "{}", display_comma_separated(items))?;
+    if !order_by.is_empty() {
+        write!(f, " ORDER BY {}", display_comma_separated(order_by))?;
+    }
+    if let Some(skip) = skip {
+        write!(f, " SKIP {skip}")?;
+    }
+    if let Some(limit) = limit {
+        write!(f, " LIMIT {limit}")?;
+    }
+    Ok(())
+}
+
+/// An item projected by `RETURN` or `WITH`, e.g. `n.name AS name`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct ProjectionItem {
+    pub expr: Expr,
+    pub alias: Option<Ident>,
+}
+
+impl Display for ProjectionItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expr)?;
+        if let Some(alias) = &self.alias {
+            write!(f, " AS {alias}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A path pattern: a node optionally followed by a chain of
+/// relationship/node pairs, e.g. `(a)-[r:KNOWS]->(b)`.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct PathPattern {
+    pub start: NodePattern,
+    pub segments: Vec<PathSegment>,
+}
+
+impl Display for PathPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.start)?;
+        for segment in &self.segments {
+            write!(f, "{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+/// One hop of a [`PathPattern`]: `-[r]->(b)`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct PathSegment {
+    pub relationship: RelationshipPattern,
+    pub node: NodePattern,
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.relationship, self.node)
+    }
+}
+
+/// A node pattern such as `(n:Person {name: 'Ann'})`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct NodePattern {
+    pub variable: Option<Ident>,
+    pub label: Option<LabelExpression>,
+    pub properties: Vec<PropertyEntry>,
+}
+
+impl Display for NodePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        display_pattern_filler(f, &self.variable, &self.label, None, &self.properties)?;
+        write!(f, ")")
+    }
+}
+
+/// A relationship pattern such as `-[r:KNOWS {since: 2020}]->`, `<-[r]-`
+/// or `--`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct RelationshipPattern {
+    pub direction: RelationshipDirection,
+    pub variable: Option<Ident>,
+    pub rel_type: Option<LabelExpression>,
+    /// `*min..max` of a variable-length relationship such as `-[:KNOWS*1..3]->`
+    pub length: Option<RelationshipLength>,
+    pub properties: Vec<PropertyEntry>,
+}
+
+impl Display for RelationshipPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.direction == RelationshipDirection::Incoming {
+            write!(f, "<")?;
+        }
+        write!(f, "-")?;
+        if self.variable.is_some()
+            || self.rel_type.is_some()
+            || self.length.is_some()
+            || !self.properties.is_empty()
+        {
+            write!(f, "[")?;
+            display_pattern_filler(
+                f,
+                &self.variable,
+                &self.rel_type,
+                self.length.as_ref(),
+                &self.properties,
+            )?;
+            write!(f, "]")?;
+        }
+        match self.direction {
+            RelationshipDirection::Outgoing => write!(f, "->"),
+            RelationshipDirection::Incoming | RelationshipDirection::Undirected => write!(f, "-"),
+        }
+    }
+}
+
+/// The labels a node must have, such as `Person` in `(n:Person)`, or the
+/// types a relationship may have, such as `KNOWS|LIKES` in `[:KNOWS|LIKES]`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub enum LabelExpression {
+    /// `Person`
+    Label(Ident),
+    /// `Person:Employee`, all of the labels
+    All(Vec<LabelExpression>),
+    /// `Cat|Dog`, any of the labels
+    Any(Vec<LabelExpression>),
+    /// `!Cat`, any label but this one
+    Not(Box<LabelExpression>),
+}
+
+impl LabelExpression {
+    /// The label, if the expression is a single label
+    pub fn as_label(&self) -> Option<&Ident> {
+        match self {
+            LabelExpression::Label(label) => Some(label),
+            _ => None,
+        }
+    }
+}
+
+impl Display for LabelExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LabelExpression::Label(label) => write!(f, "{label}"),
+            LabelExpression::All(items) => display_label_items(f, items, ":"),
+            LabelExpression::Any(items) => display_label_items(f, items, "|"),
+            LabelExpression::Not(item) => match item.as_ref() {
+                LabelExpression::All(_) | LabelExpression::Any(_) => write!(f, "!({item})"),
+                _ => write!(f, "!{item}"),
+            },
+        }
+    }
+}
+
+/// Writes the items of a conjunction or disjunction of labels, with nested
+/// conjunctions and disjunctions in parentheses
+fn display_label_items(
+    f: &mut fmt::Formatter<'_>,
+    items: &[LabelExpression],
+    separator: &str,
+) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{separator}")?;
+        }
+        match item {
+            LabelExpression::All(_) | LabelExpression::Any(_) => write!(f, "({item})")?,
+            _ => write!(f, "{item}")?,
+        }
+    }
+    Ok(())
+}
+
+/// The direction of a [`RelationshipPattern`], relative to the pattern's
+/// left-to-right reading order
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub enum RelationshipDirection {
+    /// `(a)-[r]->(b)`
+    Outgoing,
+    /// `(a)<-[r]-(b)`
+    Incoming,
+    /// `(a)-[r]-(b)`, matching relationships in either direction
+    Undirected,
+}
+
+/// The number of hops of a variable-length relationship
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub enum RelationshipLength {
+    /// `*`, any number of hops
+    Any,
+    /// `*n`, exactly `n` hops
+    Exactly(u64),
+    /// `*min..max`, where either bound may be omitted
+    Range { min: Option<u64>, max: Option<u64> },
+}
+
+impl RelationshipLength {
+    /// The smallest number of hops, defaulting to 1
+    pub fn min_hops(&self) -> u64 {
+        match self {
+            RelationshipLength::Any | RelationshipLength::Range { min: None, .. } => 1,
+            RelationshipLength::Exactly(n) | RelationshipLength::Range { min: Some(n), .. } => *n,
+        }
+    }
+
+    /// The largest number of hops, or `None` if unbounded
+    pub fn max_hops(&self) -> Option<u64> {
+        match self {
+            RelationshipLength::Any => None,
+            RelationshipLength::Exactly(n) => Some(*n),
+            RelationshipLength::Range { max, .. } => *max,
+        }
+    }
+}
+
+impl Display for RelationshipLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "*")?;
+        match self {
+            RelationshipLength::Any => Ok(()),
+            RelationshipLength::Exactly(n) => write!(f, "{n}"),
+            RelationshipLength::Range { min, max } => {
+                if let Some(min) = min {
+                    write!(f, "{min}")?;
+                }
+                write!(f, "..")?;
+                if let Some(max) = max {
+                    write!(f, "{max}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A `key: value` entry of a property map such as `{name: 'Ann', age: 42}`
+#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "visitor", derive(Visit, VisitMut))]
+pub struct PropertyEntry {
+    pub key: Ident,
+    pub value: Expr,
+}
+
+impl Display for PropertyEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.value)
+    }
+}
+
+/// Writes the `var:Label {props}` part shared by node and relationship
+/// patterns, with the `*min..max` length of a relationship after its type
+fn display_pattern_filler(
+    f: &mut fmt::Formatter<'_>,
+    variable: &Option<Ident>,
+    label: &Option<LabelExpression>,
+    length: Option<&RelationshipLength>,
+    properties: &[PropertyEntry],
+) -> fmt::Result {
+    if let Some(variable) = variable {
+        write!(f, "{variable}")?;
+    }
+    if let Some(label) = label {
+        write!(f, ":{label}")?;
+    }
+    if let Some(length) = length {
+        write!(f, "{length}")?;
+    }
+    if !properties.is_empty() {
+        if variable.is_some() || label.is_some() || length.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{{{}}}", display_comma_separated(properties))?;
+    }
+    Ok(())
+}
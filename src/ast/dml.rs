@@ -29,8 +29,8 @@ use crate::display_utils::{indented_list, Indent, SpaceOrNewline};
 use super::{
     display_comma_separated, helpers::attached_token::AttachedToken, query::InputFormatClause,
     Assignment, Expr, FromTable, Ident, InsertAliases, MysqlInsertPriority, ObjectName, OnInsert,
-    OrderByExpr, Query, SelectItem, Setting, SqliteOnConflict, TableObject, TableWithJoins,
-    UpdateTableFromKind,
+    OrderByExpr, OutputClause, Query, SelectItem, Setting, SqliteOnConflict, TableObject,
+    TableWithJoins, UpdateTableFromKind,
 };
 
 /// INSERT statement.
@@ -54,6 +54,9 @@ pub struct Insert {
     pub columns: Vec<Ident>,
     /// Overwrite (Hive)
     pub overwrite: bool,
+    /// MSSQL `OUTPUT` clause, e.g. `OUTPUT INSERTED.id`
+    /// See: <https://learn.microsoft.com/en-us/sql/t-sql/queries/output-clause-transact-sql>
+    pub output: Option<OutputClause>,
     /// A SQL query that specifies what to insert
     pub source: Option<Box<Query>>,
     /// MySQL `INSERT INTO ... SET`
@@ -143,6 +146,11 @@ impl Display for Insert {
             SpaceOrNewline.fmt(f)?;
         }
 
+        if let Some(output) = &self.output {
+            output.fmt(f)?;
+            SpaceOrNewline.fmt(f)?;
+        }
+
         if let Some(source) = &self.source {
             source.fmt(f)?;
         } else if !self.assignments.is_empty() {
@@ -43,13 +43,15 @@ use serde::{Deserialize, Serialize};
 use sqlparser_derive::{Visit, VisitMut};
 
 use crate::{
-    display_utils::SpaceOrNewline,
-    tokenizer::{Span, Token},
-};
-use crate::{
+    desugar::{DesugarError, DesugarOptions, DesugaredStatement},
+    dialect::Dialect,
     display_utils::{Indent, NewLine},
     keywords::Keyword,
 };
+use crate::{
+    display_utils::SpaceOrNewline,
+    tokenizer::{Span, Token},
+};
 
 pub use self::data_type::{
     ArrayElemTypeDef, BinaryLength, CharLengthUnits, CharacterLength, DataType, EnumMember,
@@ -114,8 +116,15 @@ use crate::ast::helpers::stmt_data_loading::StageParamsObject;
 #[cfg(feature = "visitor")]
 pub use visitor::*;
 
+pub use self::cypher::{
+    CreateClause, CypherClause, CypherMergeClause, CypherQuery, CypherUnion, DeleteClause,
+    LabelExpression, MatchClause, NodePattern, PathPattern, PathSegment, ProjectionItem,
+    PropertyEntry, RelationshipDirection, RelationshipLength, RelationshipPattern, RemoveClause,
+    RemoveItem, ReturnClause, SetClause, SetItem, UnwindClause, WithClause,
+};
 pub use self::data_type::GeometricTypeKind;
 
+mod cypher;
 mod data_type;
 mod dcl;
 mod ddl;
@@ -1178,6 +1187,11 @@ pub enum Expr {
     Lambda(LambdaFunction),
     /// Checks membership of a value in a JSON array
     MemberOf(MemberOf),
+    /// A Cypher path pattern used as a predicate, true if the pattern has a
+    /// match, e.g. `WHERE (a)-[:FRIEND]->(:Admin)`
+    CypherPattern(Box<PathPattern>),
+    /// A Cypher existential subquery, e.g. `EXISTS { MATCH (a)-->(b) WHERE b.x = 1 }`
+    CypherExists(Box<MatchClause>),
 }
 
 impl Expr {
@@ -1964,6 +1978,8 @@ impl fmt::Display for Expr {
             Expr::Prior(expr) => write!(f, "PRIOR {expr}"),
             Expr::Lambda(lambda) => write!(f, "{lambda}"),
             Expr::MemberOf(member_of) => write!(f, "{member_of}"),
+            Expr::CypherPattern(pattern) => write!(f, "{pattern}"),
+            Expr::CypherExists(subquery) => write!(f, "EXISTS {{ {subquery} }}"),
         }
     }
 }
@@ -4278,853 +4294,127 @@ pub enum Statement {
     /// ```
     /// [PostgreSQL](https://www.postgresql.org/docs/current/sql-reset.html)
     Reset(ResetStatement),
-    /// Cypher MATCH statement
-    /// Example: MATCH (n:Label) RETURN n
-    CypherMatchNode {
-        node_name: String, 
-        label: Option<String>, 
-        properties: Option<Vec<(String, String)>>,
-        return_for_match: Option<Vec<String>>,
-    },
-    CypherMatchEdge {
-        from_node: String,
-        edge_name: String,
-        to_node: String,
-        rel_type: Option<String>,
-        return_for_match: Option<Vec<String>>,
-    },
-    CypherCreate{
-        node_name: String, 
-        label: Option<String>, 
-        properties: Option<Vec<(String, String)>>,
-    },
-    CypherReturn{
-        items: Vec<(String, Option<String>)>,
-    },
-    CypherDelete {
-        node_or_edge_name: String,  
-        is_edge: bool,              
-        detach: bool,
-        label: Option<String>,
-        rel_type: Option<String>,
-        properties: Option<Vec<(String, String)>>,
-    },
-    CypherCreateRelationship{
-        from_node: String,
-        from_label: Option<String>,
-        from_properties: Option<Vec<(String, String)>>,
-        to_node: String,
-        to_label: Option<String>,
-        to_properties: Option<Vec<(String, String)>>,
-        rel_type: String,
-        properties: Option<Vec<(String, String)>>,
-    },
-}
-
-fn handle_match_node(s: &Statement) -> Statement {
-    match s {
-        Statement::CypherMatchNode {
-            node_name: _,
-            label,
-            properties,
-            return_for_match: _,
-        } => {
-            let select_token = AttachedToken::empty();
-            let projection = vec![
-                SelectItem::Wildcard(
-                    WildcardAdditionalOptions::default()
-                ),
-            ];
-            let table = TableFactor::Table {
-                name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new("nodes"))]),
-                alias: None,
-                args: None,
-                with_hints: vec![],
-                version: None,
-                with_ordinality: false,
-                partitions: vec![],
-                json_path: None,
-                sample: None,
-                index_hints: vec![],
-            };
-            let table_with_joins = TableWithJoins {
-                relation: table,
-                joins: vec![],
-            };
-            let from = vec![table_with_joins];
-            
-            // Build WHERE clause
-            // Start with label condition if present
-            let mut conditions = Vec::new();
-            
-            if let Some(lbl) = label {
-                conditions.push(Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier(Ident::new("label"))),
-                    op: BinaryOperator::Eq,
-                    right: Box::new(Expr::Value(
-                        Value::SingleQuotedString(lbl.clone()).into()
-                    )),
-                });
-            }
-            
-            // Add property conditions if present
-            // MATCH (n:Bug {name: 'Ant'}) becomes:
-            // WHERE label = 'Bug' AND json_extract(properties, '$.name') = 'Ant'
-            if let Some(props) = properties {
-                for (prop_name, prop_value) in props {
-                    let json_path = format!("$.{}", prop_name);
-                    let json_extract = Expr::Function(Function {
-                        name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new("json_extract"))]),
-                        uses_odbc_syntax: false,
-                        parameters: FunctionArguments::None,
-                        args: FunctionArguments::List(FunctionArgumentList {
-                            duplicate_treatment: None,
-                            args: vec![
-                                FunctionArg::Unnamed(FunctionArgExpr::Expr(
-                                    Expr::Identifier(Ident::new("properties"))
-                                )),
-                                FunctionArg::Unnamed(FunctionArgExpr::Expr(
-                                    Expr::Value(Value::SingleQuotedString(json_path).into())
-                                )),
-                            ],
-                            clauses: vec![],
-                        }),
-                        filter: None,
-                        null_treatment: None,
-                        over: None,
-                        within_group: vec![],
-                    });
-                    
-                    conditions.push(Expr::BinaryOp {
-                        left: Box::new(json_extract),
-                        op: BinaryOperator::Eq,
-                        right: Box::new(Expr::Value(
-                            Value::SingleQuotedString(prop_value.clone()).into()
-                        )),
-                    });
-                }
-            }
-            
-            // Combine all conditions with AND
-            let selection = if conditions.is_empty() {
-                None
-            } else if conditions.len() == 1 {
-                Some(conditions.pop().unwrap())
-            } else {
-                // Build nested AND expressions
-                let mut expr = conditions.remove(0);
-                for cond in conditions {
-                    expr = Expr::BinaryOp {
-                        left: Box::new(expr),
-                        op: BinaryOperator::And,
-                        right: Box::new(cond),
-                    };
-                }
-                Some(expr)
-            };
-            
-            let select = Select {
-                select_token,
-                distinct: None,
-                top: None,
-                top_before_distinct: false,
-                projection,
-                exclude: None,
-                into: None,
-                from,
-                lateral_views: vec![],
-                prewhere: None,
-                selection,
-                group_by: GroupByExpr::Expressions(vec![], vec![]),
-                cluster_by: vec![],
-                distribute_by: vec![],
-                sort_by: vec![],
-                having: None,
-                named_window: vec![],
-                qualify: None,
-                window_before_qualify: false,
-                value_table_mode: None,
-                connect_by: None,
-                flavor: SelectFlavor::Standard,
-            };
-            let set_expr = SetExpr::Select(Box::new(select));
-            let query = Query {
-                with: None,
-                body: Box::new(set_expr),
-                order_by: None,
-                limit_clause: None,
-                fetch: None,
-                locks: vec![],
-                for_clause: None,
-                settings: None,
-                format_clause: None,
-                pipe_operators: vec![],
-            };
-            Statement::Query(Box::new(query))
-        }
-        _ => s.clone()
-    }
-}
-
-fn handle_create_node(s: &Statement) -> Statement {
-    match s {
-        Statement::CypherCreate {
-            node_name: _,
-            label,
-            properties,
-        } => {
-            // Build columns and corresponding values
-            let mut columns: Vec<Ident> = Vec::new();
-            let mut row: Vec<Expr> = Vec::new();
-
-            if let Some(lbl) = label {
-                columns.push(Ident::new("label"));
-                row.push(Expr::Value(Value::SingleQuotedString(lbl.clone()).into()));
-            }
-
-            if let Some(props) = properties {
-                // Convert properties Vec<(String,String)> into a JSON object string
-                let mut pairs: Vec<String> = Vec::new();
-                for (k, v) in props {
-                    // minimal escaping for double quotes and backslashes
-                    let key = k.replace('"', "\\\"").replace('\\', "\\\\");
-                    let val = v.replace('"', "\\\"").replace('\\', "\\\\");
-                    pairs.push(format!("\"{}\":\"{}\"", key, val));
-                }
-                let json = format!("{{{}}}", pairs.join(","));
-                columns.push(Ident::new("properties"));
-                row.push(Expr::Value(Value::SingleQuotedString(json).into()));
-            }
-
-            // Build VALUES rows
-            let rows = if row.is_empty() {
-                vec![vec![]]
-            } else {
-                vec![row]
-            };
-
-            let values = SetExpr::Values(Values {
-                explicit_row: false,
-                value_keyword: false,
-                rows,
-            });
-
-            let query = Query {
-                with: None,
-                body: Box::new(values),
-                order_by: None,
-                limit_clause: None,
-                fetch: None,
-                locks: vec![],
-                for_clause: None,
-                settings: None,
-                format_clause: None,
-                pipe_operators: vec![],
-            };
-
-            let insert = Insert {
-                insert_token: AttachedToken::empty(),
-                or: None,
-                ignore: false,
-                into: true,
-                table: TableObject::TableName(ObjectName(vec![ObjectNamePart::Identifier(Ident::new("nodes"))])),
-                table_alias: None,
-                columns,
-                overwrite: false,
-                source: Some(Box::new(query)),
-                assignments: vec![],
-                partitioned: None,
-                after_columns: vec![],
-                has_table_keyword: false,
-                on: None,
-                returning: None,
-                replace_into: false,
-                priority: None,
-                insert_alias: None,
-                settings: None,
-                format_clause: None,
-            };
-
-            Statement::Insert(insert)
-        }
-        _ => s.clone(),
-    }
-}
-
-fn handle_match_edge(s: &Statement) -> Statement {
-    match s {
-        Statement::CypherMatchEdge {
-            from_node: _,
-            edge_name: _,
-            to_node: _,
-            rel_type,
-            return_for_match: _,
-        } => {
-            let select_token = AttachedToken::empty();
-            let projection = vec![
-                SelectItem::Wildcard(
-                    WildcardAdditionalOptions::default()
-                ),
-            ];
-            let table = TableFactor::Table {
-                name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new("edges"))]),
-                alias: None,
-                args: None,
-                with_hints: vec![],
-                version: None,
-                with_ordinality: false,
-                partitions: vec![],
-                json_path: None,
-                sample: None,
-                index_hints: vec![],
-            };
-            let table_with_joins = TableWithJoins {
-                relation: table,
-                joins: vec![],
-            };
-            let from = vec![table_with_joins];
-            
-            // Build WHERE clause for relationship type if present
-            let selection = if let Some(rt) = rel_type {
-                Some(Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier(Ident::new("type"))),
-                    op: BinaryOperator::Eq,
-                    right: Box::new(Expr::Value(
-                        Value::SingleQuotedString(rt.clone()).into()
-                    )),
-                })
-            } else {
-                None
-            };
-            
-            let select = Select {
-                select_token,
-                distinct: None,
-                top: None,
-                top_before_distinct: false,
-                projection,
-                exclude: None,
-                into: None,
-                from,
-                lateral_views: vec![],
-                prewhere: None,
-                selection,
-                group_by: GroupByExpr::Expressions(vec![], vec![]),
-                cluster_by: vec![],
-                distribute_by: vec![],
-                sort_by: vec![],
-                having: None,
-                named_window: vec![],
-                qualify: None,
-                window_before_qualify: false,
-                value_table_mode: None,
-                connect_by: None,
-                flavor: SelectFlavor::Standard,
-            };
-
-            let query = Query {
-                with: None,
-                body: Box::new(SetExpr::Select(Box::new(select))),
-                order_by: None,
-                limit_clause: None,
-                fetch: None,
-                locks: vec![],
-                for_clause: None,
-                settings: None,
-                format_clause: None,
-                pipe_operators: vec![],
-            };
-
-            Statement::Query(Box::new(query))
-        }
-        _ => s.clone(),
-    }
-}
-
-fn handle_delete_node(s: &Statement) -> Statement {
-    match s {
-        Statement::CypherDelete {
-            node_or_edge_name: _,
-            is_edge,
-            detach: _,
-            label,
-            rel_type,
-            properties,
-        } => {
-            let table_name = if *is_edge {
-                "edges".to_string()
-            } else {
-                "nodes".to_string()
-            };
-            
-            // Build WHERE clause similar to handle_match_node
-            let mut conditions = Vec::new();
-            
-            if let Some(lbl) = label {
-                conditions.push(Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier(Ident::new("label"))),
-                    op: BinaryOperator::Eq,
-                    right: Box::new(Expr::Value(
-                        Value::SingleQuotedString(lbl.clone()).into()
-                    )),
-                });
-            }
-            
-            // Add relationship type condition if present
-            if let Some(rel) = rel_type {
-                conditions.push(Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier(Ident::new("type"))),
-                    op: BinaryOperator::Eq,
-                    right: Box::new(Expr::Value(
-                        Value::SingleQuotedString(rel.clone()).into()
-                    )),
-                });
-            }
-            
-            // Add property conditions if present
-            if let Some(props) = properties {
-                for (prop_name, prop_value) in props {
-                    let json_path = format!("$.{}", prop_name);
-                    let json_extract = Expr::Function(Function {
-                        name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new("json_extract"))]),
-                        uses_odbc_syntax: false,
-                        parameters: FunctionArguments::None,
-                        args: FunctionArguments::List(FunctionArgumentList {
-                            duplicate_treatment: None,
-                            args: vec![
-                                FunctionArg::Unnamed(FunctionArgExpr::Expr(
-                                    Expr::Identifier(Ident::new("properties"))
-                                )),
-                                FunctionArg::Unnamed(FunctionArgExpr::Expr(
-                                    Expr::Value(Value::SingleQuotedString(json_path).into())
-                                )),
-                            ],
-                            clauses: vec![],
-                        }),
-                        filter: None,
-                        null_treatment: None,
-                        over: None,
-                        within_group: vec![],
-                    });
-                    
-                    conditions.push(Expr::BinaryOp {
-                        left: Box::new(json_extract),
-                        op: BinaryOperator::Eq,
-                        right: Box::new(Expr::Value(
-                            Value::SingleQuotedString(prop_value.clone()).into()
-                        )),
-                    });
-                }
-            }
-            
-            // Combine all conditions with AND
-            let selection = if conditions.is_empty() {
-                None
-            } else if conditions.len() == 1 {
-                Some(conditions.pop().unwrap())
-            } else {
-                // Build nested AND expressions
-                let mut expr = conditions.remove(0);
-                for cond in conditions {
-                    expr = Expr::BinaryOp {
-                        left: Box::new(expr),
-                        op: BinaryOperator::And,
-                        right: Box::new(cond),
-                    };
-                }
-                Some(expr)
-            };
-            
-            let table = TableFactor::Table {
-                name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new(&table_name))]),
-                alias: None,
-                args: None,
-                with_hints: vec![],
-                version: None,
-                with_ordinality: false,
-                partitions: vec![],
-                json_path: None,
-                sample: None,
-                index_hints: vec![],
-            };
-            
-            let table_with_joins = TableWithJoins {
-                relation: table,
-                joins: vec![],
-            };
-            
-            let delete = Delete {
-                delete_token: AttachedToken::empty(),
-                tables: vec![],
-                from: FromTable::WithFromKeyword(vec![table_with_joins]),
-                using: None,
-                selection,
-                returning: None,
-                order_by: vec![],
-                limit: None,
-            };
-            
-            Statement::Delete(delete)
-        }
-        _ => s.clone(),
-    }
-}
-
-fn handle_create_relationship(s: &Statement) -> Statement {
-    match s {
-        Statement::CypherCreateRelationship {
-            from_node,
-            from_label,
-            from_properties,
-            to_node,
-            to_label,
-            to_properties,
-            rel_type,
-            properties: _,
-        } => {
-            // Build WHERE conditions for the from_node
-            let mut from_conditions = Vec::new();
-            
-            if let Some(label) = from_label {
-                from_conditions.push(Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier(Ident::new("label"))),
-                    op: BinaryOperator::Eq,
-                    right: Box::new(Expr::Value(
-                        Value::SingleQuotedString(label.clone()).into()
-                    )),
-                });
-            }
-            
-            if let Some(props) = from_properties {
-                for (prop_name, prop_value) in props {
-                    let json_path = format!("$.{}", prop_name);
-                    let json_extract = Expr::Function(Function {
-                        name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new("json_extract"))]),
-                        uses_odbc_syntax: false,
-                        parameters: FunctionArguments::None,
-                        args: FunctionArguments::List(FunctionArgumentList {
-                            duplicate_treatment: None,
-                            args: vec![
-                                FunctionArg::Unnamed(FunctionArgExpr::Expr(
-                                    Expr::Identifier(Ident::new("properties"))
-                                )),
-                                FunctionArg::Unnamed(FunctionArgExpr::Expr(
-                                    Expr::Value(Value::SingleQuotedString(json_path).into())
-                                )),
-                            ],
-                            clauses: vec![],
-                        }),
-                        filter: None,
-                        null_treatment: None,
-                        over: None,
-                        within_group: vec![],
-                    });
-                    
-                    from_conditions.push(Expr::BinaryOp {
-                        left: Box::new(json_extract),
-                        op: BinaryOperator::Eq,
-                        right: Box::new(Expr::Value(
-                            Value::SingleQuotedString(prop_value.clone()).into()
-                        )),
-                    });
-                }
-            }
-            
-            // Combine from_conditions with AND
-            let from_where = if from_conditions.is_empty() {
-                None
-            } else if from_conditions.len() == 1 {
-                Some(from_conditions.pop().unwrap())
-            } else {
-                let mut expr = from_conditions.remove(0);
-                for cond in from_conditions {
-                    expr = Expr::BinaryOp {
-                        left: Box::new(expr),
-                        op: BinaryOperator::And,
-                        right: Box::new(cond),
-                    };
-                }
-                Some(expr)
-            };
-            
-            // Build WHERE conditions for the to_node (same logic)
-            let mut to_conditions = Vec::new();
-            
-            if let Some(label) = to_label {
-                to_conditions.push(Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier(Ident::new("label"))),
-                    op: BinaryOperator::Eq,
-                    right: Box::new(Expr::Value(
-                        Value::SingleQuotedString(label.clone()).into()
-                    )),
-                });
-            }
-            
-            if let Some(props) = to_properties {
-                for (prop_name, prop_value) in props {
-                    let json_path = format!("$.{}", prop_name);
-                    let json_extract = Expr::Function(Function {
-                        name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new("json_extract"))]),
-                        uses_odbc_syntax: false,
-                        parameters: FunctionArguments::None,
-                        args: FunctionArguments::List(FunctionArgumentList {
-                            duplicate_treatment: None,
-                            args: vec![
-                                FunctionArg::Unnamed(FunctionArgExpr::Expr(
-                                    Expr::Identifier(Ident::new("properties"))
-                                )),
-                                FunctionArg::Unnamed(FunctionArgExpr::Expr(
-                                    Expr::Value(Value::SingleQuotedString(json_path).into())
-                                )),
-                            ],
-                            clauses: vec![],
-                        }),
-                        filter: None,
-                        null_treatment: None,
-                        over: None,
-                        within_group: vec![],
-                    });
-                    
-                    to_conditions.push(Expr::BinaryOp {
-                        left: Box::new(json_extract),
-                        op: BinaryOperator::Eq,
-                        right: Box::new(Expr::Value(
-                            Value::SingleQuotedString(prop_value.clone()).into()
-                        )),
-                    });
-                }
-            }
-            
-            // Combine to_conditions with AND
-            let to_where = if to_conditions.is_empty() {
-                None
-            } else if to_conditions.len() == 1 {
-                Some(to_conditions.pop().unwrap())
-            } else {
-                let mut expr = to_conditions.remove(0);
-                for cond in to_conditions {
-                    expr = Expr::BinaryOp {
-                        left: Box::new(expr),
-                        op: BinaryOperator::And,
-                        right: Box::new(cond),
-                    };
-                }
-                Some(expr)
-            };
-            
-            // Build FROM clause for from_node subquery
-            let from_table = TableFactor::Table {
-                name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new("nodes"))]),
-                alias: None,
-                args: None,
-                with_hints: vec![],
-                version: None,
-                with_ordinality: false,
-                partitions: vec![],
-                json_path: None,
-                sample: None,
-                index_hints: vec![],
-            };
-            
-            let from_table_with_joins = TableWithJoins {
-                relation: from_table,
-                joins: vec![],
-            };
-            
-            // Create subquery for from_node: (SELECT id FROM nodes WHERE ...)
-            let from_subquery = Query {
-                with: None,
-                body: Box::new(SetExpr::Select(Box::new(Select {
-                    select_token: AttachedToken::empty(),
-                    distinct: None,
-                    top: None,
-                    top_before_distinct: false,
-                    projection: vec![
-                        SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("id"))),
-                    ],
-                    exclude: None,
-                    into: None,
-                    from: vec![from_table_with_joins],
-                    lateral_views: vec![],
-                    prewhere: None,
-                    selection: from_where,
-                    group_by: GroupByExpr::Expressions(vec![], vec![]),
-                    cluster_by: vec![],
-                    distribute_by: vec![],
-                    sort_by: vec![],
-                    having: None,
-                    named_window: vec![],
-                    qualify: None,
-                    window_before_qualify: false,
-                    value_table_mode: None,
-                    connect_by: None,
-                    flavor: SelectFlavor::Standard,
-                }))),
-                order_by: None,
-                limit_clause: None,
-                fetch: None,
-                locks: vec![],
-                for_clause: None,
-                settings: None,
-                format_clause: None,
-                pipe_operators: vec![],
-            };
-            
-            // Build FROM clause for to_node subquery
-            let to_table = TableFactor::Table {
-                name: ObjectName(vec![ObjectNamePart::Identifier(Ident::new("nodes"))]),
-                alias: None,
-                args: None,
-                with_hints: vec![],
-                version: None,
-                with_ordinality: false,
-                partitions: vec![],
-                json_path: None,
-                sample: None,
-                index_hints: vec![],
-            };
-            
-            let to_table_with_joins = TableWithJoins {
-                relation: to_table,
-                joins: vec![],
-            };
-            
-            // Create subquery for to_node: (SELECT id FROM nodes WHERE ...)
-            let to_subquery = Query {
-                with: None,
-                body: Box::new(SetExpr::Select(Box::new(Select {
-                    select_token: AttachedToken::empty(),
-                    distinct: None,
-                    top: None,
-                    top_before_distinct: false,
-                    projection: vec![
-                        SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("id"))),
-                    ],
-                    exclude: None,
-                    into: None,
-                    from: vec![to_table_with_joins],
-                    lateral_views: vec![],
-                    prewhere: None,
-                    selection: to_where,
-                    group_by: GroupByExpr::Expressions(vec![], vec![]),
-                    cluster_by: vec![],
-                    distribute_by: vec![],
-                    sort_by: vec![],
-                    having: None,
-                    named_window: vec![],
-                    qualify: None,
-                    window_before_qualify: false,
-                    value_table_mode: None,
-                    connect_by: None,
-                    flavor: SelectFlavor::Standard,
-                }))),
-                order_by: None,
-                limit_clause: None,
-                fetch: None,
-                locks: vec![],
-                for_clause: None,
-                settings: None,
-                format_clause: None,
-                pipe_operators: vec![],
-            };
-            
-            // Build VALUES rows with the subqueries
-            let rows = vec![vec![
-                Expr::Subquery(Box::new(from_subquery)),
-                Expr::Subquery(Box::new(to_subquery)),
-                Expr::Value(Value::SingleQuotedString(rel_type.clone()).into()),
-                Expr::Value(Value::SingleQuotedString("{}".to_string()).into()),
-            ]];
-            
-            let table_name = ObjectName(vec![
-                ObjectNamePart::Identifier(Ident::new("edges"))
-            ]);
-
-            
-            let insert = Insert {
-                insert_token: AttachedToken::empty(),
-                or: None,
-                ignore: false,
-                into: true,
-                table: TableObject::TableName(table_name),
-                table_alias: None,
-                columns: vec![
-                    Ident::new("src_id"),
-                    Ident::new("dst_id"),
-                    Ident::new("type"),
-                    Ident::new("properties"),
-                ],
-                overwrite: false,
-                source: Some(Box::new(Query {
-                    with: None,
-                    body: Box::new(SetExpr::Values(Values {
-                        explicit_row: false,
-                        value_keyword: false,
-                        rows,
-                    })),
-                    order_by: None,
-                    limit_clause: None,
-                    fetch: None,
-                    locks: vec![],
-                    for_clause: None,
-                    settings: None,
-                    format_clause: None,
-                    pipe_operators: vec![],
-                })),
-                assignments: vec![],
-                partitioned: None,
-                after_columns: vec![],
-                has_table_keyword: false,
-                on: None,
-                returning: None,
-                replace_into: false,
-                priority: None,
-                insert_alias: None,
-                settings: None,
-                format_clause: None,
-            };
-            
-            Statement::Insert(insert)
-        }
-        _ => s.clone(),
-    }
+    /// An [openCypher](https://opencypher.org/) graph query, e.g.
+    ///
+    /// ```text
+    /// MATCH (n:Person {name: 'Ann'}) RETURN n
+    /// ```
+    ///
+    /// Only parsed by [`CypherDialect`](crate::dialect::CypherDialect).
+    /// See [`Statement::desugar_cypher_to_sql`] to translate it to SQL.
+    Cypher(CypherQuery),
 }
 
 impl Statement {
-    pub fn desugar_cypher_to_sql(&self) -> Self {
+    /// Translates a [`Statement::Cypher`] query into equivalent SQL
+    /// statements over the `nodes` and `edges` tables, to be run in order by
+    /// a database speaking `dialect`. Most queries become a single statement,
+    /// but e.g. `DETACH DELETE` deletes the relationships of a node before
+    /// the node. Any other statement is returned unchanged.
+    ///
+    /// Creating a relationship between nodes created by the same `CREATE`
+    /// needs their generated ids, which Snowflake and BigQuery cannot return,
+    /// so it is [`DesugarError::Unsupported`] in those dialects.
+    ///
+    /// ```
+    /// # use sqlparser::dialect::{CypherDialect, PostgreSqlDialect};
+    /// # use sqlparser::parser::Parser;
+    /// let cypher = "MATCH (n:Person) RETURN n.name";
+    /// let statements = Parser::parse_sql(&CypherDialect {}, cypher).unwrap();
+    /// let sql = statements[0].desugar_cypher_to_sql(&PostgreSqlDialect {}).unwrap();
+    /// assert_eq!(
+    ///     sql[0].to_string(),
+    ///     "SELECT n.properties ->> 'name' AS \"n.name\" FROM nodes AS n WHERE n.label = 'Person'"
+    /// );
+    /// ```
+    pub fn desugar_cypher_to_sql(
+        &self,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, DesugarError> {
+        self.desugar_cypher_to_sql_with_options(dialect, &DesugarOptions::default())
+    }
+
+    /// Like [`Statement::desugar_cypher_to_sql`], with the translation
+    /// controlled by `options`.
+    pub fn desugar_cypher_to_sql_with_options(
+        &self,
+        dialect: &dyn Dialect,
+        options: &DesugarOptions,
+    ) -> Result<Vec<Statement>, DesugarError> {
+        Ok(self
+            .desugar_cypher(dialect, options)?
+            .into_iter()
+            .map(|desugared| desugared.statement)
+            .collect())
+    }
+
+    /// Like [`Statement::desugar_cypher_to_sql_with_options`], also returning
+    /// the names of the parameters of each statement in the order its
+    /// placeholders are bound.
+    ///
+    /// PostgreSQL, DuckDB, MS SQL and BigQuery read properties stored in JSON
+    /// as text, which is cast to a number when ordered against a parameter,
+    /// as in `n.age > $min`. A parameter compared for equality, as in
+    /// `n.age = $age` or `{id: $id}`, may be a string, so the property is
+    /// compared as text, and the parameter must be passed as text, e.g.
+    /// `'42'`.
+    ///
+    /// The statements of a query are only atomic when run in one
+    /// transaction. Without an upsert to use, i.e. in dialects other than
+    /// Snowflake, MS SQL and BigQuery when the properties of a `MERGE`
+    /// pattern are not covered by a unique index, `MERGE` updates the
+    /// matching nodes, then inserts the node unless one matches. A
+    /// concurrent `MERGE` of the same node can insert it in between, so
+    /// both statements must run in one serializable transaction, or with the
+    /// table locked, to create the node only once.
+    ///
+    /// A node stored in a table with a label column has at most one label,
+    /// as with the default `nodes` table. Adding a label with `SET n:Label`
+    /// would have to replace the label the node has, so it is
+    /// [`DesugarError::Unsupported`], unless the table only stores nodes with
+    /// that label.
+    ///
+    /// ```
+    /// # use sqlparser::desugar::{DesugarError, DesugarOptions};
+    /// # use sqlparser::dialect::{CypherDialect, GenericDialect};
+    /// # use sqlparser::parser::Parser;
+    /// let cypher = "MATCH (n) SET n.color = 'red', n:Insect";
+    /// let statements = Parser::parse_sql(&CypherDialect {}, cypher).unwrap();
+    /// assert!(matches!(
+    ///     statements[0].desugar_cypher(&GenericDialect {}, &DesugarOptions::default()),
+    ///     Err(DesugarError::Unsupported(_))
+    /// ));
+    /// ```
+    ///
+    /// ```
+    /// # use sqlparser::desugar::DesugarOptions;
+    /// # use sqlparser::dialect::{CypherDialect, MySqlDialect};
+    /// # use sqlparser::parser::Parser;
+    /// let cypher = "MATCH (n:User {id: $id}) RETURN n.name";
+    /// let statements = Parser::parse_sql(&CypherDialect {}, cypher).unwrap();
+    /// let desugared = &statements[0]
+    ///     .desugar_cypher(&MySqlDialect {}, &DesugarOptions::default())
+    ///     .unwrap()[0];
+    /// assert_eq!(
+    ///     desugared.statement.to_string(),
+    ///     "SELECT json_extract(n.properties, '$.name') AS `n.name` FROM nodes AS n \
+    ///      WHERE n.label = 'User' AND json_extract(n.properties, '$.id') = ?"
+    /// );
+    /// assert_eq!(desugared.parameters, ["id"]);
+    /// ```
+    pub fn desugar_cypher(
+        &self,
+        dialect: &dyn Dialect,
+        options: &DesugarOptions,
+    ) -> Result<Vec<DesugaredStatement>, DesugarError> {
         match self {
-            Statement::CypherMatchNode { 
-                node_name: _,
-                label: _,
-                properties: _,
-                return_for_match: _,
-            } => {
-                // construct an SQL Query obj
-                handle_match_node(self)
-            }
-            Statement::CypherMatchEdge {
-                from_node: _,
-                edge_name: _,
-                to_node: _,
-                rel_type: _,
-                return_for_match: _,
-            } => {
-                // construct a SELECT query from edges table
-                handle_match_edge(self)
-            }
-            Statement::CypherCreate { node_name: _, label: _, properties: _ } => {
-                // construct an INSERT statement
-                handle_create_node(self)
-            }
-            Statement::CypherDelete { node_or_edge_name: _, is_edge: _, detach: _, label: _, rel_type: _, properties: _ } => {
-                // construct a DELETE statement
-                handle_delete_node(self)
+            Statement::Cypher(query) => {
+                crate::desugar::desugar_cypher_query(query, dialect, options)
             }
-            Statement::CypherCreateRelationship { from_node: _, from_label: _, from_properties: _, to_node: _, to_label: _, to_properties: _, rel_type: _, properties: _ } => {
-                // construct an INSERT statement with subqueries
-                handle_create_relationship(self)
-            }
-            _ => self.clone(),
+            _ => Ok(vec![DesugaredStatement {
+                statement: self.clone(),
+                parameters: vec![],
+            }]),
         }
     }
 }
@@ -6626,89 +5916,7 @@ impl fmt::Display for Statement {
             Statement::Vacuum(s) => write!(f, "{s}"),
             Statement::AlterUser(s) => write!(f, "{s}"),
             Statement::Reset(s) => write!(f, "{s}"),
-            Statement::CypherMatchNode { node_name, label, properties, return_for_match } => {
-                // Convert to SQL SELECT
-                if let Some(props) = properties {
-                    // MATCH (n:Bug {name: 'Ant'}) -> WHERE with JSON query
-                    let where_clause = props.iter()
-                        .map(|(k, v)| format!("json_extract(properties, '$.{}') = '{}'", k, v))
-                        .collect::<Vec<_>>()
-                        .join(" AND ");
-                    
-                    if let Some(l) = label {
-                        write!(f, "SELECT * FROM nodes WHERE label = '{}' AND {}", l, where_clause)
-                    } else {
-                        write!(f, "SELECT * FROM nodes WHERE {}", where_clause)
-                    }
-                } else if let Some(l) = label {
-                    // MATCH (n:Bug) -> SELECT with label filter
-                    write!(f, "SELECT * FROM nodes WHERE label = '{}'", l)
-                } else {
-                    // MATCH (n) -> SELECT all
-                    write!(f, "SELECT * FROM nodes")
-                }
-            }
-
-            Statement::CypherMatchEdge { from_node, edge_name, to_node, rel_type, return_for_match } => {
-                // Convert to SQL JOIN
-                if let Some(rt) = rel_type {
-                    write!(f, "SELECT e.* FROM edges e WHERE e.type = '{}'", rt)
-                } else {
-                    write!(f, "SELECT * FROM edges")
-                }
-            }
-
-            Statement::CypherCreate { node_name, label, properties } => {
-                let default_label = "Node".to_string();
-                let label_str = label.as_ref().unwrap_or(&default_label);
-
-                
-                if let Some(props) = properties {
-                    let json_props = props.iter()
-                        .map(|(k, v)| format!("\"{}\":\"{}\"", k, v))
-                        .collect::<Vec<_>>()
-                        .join(",");
-                    
-                    write!(f, "INSERT INTO nodes (label, properties) VALUES ('{}', '{{{}}}'); SELECT * FROM nodes WHERE id = last_insert_rowid()", label_str, json_props)
-                } else {
-                    write!(f, "INSERT INTO nodes (label, properties) VALUES ('{}', '{{}}'); SELECT * FROM nodes WHERE id = last_insert_rowid()", label_str)
-                }
-            }
-
-            Statement::CypherReturn { items } => {
-                write!(f, "SELECT ")?;
-                for (i, (expr, alias)) in items.iter().enumerate() {
-                    if i > 0 { write!(f, ", ")?; }
-                    write!(f, "{}", expr)?;
-                    if let Some(a) = alias {
-                        write!(f, " AS {}", a)?;
-                    }
-                }
-                Ok(())
-            }
-
-            Statement::CypherDelete { node_or_edge_name, is_edge, detach, label, rel_type, properties } => {
-                if *is_edge {
-                    write!(f, "DELETE FROM edges WHERE id = ?")  // Will need variable binding
-                } else if *detach {
-                    write!(f, "DELETE FROM edges WHERE src_id = ? OR dst_id = ?; DELETE FROM nodes WHERE id = ?")
-                } else {
-                    write!(f, "DELETE FROM nodes WHERE id = ?")
-                }
-            }
-
-            Statement::CypherCreateRelationship { from_node, from_label, from_properties, to_node, to_label, to_properties, rel_type, properties } => {
-                if let Some(props) = properties {
-                    let json_props = props.iter()
-                        .map(|(k, v)| format!("\"{}\":\"{}\"", k, v))
-                        .collect::<Vec<_>>()
-                        .join(",");
-                    
-                    write!(f, "INSERT INTO edges (src_id, dst_id, type, properties) VALUES (?, ?, '{}', '{{{}}}')", rel_type, json_props)
-                } else {
-                    write!(f, "INSERT INTO edges (src_id, dst_id, type, properties) VALUES (?, ?, '{}', '{{}}')", rel_type)
-                }
-            }
+            Statement::Cypher(query) => write!(f, "{query}"),
         }
     }
 }
@@ -329,6 +329,16 @@ pub enum BinaryOperator {
     /// ':=' Assignment Operator
     /// See <https://dev.mysql.com/doc/refman/8.4/en/assignment-operators.html#operator_assign-value>
     Assignment,
+    /// String prefix match, e.g. `a STARTS WITH b` (Cypher-specific)
+    CypherStartsWith,
+    /// String suffix match, e.g. `a ENDS WITH b` (Cypher-specific)
+    CypherEndsWith,
+    /// Substring match, e.g. `a CONTAINS b` (Cypher-specific)
+    CypherContains,
+    /// String matches regular expression, e.g. `a =~ b` (Cypher-specific)
+    CypherRegexMatch,
+    /// List membership, e.g. `a IN [1, 2, 3]` (Cypher-specific)
+    CypherIn,
 }
 
 impl fmt::Display for BinaryOperator {
@@ -405,6 +415,11 @@ impl fmt::Display for BinaryOperator {
             BinaryOperator::At => f.write_str("@"),
             BinaryOperator::TildeEq => f.write_str("~="),
             BinaryOperator::Assignment => f.write_str(":="),
+            BinaryOperator::CypherStartsWith => f.write_str("STARTS WITH"),
+            BinaryOperator::CypherEndsWith => f.write_str("ENDS WITH"),
+            BinaryOperator::CypherContains => f.write_str("CONTAINS"),
+            BinaryOperator::CypherRegexMatch => f.write_str("=~"),
+            BinaryOperator::CypherIn => f.write_str("IN"),
         }
     }
 }
@@ -36,8 +36,8 @@ use super::{
     JoinConstraint, JoinOperator, JsonPath, JsonPathElem, LateralView, LimitClause,
     MatchRecognizePattern, Measure, NamedParenthesizedList, NamedWindowDefinition, ObjectName,
     ObjectNamePart, Offset, OnConflict, OnConflictAction, OnInsert, OpenStatement, OrderBy,
-    OrderByExpr, OrderByKind, Partition, PivotValueSource, ProjectionSelect, Query, RaiseStatement,
-    RaiseStatementValue, ReferentialAction, RenameSelectItem, ReplaceSelectElement,
+    OrderByExpr, OrderByKind, OutputClause, Partition, PivotValueSource, ProjectionSelect, Query,
+    RaiseStatement, RaiseStatementValue, ReferentialAction, RenameSelectItem, ReplaceSelectElement,
     ReplaceSelectItem, Select, SelectInto, SelectItem, SetExpr, SqlOption, Statement, Subscript,
     SymbolDefinition, TableAlias, TableAliasColumnDef, TableConstraint, TableFactor, TableObject,
     TableOptionsClustered, TableWithJoins, Update, UpdateTableFromKind, Use, Value, Values,
@@ -477,12 +477,7 @@ impl Spanned for Statement {
             Statement::Vacuum(..) => Span::empty(),
             Statement::AlterUser(..) => Span::empty(),
             Statement::Reset(..) => Span::empty(),
-            Statement::CypherMatchNode { .. } => Span::empty(),
-            Statement::CypherMatchEdge { .. } => Span::empty(),
-            Statement::CypherCreate { .. } => Span::empty(),
-            Statement::CypherReturn { .. } => Span::empty(),
-            Statement::CypherDelete { .. } => Span::empty(),
-            Statement::CypherCreateRelationship { .. } => Span::empty(),
+            Statement::Cypher(..) => Span::empty(),
         }
     }
 }
@@ -1236,6 +1231,7 @@ impl Spanned for Insert {
             table_alias,
             columns,
             overwrite: _, // bool
+            output,
             source,
             partitioned,
             after_columns,
@@ -1255,6 +1251,7 @@ impl Spanned for Insert {
                 .chain(core::iter::once(table.span()))
                 .chain(table_alias.as_ref().map(|i| i.span))
                 .chain(columns.iter().map(|i| i.span))
+                .chain(output.as_ref().map(|i| i.span()))
                 .chain(source.as_ref().map(|q| q.span()))
                 .chain(assignments.iter().map(|i| i.span()))
                 .chain(partitioned.iter().flat_map(|i| i.iter().map(|k| k.span())))
@@ -1355,6 +1352,8 @@ impl Spanned for AssignmentTarget {
 /// - [Expr::Dictionary] # DuckDB specific
 /// - [Expr::Map] # DuckDB specific
 /// - [Expr::Lambda]
+/// - [Expr::CypherPattern] # Cypher specific
+/// - [Expr::CypherExists] # Cypher specific
 impl Spanned for Expr {
     fn span(&self) -> Span {
         match self {
@@ -1557,6 +1556,8 @@ impl Spanned for Expr {
             Expr::Prior(expr) => expr.span(),
             Expr::Lambda(_) => Span::empty(),
             Expr::MemberOf(member_of) => member_of.value.span().union(&member_of.array.span()),
+            Expr::CypherPattern(_) => Span::empty(),
+            Expr::CypherExists(_) => Span::empty(),
         }
     }
 }
@@ -2264,6 +2265,29 @@ impl Spanned for SelectInto {
     }
 }
 
+/// # partial span
+///
+/// Missing spans:
+/// - the `OUTPUT` or `RETURNING` keyword
+impl Spanned for OutputClause {
+    fn span(&self) -> Span {
+        match self {
+            OutputClause::Output {
+                select_items,
+                into_table,
+            } => union_spans(
+                select_items
+                    .iter()
+                    .map(|i| i.span())
+                    .chain(into_table.as_ref().map(|i| i.span())),
+            ),
+            OutputClause::Returning { select_items } => {
+                union_spans(select_items.iter().map(|i| i.span()))
+            }
+        }
+    }
+}
+
 impl Spanned for UpdateTableFromKind {
     fn span(&self) -> Span {
         let from = match self {
@@ -2365,7 +2389,7 @@ impl Spanned for AlterTable {
 
 #[cfg(test)]
 pub mod tests {
-    use crate::dialect::{Dialect, GenericDialect, SnowflakeDialect};
+    use crate::dialect::{Dialect, GenericDialect, MsSqlDialect, SnowflakeDialect};
     use crate::parser::Parser;
     use crate::tokenizer::Span;
 
@@ -2515,6 +2539,24 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_insert_output_span() {
+        let dialect = &MsSqlDialect {};
+        let mut test = SpanTest::new(
+            dialect,
+            "INSERT INTO nodes (label) OUTPUT INSERTED.id AS a_id VALUES ('Bug')",
+        );
+        let Statement::Insert(insert) = test.0.parse_statement().unwrap() else {
+            panic!("expected insert");
+        };
+
+        // 'OUTPUT' missing
+        assert_eq!(
+            test.get_source(insert.output.unwrap().span()),
+            "INSERTED.id AS a_id"
+        );
+    }
+
     #[test]
     fn test_placeholder_span() {
         let sql = "\nSELECT\n  :fooBar";
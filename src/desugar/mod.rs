@@ -0,0 +1,4449 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Translation of [`CypherQuery`] ASTs into SQL [`Statement`]s.
+//!
+//! By default the graph is stored in two tables:
+//!
+//! ```sql
+//! CREATE TABLE nodes (id INTEGER PRIMARY KEY, label TEXT, properties TEXT);
+//! CREATE TABLE edges (id INTEGER PRIMARY KEY, src_id INTEGER, dst_id INTEGER, type TEXT, properties TEXT);
+//! ```
+//!
+//! where `properties` holds a JSON object. Other layouts are described by a
+//! [`GraphSchema`] passed in the [`DesugarOptions`]. See
+//! [`Statement::desugar_cypher_to_sql`] for the entry point.
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+
+mod parameters;
+mod schema;
+use self::parameters::{bind_parameters, pattern_parameters, ParameterStyle};
+pub use self::schema::{EdgeTable, GraphSchema, NodeTable, PropertyColumn, PropertyColumns};
+
+use crate::ast::helpers::attached_token::AttachedToken;
+use crate::ast::helpers::stmt_create_table::CreateTableBuilder;
+use crate::ast::{
+    display_separated, ArrayElemTypeDef, Assignment, AssignmentTarget, BinaryOperator, CaseWhen,
+    CastKind, CharacterLength, ConflictTarget, CreateClause, Cte, CypherClause, CypherMergeClause,
+    CypherQuery, DataType, Delete, DeleteClause, Distinct, DoUpdate, ExactNumberInfo, Expr, Fetch,
+    FromTable, Function, FunctionArg, FunctionArgExpr, FunctionArgOperator, FunctionArgumentList,
+    FunctionArguments, GroupByExpr, Ident, Insert, Join, JoinConstraint, JoinOperator, JsonPath,
+    JsonPathElem, LabelExpression, LimitClause, MatchClause, MergeAction, MergeClause,
+    MergeClauseKind, MergeInsertExpr, MergeInsertKind, NodePattern, ObjectName, ObjectNamePart,
+    ObjectType, Offset, OffsetRows, OnConflict, OnConflictAction, OnInsert, OrderBy, OrderByExpr,
+    OrderByKind, OrderByOptions, OutputClause, PathPattern, PathSegment, ProjectionItem,
+    PropertyEntry, Query, RelationshipDirection, RelationshipLength, RelationshipPattern,
+    RemoveItem, ReturnClause, Select, SelectFlavor, SelectInto, SelectItem,
+    SelectItemQualifiedWildcardKind, SetExpr, SetItem, SetOperator, SetQuantifier, Statement,
+    TableAlias, TableAliasColumnDef, TableFactor, TableObject, TableWithJoins, Top, TopQuantity,
+    TypedString, UnaryOperator, UnwindClause, Update, Value, Values, WildcardAdditionalOptions,
+    With, WithClause,
+};
+use crate::dialect::{
+    BigQueryDialect, Dialect, DuckDbDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect,
+    SnowflakeDialect,
+};
+use crate::keywords::ALL_KEYWORDS;
+
+/// Error returned when a Cypher query cannot be translated to SQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DesugarError {
+    /// The query uses a Cypher construct that has no SQL translation
+    Unsupported(String),
+    /// The query refers to a variable that no pattern binds
+    UnboundVariable(String),
+    /// The query is not valid Cypher, e.g. the queries combined by `UNION`
+    /// return different columns
+    InvalidQuery(String),
+}
+
+impl fmt::Display for DesugarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DesugarError::Unsupported(s) => write!(f, "unsupported Cypher query: {s}"),
+            DesugarError::UnboundVariable(s) => write!(f, "variable `{s}` is not defined"),
+            DesugarError::InvalidQuery(s) => write!(f, "invalid Cypher query: {s}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DesugarError {}
+
+/// One of the SQL statements a Cypher query is translated to, see
+/// [`Statement::desugar_cypher`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesugaredStatement {
+    /// The SQL statement
+    pub statement: Statement,
+    /// The names of the Cypher parameters bound to the placeholders of
+    /// `statement`: `id` for `$id`. For `?` placeholders, there is one name
+    /// per placeholder in the order they occur; for `$1` placeholders, the
+    /// `n`th name is bound to `$n`; for `@id` placeholders, each name occurs
+    /// once. A name can also be bound to the value returned by an earlier
+    /// statement under that name, e.g. `a_id` for
+    /// `INSERT ... RETURNING id AS a_id`.
+    pub parameters: Vec<String>,
+}
+
+/// Options that control how [`Statement::desugar_cypher_to_sql_with_options`]
+/// translates Cypher to SQL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesugarOptions {
+    /// The SQL aggregate function that Cypher's `collect()` is translated
+    /// to. Default is `json_group_array`.
+    pub collect_function: String,
+    /// The tables storing the graph
+    pub schema: GraphSchema,
+}
+
+impl Default for DesugarOptions {
+    fn default() -> Self {
+        Self {
+            collect_function: "json_group_array".to_string(),
+            schema: GraphSchema::default(),
+        }
+    }
+}
+
+impl DesugarOptions {
+    /// Create a new [`DesugarOptions`]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the SQL aggregate function that `collect(x)` is translated to,
+    /// e.g. `array_agg` for PostgreSQL or `json_agg` to build a JSON array.
+    pub fn with_collect_function(mut self, collect_function: &str) -> Self {
+        self.collect_function = collect_function.to_string();
+        self
+    }
+
+    /// Set the tables that nodes, relationships and their properties are
+    /// stored in. See [`GraphSchema`].
+    pub fn with_schema(mut self, schema: GraphSchema) -> Self {
+        self.schema = schema;
+        self
+    }
+}
+
+/// The Cypher aggregate functions, which make `RETURN` group by its other items
+const AGGREGATE_FUNCTIONS: [&str; 6] = ["count", "sum", "avg", "min", "max", "collect"];
+
+/// Translate a Cypher query into SQL statements to run in order, with the
+/// parameters, e.g. `$id`, replaced by the placeholders of `dialect`.
+pub(crate) fn desugar_cypher_query(
+    query: &CypherQuery,
+    dialect: &dyn Dialect,
+    options: &DesugarOptions,
+) -> Result<Vec<DesugaredStatement>, DesugarError> {
+    let style = ParameterStyle::of(dialect);
+    Ok(desugar_statements(query, dialect, options)?
+        .into_iter()
+        .map(|mut statement| {
+            // the parameters are bound in the order they occur in the SQL,
+            // which can differ from the Cypher query
+            let parameters = bind_parameters(&mut statement, style);
+            DesugaredStatement {
+                statement,
+                parameters,
+            }
+        })
+        .collect())
+}
+
+/// Translates `query`, leaving its parameters as `$name`
+fn desugar_statements(
+    query: &CypherQuery,
+    dialect: &dyn Dialect,
+    options: &DesugarOptions,
+) -> Result<Vec<Statement>, DesugarError> {
+    if !query.unions.is_empty() {
+        return Ok(vec![desugar_union(dialect, options, query)?]);
+    }
+    match query.clauses.as_slice() {
+        [CypherClause::Match(m), CypherClause::Delete(d)] => desugar_delete(dialect, options, m, d),
+        [reading @ .., CypherClause::Create(c)]
+            if !reading.is_empty()
+                && reading
+                    .iter()
+                    .all(|c| matches!(c, CypherClause::Match(_) | CypherClause::Unwind(_))) =>
+        {
+            desugar_create_from(dialect, options, reading, c)
+        }
+        [CypherClause::Create(c)] => desugar_create(dialect, options, c),
+        [CypherClause::Merge(mc)] => desugar_merge(dialect, options, mc),
+        [CypherClause::Match(m), updates @ ..]
+            if !updates.is_empty()
+                && updates
+                    .iter()
+                    .all(|c| matches!(c, CypherClause::Set(_) | CypherClause::Remove(_))) =>
+        {
+            desugar_update(dialect, options, m, updates)
+        }
+        _ => {
+            let query = desugar_read_query(dialect, options, &query.clauses, &mut 0)?;
+            Ok(vec![Statement::Query(Box::new(query))])
+        }
+    }
+}
+
+/// `MATCH (a)-[r]->(b) RETURN a, b` selects from `nodes AS a`, joined to
+/// `edges AS r` and `nodes AS b`. Each `WITH` ends a part of the query, which
+/// becomes a CTE that the next part selects from.
+///
+/// Generated aliases are numbered from `anonymous`, which is left at the
+/// last number used.
+fn desugar_read_query(
+    dialect: &dyn Dialect,
+    options: &DesugarOptions,
+    clauses: &[CypherClause],
+    anonymous: &mut usize,
+) -> Result<Query, DesugarError> {
+    let unsupported = || DesugarError::Unsupported(display_separated(clauses, " ").to_string());
+    let (last, clauses) = clauses.split_last().ok_or_else(unsupported)?;
+    let mut scope = MatchScope::new(dialect, options);
+    scope.anonymous = *anonymous;
+    for clause in clauses {
+        match clause {
+            CypherClause::Match(m) => scope.add_match(m)?,
+            CypherClause::Unwind(u) => scope.add_unwind(u)?,
+            CypherClause::With(w) => scope = scope.into_next_part(w)?,
+            _ => return Err(unsupported()),
+        }
+    }
+    match last {
+        CypherClause::Return(r) => {
+            let projection = r
+                .items
+                .iter()
+                .map(|item| scope.projection_item(item))
+                .collect::<Result<_, _>>()?;
+            *anonymous = scope.anonymous;
+            project(scope, projection, r.into())
+        }
+        CypherClause::Match(m) => {
+            scope.add_match(m)?;
+            *anonymous = scope.anonymous;
+            let projection = vec![SelectItem::Wildcard(WildcardAdditionalOptions::default())];
+            Ok(scope.into_query(projection))
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+/// `... RETURN a.name AS name UNION [ALL] ... RETURN b.name AS name`
+/// combines the rows of the queries with `UNION [ALL]`, once each is checked
+/// to return the same columns. The CTEs of all queries are moved to the
+/// combined query, and a query with `ORDER BY`, `SKIP` or `LIMIT` is selected
+/// from as a derived table, as neither is allowed in the operands of a
+/// `UNION` by e.g. SQLite.
+fn desugar_union(
+    dialect: &dyn Dialect,
+    options: &DesugarOptions,
+    cypher: &CypherQuery,
+) -> Result<Statement, DesugarError> {
+    let first = cypher.unions[0].all;
+    if cypher.unions.iter().any(|union| union.all != first) {
+        return Err(DesugarError::InvalidQuery(format!(
+            "UNION mixed with UNION ALL in {cypher}"
+        )));
+    }
+    let parts = core::iter::once(cypher.clauses.as_slice())
+        .chain(cypher.unions.iter().map(|union| union.clauses.as_slice()));
+    let mut columns: Option<Vec<String>> = None;
+    let mut anonymous = 0;
+    let mut ctes = vec![];
+    let mut recursive = false;
+    let mut body: Option<SetExpr> = None;
+    for clauses in parts {
+        let Some(CypherClause::Return(r)) = clauses.last() else {
+            return Err(DesugarError::InvalidQuery(format!(
+                "UNION of a query without RETURN in {cypher}"
+            )));
+        };
+        let names = r
+            .items
+            .iter()
+            .map(|item| match &item.alias {
+                Some(alias) => alias.value.clone(),
+                None => item.expr.to_string(),
+            })
+            .collect::<Vec<_>>();
+        match &columns {
+            None => columns = Some(names),
+            Some(expected) if expected.len() != names.len() => {
+                return Err(DesugarError::InvalidQuery(format!(
+                    "UNION of queries returning {} and {} columns",
+                    expected.len(),
+                    names.len()
+                )))
+            }
+            Some(expected) if *expected != names => {
+                return Err(DesugarError::InvalidQuery(format!(
+                    "UNION of queries returning columns {} and {}",
+                    expected.join(", "),
+                    names.join(", ")
+                )))
+            }
+            Some(_) => {}
+        }
+
+        let mut part = desugar_read_query(dialect, options, clauses, &mut anonymous)?;
+        if let Some(with) = part.with.take() {
+            ctes.extend(with.cte_tables);
+            recursive |= with.recursive;
+        }
+        let part = if part.order_by.is_some() || part.limit_clause.is_some() {
+            anonymous += 1;
+            let derived = TableFactor::Derived {
+                lateral: false,
+                subquery: Box::new(part),
+                alias: Some(TableAlias {
+                    name: Ident::new(format!("_union{anonymous}")),
+                    columns: vec![],
+                }),
+            };
+            let projection = vec![SelectItem::Wildcard(WildcardAdditionalOptions::default())];
+            let from = TableWithJoins {
+                relation: derived,
+                joins: vec![],
+            };
+            SetExpr::Select(Box::new(select(projection, vec![from], None)))
+        } else {
+            *part.body
+        };
+        body = Some(match body {
+            None => part,
+            Some(left) => SetExpr::SetOperation {
+                op: SetOperator::Union,
+                set_quantifier: match first {
+                    true => SetQuantifier::All,
+                    false => SetQuantifier::None,
+                },
+                left: Box::new(left),
+                right: Box::new(part),
+            },
+        });
+    }
+
+    let mut combined = query(body.expect("a UNION combines at least two queries"));
+    if !ctes.is_empty() {
+        combined.with = Some(With {
+            with_token: AttachedToken::empty(),
+            recursive,
+            cte_tables: ctes,
+        });
+    }
+    Ok(Statement::Query(Box::new(combined)))
+}
+
+/// The part of `RETURN` and `WITH` following the keyword
+struct ProjectionBody<'c> {
+    distinct: bool,
+    items: &'c [ProjectionItem],
+    order_by: &'c [OrderByExpr],
+    skip: &'c Option<Expr>,
+    limit: &'c Option<Expr>,
+}
+
+impl<'c> From<&'c ReturnClause> for ProjectionBody<'c> {
+    fn from(r: &'c ReturnClause) -> Self {
+        Self {
+            distinct: r.distinct,
+            items: &r.items,
+            order_by: &r.order_by,
+            skip: &r.skip,
+            limit: &r.limit,
+        }
+    }
+}
+
+impl<'c> From<&'c WithClause> for ProjectionBody<'c> {
+    fn from(w: &'c WithClause) -> Self {
+        Self {
+            distinct: w.distinct,
+            items: &w.items,
+            order_by: &w.order_by,
+            skip: &w.skip,
+            limit: &w.limit,
+        }
+    }
+}
+
+/// Selects `projection`, the translated items of `body`, from the tables
+/// matched so far, if any. If any item is an aggregate, such as `count(*)`,
+/// the other items are the `GROUP BY` keys.
+fn project(
+    scope: MatchScope,
+    projection: Vec<SelectItem>,
+    body: ProjectionBody,
+) -> Result<Query, DesugarError> {
+    let group_by = if body.items.iter().any(|item| is_aggregate(&item.expr)) {
+        let mut keys = vec![];
+        for item in body.items.iter().filter(|item| !is_aggregate(&item.expr)) {
+            keys.extend(scope.grouping_keys(&item.expr)?);
+        }
+        keys
+    } else {
+        vec![]
+    };
+    let order_by = body
+        .order_by
+        .iter()
+        .map(|item| {
+            Ok(OrderByExpr {
+                expr: scope.translate_sort_key(&item.expr, body.items)?,
+                ..item.clone()
+            })
+        })
+        .collect::<Result<Vec<_>, DesugarError>>()?;
+    let skip = body
+        .skip
+        .as_ref()
+        .map(|e| scope.translate_expr(e))
+        .transpose()?;
+    let limit = body
+        .limit
+        .as_ref()
+        .map(|e| scope.translate_expr(e))
+        .transpose()?;
+
+    let is_mssql = scope.dialect.is::<MsSqlDialect>();
+    let mut query = scope.into_query(projection);
+    if body.distinct {
+        query_select(&mut query).distinct = Some(Distinct::Distinct);
+    }
+    if !group_by.is_empty() {
+        query_select(&mut query).group_by = GroupByExpr::Expressions(group_by, vec![]);
+    }
+    if !order_by.is_empty() {
+        query.order_by = Some(OrderBy {
+            kind: OrderByKind::Expressions(order_by),
+            interpolate: None,
+        });
+    }
+    if is_mssql {
+        // T-SQL has no `LIMIT`, but `TOP` and `OFFSET ... FETCH`, which
+        // needs an `ORDER BY`
+        match (skip, limit) {
+            (None, Some(limit)) => {
+                let quantity = match limit.to_string().parse() {
+                    Ok(constant) => TopQuantity::Constant(constant),
+                    Err(_) => TopQuantity::Expr(limit),
+                };
+                query_select(&mut query).top = Some(Top {
+                    with_ties: false,
+                    percent: false,
+                    quantity: Some(quantity),
+                });
+            }
+            (Some(skip), limit) => {
+                query.order_by.get_or_insert_with(|| OrderBy {
+                    kind: OrderByKind::Expressions(vec![unordered()]),
+                    interpolate: None,
+                });
+                query.limit_clause = Some(LimitClause::LimitOffset {
+                    limit: None,
+                    offset: Some(Offset {
+                        value: skip,
+                        rows: OffsetRows::Rows,
+                    }),
+                    limit_by: vec![],
+                });
+                query.fetch = limit.map(|quantity| Fetch {
+                    with_ties: false,
+                    percent: false,
+                    quantity: Some(quantity),
+                });
+            }
+            (None, None) => {}
+        }
+    } else if skip.is_some() || limit.is_some() {
+        query.limit_clause = Some(LimitClause::LimitOffset {
+            limit,
+            offset: skip.map(|value| Offset {
+                value,
+                rows: OffsetRows::None,
+            }),
+            limit_by: vec![],
+        });
+    }
+    Ok(query)
+}
+
+/// `MATCH ... DELETE n` deletes the rows of the table of `n` whose id is
+/// selected by the `MATCH`. A node is only deleted if no relationship
+/// starts or ends at it, unless `DETACH DELETE n` first deletes those
+/// relationships. Relationships are deleted before nodes, so that
+/// `DELETE r, n` deletes `n` once `r` was its last relationship.
+///
+/// Deleting some of the items can change what the `MATCH` selects for the
+/// others, so when deleting more than one item, or detaching a node matched
+/// by a relationship, the ids are first selected into a temporary table
+/// with a column per item, which is dropped after the `DELETE` statements:
+///
+/// ```sql
+/// CREATE TEMPORARY TABLE _deleted AS SELECT r.id AS r, n.id AS n FROM ...;
+/// DELETE FROM edges WHERE id IN (SELECT r FROM _deleted);
+/// DELETE FROM nodes WHERE id IN (SELECT n FROM _deleted) AND NOT EXISTS (...);
+/// DROP TABLE _deleted
+/// ```
+///
+/// MySQL cannot select the ids from the table it deletes from in a
+/// subquery, so there they are selected through a derived table, see
+/// [`derived_ids`].
+fn desugar_delete(
+    dialect: &dyn Dialect,
+    options: &DesugarOptions,
+    m: &MatchClause,
+    d: &DeleteClause,
+) -> Result<Vec<Statement>, DesugarError> {
+    let mut scope = MatchScope::new(dialect, options);
+    scope.add_match(m)?;
+    let mut targets = vec![];
+    for item in &d.items {
+        let Expr::Identifier(target) = item else {
+            return Err(DesugarError::Unsupported(d.to_string()));
+        };
+        if targets
+            .iter()
+            .any(|(binding, ..): &(Binding, _, _)| &binding.variable == target)
+        {
+            continue;
+        }
+        let binding = scope.resolve(target)?;
+        let (Some(table), Some(id_column)) = (binding.kind.table_name(), binding.kind.id_column())
+        else {
+            return Err(DesugarError::Unsupported(d.to_string()));
+        };
+        targets.push((binding.clone(), table, id_column));
+    }
+    // relationships first, the sort being stable
+    targets.sort_by_key(|(binding, ..)| matches!(binding.kind, BindingKind::Node(_)));
+    let detached_node = d.detach && matches!(targets[0].0.kind, BindingKind::Node(_));
+    let materialized = targets.len() > 1
+        || (detached_node && m.pattern.iter().any(|path| !path.segments.is_empty()));
+    let is_mysql = dialect.is::<MySqlDialect>();
+
+    let projection = targets
+        .iter()
+        .map(|(binding, _, id_column)| {
+            let expr = column(Some(&binding.alias), id_column);
+            match materialized || is_mysql {
+                true => SelectItem::ExprWithAlias {
+                    expr,
+                    alias: sql_ident(dialect, &binding.variable),
+                },
+                false => SelectItem::UnnamedExpr(expr),
+            }
+        })
+        .collect();
+    let matched = scope.into_query(projection);
+    let temporary = match dialect.is::<MsSqlDialect>() {
+        true => "#_deleted",
+        false => "_deleted",
+    };
+    let edge_tables = options.schema.edge_tables();
+
+    let mut statements = vec![];
+    if materialized {
+        statements.push(temporary_table(dialect, temporary, matched.clone()));
+    }
+    for (binding, table, id_column) in &targets {
+        let name = sql_ident(dialect, &binding.variable);
+        let ids = if materialized {
+            query(SetExpr::Select(Box::new(select(
+                vec![SelectItem::UnnamedExpr(Expr::Identifier(name.clone()))],
+                vec![table_with_joins(temporary, None)],
+                None,
+            ))))
+        } else if is_mysql {
+            derived_ids(matched.clone(), &name)
+        } else {
+            matched.clone()
+        };
+        let selected = |column: &str| Expr::InSubquery {
+            expr: Box::new(ident(column)),
+            subquery: Box::new(ids.clone()),
+            negated: false,
+        };
+        let mut selection = selected(id_column);
+        let is_node = matches!(binding.kind, BindingKind::Node(_));
+        if is_node && d.detach {
+            for edges in &edge_tables {
+                let endpoints = [&edges.source_column, &edges.target_column];
+                let incident = if materialized && is_mysql {
+                    // MySQL cannot refer to a temporary table twice in a query
+                    let endpoint = Expr::InList {
+                        expr: Box::new(Expr::Identifier(name.clone())),
+                        list: endpoints.map(|column| ident(column)).to_vec(),
+                        negated: false,
+                    };
+                    let deleted = select(
+                        vec![SelectItem::UnnamedExpr(number(1))],
+                        vec![table_with_joins(temporary, None)],
+                        Some(endpoint),
+                    );
+                    Expr::Exists {
+                        subquery: Box::new(query(SetExpr::Select(Box::new(deleted)))),
+                        negated: false,
+                    }
+                } else {
+                    Expr::BinaryOp {
+                        left: Box::new(selected(endpoints[0])),
+                        op: BinaryOperator::Or,
+                        right: Box::new(selected(endpoints[1])),
+                    }
+                };
+                statements.push(delete(&edges.name, incident));
+            }
+        } else if is_node {
+            for edges in &edge_tables {
+                let edge = Ident::new(&edges.name);
+                let node_id = column(Some(&Ident::new(*table)), id_column);
+                let incident = Expr::BinaryOp {
+                    left: Box::new(eq(
+                        column(Some(&edge), &edges.source_column),
+                        node_id.clone(),
+                    )),
+                    op: BinaryOperator::Or,
+                    right: Box::new(eq(column(Some(&edge), &edges.target_column), node_id)),
+                };
+                let relationships = select(
+                    vec![SelectItem::UnnamedExpr(number(1))],
+                    vec![table_with_joins(&edges.name, None)],
+                    Some(incident),
+                );
+                let unconnected = Expr::Exists {
+                    subquery: Box::new(query(SetExpr::Select(Box::new(relationships)))),
+                    negated: true,
+                };
+                selection = and(selection, unconnected);
+            }
+        }
+        statements.push(delete(table, selection));
+    }
+    if materialized {
+        statements.push(drop_table(temporary));
+    }
+    Ok(statements)
+}
+
+/// A property or label change made by `SET` or `REMOVE`
+enum Change<'c> {
+    Set(&'c SetItem),
+    Remove(&'c RemoveItem),
+}
+
+impl<'c> Change<'c> {
+    fn variable(&self) -> &'c Ident {
+        match self {
+            Change::Set(
+                SetItem::Property { variable, .. }
+                | SetItem::Properties { variable, .. }
+                | SetItem::Label { variable, .. },
+            )
+            | Change::Remove(
+                RemoveItem::Property { variable, .. } | RemoveItem::Label { variable, .. },
+            ) => variable,
+        }
+    }
+}
+
+/// `MATCH ... SET n.key = v` and `MATCH ... REMOVE n.key` update the row of
+/// `n` selected by the `MATCH`. Properties in the JSON column are changed
+/// with the JSON functions of `dialect`, see [`json_set`] and
+/// [`json_remove`]. A node has a single label in the label column, so
+/// `SET n:Label` is unsupported unless the table only stores nodes with
+/// that label, and `REMOVE n:Label` sets the column to `NULL` on the nodes
+/// having it. The new values can refer to the properties of `n`, as in
+/// `SET n.age = n.age + 1`, but not to other variables. On MySQL, the ids
+/// are selected through a derived table, see [`derived_ids`].
+fn desugar_update(
+    dialect: &dyn Dialect,
+    options: &DesugarOptions,
+    m: &MatchClause,
+    clauses: &[CypherClause],
+) -> Result<Vec<Statement>, DesugarError> {
+    let changes: Vec<Change> = clauses
+        .iter()
+        .flat_map(|clause| match clause {
+            CypherClause::Set(s) => s.items.iter().map(Change::Set).collect(),
+            CypherClause::Remove(r) => r.items.iter().map(Change::Remove).collect(),
+            _ => vec![],
+        })
+        .collect();
+    let target = changes[0].variable();
+    if changes.iter().any(|change| change.variable() != target) {
+        return Err(DesugarError::Unsupported(format!(
+            "updating more than one variable in {}",
+            display_separated(clauses, " ")
+        )));
+    }
+
+    let mut scope = MatchScope::new(dialect, options);
+    scope.add_match(m)?;
+    let binding = scope.resolve(target)?;
+    let kind = binding.kind;
+    let (Some(table), Some(id_column)) = (kind.table_name(), kind.id_column()) else {
+        return Err(DesugarError::Unsupported(format!(
+            "updating `{target}`, which is not a node or relationship"
+        )));
+    };
+    let id = column(Some(&binding.alias), id_column);
+    let ids = match dialect.is::<MySqlDialect>() {
+        true => {
+            let name = sql_ident(dialect, &Ident::new(id_column));
+            let projection = SelectItem::ExprWithAlias {
+                expr: id,
+                alias: name.clone(),
+            };
+            derived_ids(scope.into_query(vec![projection]), &name)
+        }
+        false => scope.into_query(vec![SelectItem::UnnamedExpr(id)]),
+    };
+
+    let mut update = RowUpdate::new(dialect, options, target, kind, None)?;
+    for change in &changes {
+        match change {
+            Change::Set(item) => update.set(item)?,
+            Change::Remove(item) => update.remove(item)?,
+        }
+    }
+    let assignments = update.into_assignments();
+    if assignments.is_empty() {
+        return Ok(vec![]);
+    }
+    Ok(vec![Statement::Update(Update {
+        update_token: AttachedToken::empty(),
+        table: table_with_joins(table, None),
+        assignments,
+        from: None,
+        selection: Some(Expr::InSubquery {
+            expr: Box::new(ident(id_column)),
+            subquery: Box::new(ids),
+            negated: false,
+        }),
+        returning: None,
+        or: None,
+        limit: None,
+    })])
+}
+
+/// The new values of the columns of a row changed by `SET` and `REMOVE`
+struct RowUpdate<'a> {
+    dialect: &'a dyn Dialect,
+    schema: &'a GraphSchema,
+    kind: BindingKind<'a>,
+    storage: &'a PropertyColumns,
+    /// Qualifies the current values of the columns, e.g. `nodes` in
+    /// `nodes.properties`, where the new values are computed next to another
+    /// row
+    qualifier: Option<Ident>,
+    /// The changed row, to translate the new values
+    row: MatchScope<'a>,
+    /// The new values of the columns other than the JSON column
+    assignments: Vec<(String, Expr)>,
+    /// The new value of the JSON column, if it changes
+    json: Option<Expr>,
+}
+
+impl<'a> RowUpdate<'a> {
+    /// Changes the row of the table of `kind` bound to `variable`
+    fn new(
+        dialect: &'a dyn Dialect,
+        options: &'a DesugarOptions,
+        variable: &Ident,
+        kind: BindingKind<'a>,
+        qualifier: Option<Ident>,
+    ) -> Result<Self, DesugarError> {
+        let (Some(table), Some(storage)) = (kind.table_name(), kind.properties()) else {
+            return Err(DesugarError::Unsupported(format!(
+                "updating `{variable}`, which is not a node or relationship"
+            )));
+        };
+        let mut row = MatchScope::new(dialect, options);
+        row.bindings.push(Binding {
+            variable: variable.clone(),
+            alias: Ident::new(table),
+            kind,
+        });
+        Ok(Self {
+            dialect,
+            schema: &options.schema,
+            kind,
+            storage,
+            qualifier,
+            row,
+            assignments: vec![],
+            json: None,
+        })
+    }
+
+    fn set(&mut self, item: &SetItem) -> Result<(), DesugarError> {
+        match item {
+            SetItem::Property { key, value, .. } => {
+                let sql = match value {
+                    Expr::Array(_) | Expr::Dictionary(_) => value.clone(),
+                    _ => self.row.translate_expr(value)?,
+                };
+                self.set_property(&key.value, value, sql)
+            }
+            SetItem::Properties {
+                value: Expr::Dictionary(fields),
+                merge,
+                ..
+            } => {
+                let properties: Vec<PropertyEntry> = fields
+                    .iter()
+                    .map(|field| PropertyEntry {
+                        key: field.key.clone(),
+                        value: *field.value.clone(),
+                    })
+                    .collect();
+                let translated = self.row.translate_properties(&properties)?;
+                if !merge {
+                    return self.replace_properties(&translated);
+                }
+                for (entry, sql) in properties.iter().zip(translated) {
+                    self.set_property(&entry.key.value, &entry.value, sql.value)?;
+                }
+                Ok(())
+            }
+            SetItem::Properties {
+                value: value @ Expr::Value(_),
+                merge: false,
+                ..
+            } if has_parameter(value) => self.replace_with_parameter(value.clone()),
+            SetItem::Label { variable, label } => {
+                let BindingKind::Node(table) = self.kind else {
+                    return Err(DesugarError::Unsupported(format!(
+                        "changing the type of relationship `{variable}`"
+                    )));
+                };
+                // if `None`, every node of the table has the label, otherwise
+                // replacing the single label would remove the one it has
+                match node_label_column(self.schema, table, label)? {
+                    None => Ok(()),
+                    Some(label_column) => Err(DesugarError::Unsupported(format!(
+                        "adding label `{label}` to nodes with a single label in column \
+                         `{label_column}` of table `{}`",
+                        table.name
+                    ))),
+                }
+            }
+            _ => Err(DesugarError::Unsupported(format!("SET {item}"))),
+        }
+    }
+
+    fn remove(&mut self, item: &RemoveItem) -> Result<(), DesugarError> {
+        match item {
+            RemoveItem::Property { key, .. } => self.remove_property(&key.value),
+            RemoveItem::Label { variable, label } => {
+                let BindingKind::Node(table) = self.kind else {
+                    return Err(DesugarError::Unsupported(format!(
+                        "changing the type of relationship `{variable}`"
+                    )));
+                };
+                let Some(label_column) = node_label_column(self.schema, table, label)? else {
+                    return Err(DesugarError::Unsupported(format!(
+                        "removing label `{label}` from the nodes of table `{}`",
+                        table.name
+                    )));
+                };
+                // the label is only removed from the nodes that have it
+                let current = column(self.qualifier.as_ref(), label_column);
+                let removed = Expr::Case {
+                    case_token: AttachedToken::empty(),
+                    end_token: AttachedToken::empty(),
+                    operand: None,
+                    conditions: vec![CaseWhen {
+                        condition: eq(current.clone(), string_lit(&label.value)),
+                        result: Expr::value(Value::Null),
+                    }],
+                    else_result: Some(Box::new(current)),
+                };
+                self.assign(label_column, removed);
+                Ok(())
+            }
+        }
+    }
+
+    /// The `SET` assignments of the changed columns
+    fn into_assignments(self) -> Vec<Assignment> {
+        let mut assignments = self.assignments;
+        if let (Some(json_column), Some(json)) = (&self.storage.json_column, self.json) {
+            assignments.push((json_column.clone(), json));
+        }
+        assignments
+            .into_iter()
+            .map(|(name, value)| Assignment {
+                target: AssignmentTarget::ColumnName(object_name(&name)),
+                value,
+            })
+            .collect()
+    }
+
+    fn assign(&mut self, name: &str, value: Expr) {
+        self.assignments.retain(|(assigned, _)| assigned != name);
+        self.assignments.push((name.to_string(), value));
+    }
+
+    /// The JSON column with the changes so far
+    fn json_column(&mut self, key: &str) -> Result<Expr, DesugarError> {
+        match (self.json.take(), &self.storage.json_column) {
+            (Some(json), _) => Ok(json),
+            (None, Some(json_column)) => Ok(column(self.qualifier.as_ref(), json_column)),
+            (None, None) => Err(unmapped_property(key)),
+        }
+    }
+    /// Sets the property `key` to the Cypher `value`, translated to `sql`
+    fn set_property(&mut self, key: &str, value: &Expr, sql: Expr) -> Result<(), DesugarError> {
+        if is_null(value) {
+            return self.remove_property(key);
+        }
+        let literal_json = match value {
+            Expr::Value(v) => matches!(v.value, Value::Boolean(_)),
+            Expr::Array(_) | Expr::Dictionary(_) => true,
+            _ => false,
+        };
+        match self.storage.columns.get(key) {
+            Some(PropertyColumn::Column(name)) => {
+                let sql = match value {
+                    Expr::Array(_) | Expr::Dictionary(_) => string_lit(&json_value(value)?),
+                    _ => sql,
+                };
+                self.assign(name, sql);
+            }
+            Some(PropertyColumn::JsonPath { .. }) => return Err(json_path_update(key)),
+            None => {
+                let sql = if literal_json {
+                    json_literal(self.dialect, &json_value(value)?)
+                } else {
+                    sql
+                };
+                let json = self.json_column(key)?;
+                self.json = Some(json_set(self.dialect, json, key, sql));
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_property(&mut self, key: &str) -> Result<(), DesugarError> {
+        match self.storage.columns.get(key) {
+            Some(PropertyColumn::Column(name)) => self.assign(name, Expr::value(Value::Null)),
+            Some(PropertyColumn::JsonPath { .. }) => return Err(json_path_update(key)),
+            None => {
+                let json = self.json_column(key)?;
+                self.json = Some(json_remove(self.dialect, json, key));
+            }
+        }
+        Ok(())
+    }
+
+    /// `SET n = {...}` replaces all properties with the non-null `properties`
+    fn replace_properties(&mut self, properties: &[PropertyEntry]) -> Result<(), DesugarError> {
+        for (key, storage) in &self.storage.columns {
+            match storage {
+                PropertyColumn::Column(name) => self.assign(name, Expr::value(Value::Null)),
+                PropertyColumn::JsonPath { .. } => return Err(json_path_update(key)),
+            }
+        }
+        let properties: Vec<PropertyEntry> = properties
+            .iter()
+            .filter(|entry| !is_null(&entry.value))
+            .cloned()
+            .collect();
+        let mut columns = vec![];
+        let mut values = vec![];
+        let json = property_values(
+            self.dialect,
+            self.storage,
+            &properties,
+            &mut columns,
+            &mut values,
+        )?;
+        for (name, value) in columns.into_iter().zip(values) {
+            self.assign(&name.value, value);
+        }
+        match (&self.storage.json_column, json.first()) {
+            (Some(_), _) => self.json = Some(json_document(self.dialect, &json)?),
+            (None, Some(entry)) => return Err(unmapped_property(&entry.key.value)),
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    /// `SET n = $props` replaces the JSON column with the parameter
+    fn replace_with_parameter(&mut self, parameter: Expr) -> Result<(), DesugarError> {
+        match (
+            &self.storage.json_column,
+            self.storage.columns.keys().next(),
+        ) {
+            (Some(_), None) => {
+                self.json = Some(parameter);
+                Ok(())
+            }
+            (None, _) | (_, Some(_)) => Err(DesugarError::Unsupported(format!(
+                "replacing the properties stored in columns of their own with {parameter}"
+            ))),
+        }
+    }
+}
+
+fn json_path_update(key: &str) -> DesugarError {
+    DesugarError::Unsupported(format!("setting property `{key}` stored at a JSON path"))
+}
+
+fn is_null(value: &Expr) -> bool {
+    matches!(value, Expr::Value(v) if v.value == Value::Null)
+}
+
+/// `CREATE (a:Label {k: v})-[:TYPE]->(b), (c)` inserts every node of its
+/// patterns into the table of its label, then every relationship into the
+/// table of its type. A node variable occurring more than once refers to the
+/// same node.
+///
+/// The ids of the nodes a relationship connects are only known once they
+/// are inserted. PostgreSQL inserts them in CTEs returning their ids:
+///
+/// ```sql
+/// WITH a AS (INSERT INTO nodes ... RETURNING id), b AS (...)
+/// INSERT INTO edges (src_id, dst_id, ...) VALUES ((SELECT id FROM a), (SELECT id FROM b), ...)
+/// ```
+///
+/// Other dialects insert them in statements of their own, returning the id
+/// as e.g. `a_id`, which is then a parameter of the statements inserting the
+/// relationships as `$a_id`. MSSQL returns it with `OUTPUT INSERTED.id AS
+/// a_id`, and MySQL with a `SELECT LAST_INSERT_ID() AS a_id` following the
+/// `INSERT`. Snowflake and BigQuery cannot return generated ids, so there
+/// creating relationships between new nodes is unsupported.
+fn desugar_create(
+    dialect: &dyn Dialect,
+    options: &DesugarOptions,
+    c: &CreateClause,
+) -> Result<Vec<Statement>, DesugarError> {
+    let schema = &options.schema;
+    let unbound = MatchScope::new(dialect, options);
+    let mut created = CreatedElements::default();
+    for path in &c.pattern {
+        let properties = path
+            .segments
+            .iter()
+            .flat_map(|segment| [&segment.node.properties, &segment.relationship.properties]);
+        for properties in core::iter::once(&path.start.properties).chain(properties) {
+            // without variables, the translation only checks the values
+            unbound.translate_properties(properties)?;
+        }
+        let mut previous = created.add_node(c, &path.start)?;
+        for segment in &path.segments {
+            let next = created.add_node(c, &segment.node)?;
+            let alias = created.alias(&segment.relationship.variable, "_r");
+            created
+                .relationships
+                .push((alias, &segment.relationship, previous, next));
+            previous = next;
+        }
+    }
+    let CreatedElements {
+        nodes,
+        relationships,
+        ..
+    } = created;
+    let mut statements = vec![];
+    for (_, node) in &nodes {
+        statements.push(insert_node(dialect, schema, node)?);
+    }
+    if relationships.is_empty() {
+        return Ok(statements);
+    }
+
+    let chained_in_ctes = dialect.is::<PostgreSqlDialect>();
+    if dialect.is::<SnowflakeDialect>() || dialect.is::<BigQueryDialect>() {
+        return Err(DesugarError::Unsupported(format!(
+            "creating relationships between the nodes created by {c}"
+        )));
+    }
+    let parameters = pattern_parameters(&c.pattern);
+    let mut ids = vec![];
+    let mut last_insert_ids = vec![];
+    for (i, ((alias, node), statement)) in nodes.iter().zip(&mut statements).enumerate() {
+        let id_column = &node_table(schema, node)?.id_column;
+        let Statement::Insert(insert) = statement else {
+            unreachable!("nodes are inserted with INSERT")
+        };
+        let connected = relationships
+            .iter()
+            .any(|(_, _, start, end)| *start == i || *end == i);
+        if chained_in_ctes {
+            insert.returning = connected.then(|| vec![SelectItem::UnnamedExpr(ident(id_column))]);
+            let select = select(
+                vec![SelectItem::UnnamedExpr(ident(id_column))],
+                vec![table_named(
+                    ObjectName::from(vec![sql_ident(dialect, alias)]),
+                    None,
+                )],
+                None,
+            );
+            ids.push(Expr::Subquery(Box::new(query(SetExpr::Select(Box::new(
+                select,
+            ))))));
+        } else {
+            let name = format!("{}_id", alias.value);
+            if parameters.contains(&name) {
+                return Err(DesugarError::Unsupported(format!(
+                    "parameter `${name}` named like the id of the created node `{alias}`"
+                )));
+            }
+            if connected {
+                let id = |expr| SelectItem::ExprWithAlias {
+                    expr,
+                    alias: Ident::new(&name),
+                };
+                if dialect.is::<MySqlDialect>() {
+                    let last_insert_id = function("LAST_INSERT_ID", vec![]);
+                    last_insert_ids.push((i, select(vec![id(last_insert_id)], vec![], None)));
+                } else if dialect.is::<MsSqlDialect>() {
+                    let inserted = column(Some(&Ident::new("INSERTED")), id_column);
+                    insert.output = Some(OutputClause::Output {
+                        select_items: vec![id(inserted)],
+                        into_table: None,
+                    });
+                } else {
+                    insert.returning = Some(vec![id(ident(id_column))]);
+                }
+            }
+            ids.push(Expr::value(Value::Placeholder(format!("${name}"))));
+        }
+    }
+    // each after the `INSERT` of its node, last first to keep the indexes
+    for (i, select) in last_insert_ids.into_iter().rev() {
+        let select = query(SetExpr::Select(Box::new(select)));
+        statements.insert(i + 1, Statement::Query(Box::new(select)));
+    }
+    for (_, relationship, start, end) in &relationships {
+        let (table, columns, row) = edge_row(
+            dialect,
+            schema,
+            relationship,
+            ids[*start].clone(),
+            ids[*end].clone(),
+        )?;
+        statements.push(insert(&table.name, columns, values(dialect, row)));
+    }
+    if !chained_in_ctes {
+        return Ok(statements);
+    }
+
+    let Some(Statement::Insert(last)) = statements.pop() else {
+        unreachable!("relationships are inserted with INSERT")
+    };
+    let names = nodes
+        .iter()
+        .map(|(alias, _)| alias)
+        .chain(relationships.iter().map(|(alias, ..)| alias));
+    let cte_tables = names
+        .zip(statements)
+        .map(|(name, statement)| Cte {
+            alias: TableAlias {
+                name: sql_ident(dialect, name),
+                columns: vec![],
+            },
+            query: Box::new(query(SetExpr::Insert(statement))),
+            from: None,
+            materialized: None,
+            closing_paren_token: AttachedToken::empty(),
+        })
+        .collect();
+    let mut chained = query(SetExpr::Insert(Statement::Insert(last)));
+    chained.with = Some(With {
+        with_token: AttachedToken::empty(),
+        recursive: false,
+        cte_tables,
+    });
+    Ok(vec![Statement::Query(Box::new(chained))])
+}
+
+/// The nodes and relationships of the patterns of a `CREATE` clause, named
+/// by their variable or an alias such as `_n1`. Relationships refer to the
+/// nodes they connect by index.
+#[derive(Default)]
+struct CreatedElements<'c> {
+    nodes: Vec<(Ident, &'c NodePattern)>,
+    relationships: Vec<(Ident, &'c RelationshipPattern, usize, usize)>,
+    anonymous: usize,
+}
+
+impl<'c> CreatedElements<'c> {
+    fn alias(&mut self, variable: &Option<Ident>, prefix: &str) -> Ident {
+        match variable {
+            Some(variable) => variable.clone(),
+            None => {
+                self.anonymous += 1;
+                Ident::new(format!("{prefix}{}", self.anonymous))
+            }
+        }
+    }
+
+    /// Adds `node` unless its variable refers to a node added before, and
+    /// returns its index
+    fn add_node(&mut self, c: &CreateClause, node: &'c NodePattern) -> Result<usize, DesugarError> {
+        let bound = node.variable.as_ref().and_then(|variable| {
+            self.nodes
+                .iter()
+                .position(|(_, n)| n.variable.as_ref() == Some(variable))
+        });
+        match bound {
+            Some(_) if node.label.is_some() || !node.properties.is_empty() => Err(
+                DesugarError::Unsupported(format!("node variable declared more than once in {c}")),
+            ),
+            Some(i) => Ok(i),
+            None => {
+                let alias = self.alias(&node.variable, "_n");
+                self.nodes.push((alias, node));
+                Ok(self.nodes.len() - 1)
+            }
+        }
+    }
+}
+
+/// `MATCH (a), (b) CREATE (a)-[:TYPE]->(b)` inserts a row into the table of
+/// `TYPE` for each match of `a` and `b`, and `UNWIND $rows AS row CREATE
+/// (:User {id: row.id})` a node for each element of `$rows`, with a statement
+/// per pattern of the `CREATE` clause. The nodes created are not bound, so a
+/// relationship can only connect nodes bound by the `reading` clauses.
+fn desugar_create_from(
+    dialect: &dyn Dialect,
+    options: &DesugarOptions,
+    reading: &[CypherClause],
+    c: &CreateClause,
+) -> Result<Vec<Statement>, DesugarError> {
+    let scope = || {
+        let mut scope = MatchScope::new(dialect, options);
+        for clause in reading {
+            match clause {
+                CypherClause::Match(m) => scope.add_match(m)?,
+                CypherClause::Unwind(u) => scope.add_unwind(u)?,
+                _ => unreachable!("only MATCH and UNWIND are reading clauses"),
+            }
+        }
+        Ok::<_, DesugarError>(scope)
+    };
+    let mut statements = vec![];
+    for path in &c.pattern {
+        let scope = scope()?;
+        let (table, columns, row) = match path.segments.as_slice() {
+            [] => {
+                if let Some(variable) = &path.start.variable {
+                    if scope.resolve(variable).is_ok() {
+                        return Err(DesugarError::Unsupported(format!(
+                            "node `{variable}` is bound before {c}"
+                        )));
+                    }
+                }
+                let storage = &node_table(&options.schema, &path.start)?.properties;
+                let node = NodePattern {
+                    properties: scope
+                        .translate_created_properties(storage, &path.start.properties)?,
+                    ..path.start.clone()
+                };
+                let (table, columns, row) = node_row(dialect, &options.schema, &node)?;
+                (table.name.as_str(), columns, row)
+            }
+            [segment] => {
+                let node_id =
+                    |node: &NodePattern| match node.variable.as_ref().map(|v| scope.resolve(v)) {
+                        Some(Ok(Binding {
+                            alias,
+                            kind: BindingKind::Node(table),
+                            ..
+                        })) => Ok(column(Some(alias), &table.id_column)),
+                        Some(Ok(_)) => Err(DesugarError::Unsupported(c.to_string())),
+                        None | Some(Err(_)) => Err(DesugarError::Unsupported(format!(
+                            "relationship to a node created by {c}"
+                        ))),
+                    };
+                let storage = &edge_table(&options.schema, &segment.relationship)?.properties;
+                let relationship = RelationshipPattern {
+                    properties: scope
+                        .translate_created_properties(storage, &segment.relationship.properties)?,
+                    ..segment.relationship.clone()
+                };
+                let (table, columns, row) = edge_row(
+                    dialect,
+                    &options.schema,
+                    &relationship,
+                    node_id(&path.start)?,
+                    node_id(&segment.node)?,
+                )?;
+                (table.name.as_str(), columns, row)
+            }
+            _ => return Err(DesugarError::Unsupported(c.to_string())),
+        };
+        let projection = row.into_iter().map(SelectItem::UnnamedExpr).collect();
+        statements.push(insert(table, columns, scope.into_query(projection)));
+    }
+    Ok(statements)
+}
+
+fn insert_node(
+    dialect: &dyn Dialect,
+    schema: &GraphSchema,
+    node: &NodePattern,
+) -> Result<Statement, DesugarError> {
+    let (table, columns, row) = node_row(dialect, schema, node)?;
+    let mut statement = insert(&table.name, columns, values(dialect, row));
+    if let Statement::Insert(insert) = &mut statement {
+        // `DEFAULT VALUES` rather than `VALUES ()`, which only MySQL accepts
+        if insert.columns.is_empty() && !dialect.is::<MySqlDialect>() {
+            insert.source = None;
+        }
+    }
+    Ok(statement)
+}
+
+/// The table to insert the node `node` into, with the columns and values of
+/// its row
+fn node_row<'s>(
+    dialect: &dyn Dialect,
+    schema: &'s GraphSchema,
+    node: &NodePattern,
+) -> Result<(&'s NodeTable, Vec<Ident>, Vec<Expr>), DesugarError> {
+    let table = node_table(schema, node)?;
+    let mut columns = vec![];
+    let mut row = vec![];
+    if let Some(label) = single_label(&node.label, node)? {
+        if let Some(label_column) = node_label_column(schema, table, label)? {
+            columns.push(Ident::new(label_column));
+            row.push(string_lit(&label.value));
+        }
+    }
+    let json = property_values(
+        dialect,
+        &table.properties,
+        &node.properties,
+        &mut columns,
+        &mut row,
+    )?;
+    if let Some(entry) = json.first() {
+        let Some(json_column) = &table.properties.json_column else {
+            return Err(unmapped_property(&entry.key.value));
+        };
+        columns.push(Ident::new(json_column));
+        row.push(json_document(dialect, &json)?);
+    }
+    Ok((table, columns, row))
+}
+
+/// `MERGE (n:Label {k: v}) ON CREATE SET ... ON MATCH SET ...` inserts the
+/// node, with the `ON CREATE` items applied, unless a node with the label
+/// and properties of the pattern exists, which the `ON MATCH` items update
+/// instead. The upsert of the target dialect is used:
+///
+/// - MySQL: `INSERT ... ON DUPLICATE KEY UPDATE ...`
+/// - Snowflake, MS SQL and BigQuery: `MERGE INTO ... WHEN MATCHED THEN UPDATE
+///   ... WHEN NOT MATCHED THEN INSERT ...`
+/// - others, e.g. PostgreSQL and SQLite: `INSERT ... ON CONFLICT (...) DO
+///   UPDATE ...`
+///
+/// An existing node is found by the unique key of its table in `INSERT`
+/// upserts, so the properties of the pattern must be stored in columns of
+/// their own, covered with the label column by a unique index. Otherwise,
+/// e.g. for properties stored in JSON, the matching nodes are updated first,
+/// then the node is inserted unless one matches:
+///
+/// ```sql
+/// UPDATE nodes SET ... WHERE nodes.label = 'User' AND ...;
+/// INSERT INTO nodes (label, properties) SELECT 'User', '{"id":1}'
+/// WHERE NOT EXISTS (SELECT 1 FROM nodes WHERE nodes.label = 'User' AND ...)
+/// ```
+///
+/// Inserting first would apply the `ON MATCH` items to the new node, and
+/// updating first would insert a duplicate if they changed a property of the
+/// pattern, which is unsupported. The two statements are not atomic, see
+/// [`Statement::desugar_cypher`].
+fn desugar_merge(
+    dialect: &dyn Dialect,
+    options: &DesugarOptions,
+    mc: &CypherMergeClause,
+) -> Result<Vec<Statement>, DesugarError> {
+    let schema = &options.schema;
+    let node = &mc.pattern.start;
+    if !mc.pattern.segments.is_empty() {
+        return Err(DesugarError::Unsupported(format!(
+            "MERGE of a relationship in {mc}"
+        )));
+    }
+    if node.properties.is_empty() {
+        return Err(DesugarError::Unsupported(format!(
+            "MERGE of a node without properties in {mc}"
+        )));
+    }
+    // without variables, the translation only checks the values
+    MatchScope::new(dialect, options).translate_properties(&node.properties)?;
+    for item in mc.on_create.iter().chain(&mc.on_match) {
+        let variable = Change::Set(item).variable();
+        if node.variable.as_ref() != Some(variable) {
+            return Err(DesugarError::UnboundVariable(variable.value.clone()));
+        }
+    }
+    let table = node_table(schema, node)?;
+
+    let mut created = node.clone();
+    for item in &mc.on_create {
+        let set = |properties: &mut Vec<PropertyEntry>, key: &Ident, value: &Expr| {
+            properties.retain(|entry| entry.key != *key);
+            if !is_null(value) {
+                properties.push(PropertyEntry {
+                    key: key.clone(),
+                    value: value.clone(),
+                });
+            }
+        };
+        match item {
+            SetItem::Property { key, value, .. } => set(&mut created.properties, key, value),
+            SetItem::Properties {
+                value: Expr::Dictionary(fields),
+                merge,
+                ..
+            } => {
+                if !merge {
+                    created.properties.clear();
+                }
+                for field in fields {
+                    set(&mut created.properties, &field.key, &field.value);
+                }
+            }
+            SetItem::Label { label, .. } => match &created.label {
+                Some(existing) if existing.as_label() != Some(label) => {
+                    return Err(DesugarError::Unsupported(format!(
+                        "ON CREATE SET {item} adding a second label in {mc}"
+                    )));
+                }
+                _ => created.label = Some(LabelExpression::Label(label.clone())),
+            },
+            _ => return Err(DesugarError::Unsupported(format!("ON CREATE SET {item}"))),
+        }
+    }
+    let (created_table, columns, row) = node_row(dialect, schema, &created)?;
+    if !core::ptr::eq(created_table, table) {
+        return Err(DesugarError::Unsupported(format!(
+            "ON CREATE SET storing the node in another table in {mc}"
+        )));
+    }
+
+    let mut assignments = vec![];
+    if let Some(variable) = &node.variable {
+        let qualifier = Some(Ident::new(&table.name));
+        let kind = BindingKind::Node(table);
+        let mut update = RowUpdate::new(dialect, options, variable, kind, qualifier)?;
+        for item in &mc.on_match {
+            update.set(item)?;
+        }
+        assignments = update.into_assignments();
+    }
+
+    if dialect.is::<SnowflakeDialect>()
+        || dialect.is::<MsSqlDialect>()
+        || dialect.is::<BigQueryDialect>()
+    {
+        let qualifier = Ident::new(&table.name);
+        let conditions = node_conditions(dialect, schema, Some(&qualifier), table, node)?;
+        let source = select(
+            vec![SelectItem::ExprWithAlias {
+                expr: number(1),
+                alias: Ident::new("one"),
+            }],
+            vec![],
+            None,
+        );
+        let mut clauses = vec![];
+        if !assignments.is_empty() {
+            clauses.push(MergeClause {
+                clause_kind: MergeClauseKind::Matched,
+                predicate: None,
+                action: MergeAction::Update { assignments },
+            });
+        }
+        clauses.push(MergeClause {
+            clause_kind: MergeClauseKind::NotMatched,
+            predicate: None,
+            action: MergeAction::Insert(MergeInsertExpr {
+                columns,
+                kind: MergeInsertKind::Values(Values {
+                    explicit_row: false,
+                    value_keyword: false,
+                    rows: vec![row],
+                }),
+            }),
+        });
+        return Ok(vec![Statement::Merge {
+            into: true,
+            table: table_with_joins(&table.name, None).relation,
+            source: TableFactor::Derived {
+                lateral: false,
+                subquery: Box::new(query(SetExpr::Select(Box::new(source)))),
+                alias: Some(TableAlias {
+                    name: Ident::new("_merge"),
+                    columns: vec![],
+                }),
+            },
+            on: Box::new(and_all(conditions).ok_or_else(|| {
+                DesugarError::Unsupported(format!("MERGE of a node without properties in {mc}"))
+            })?),
+            clauses,
+            output: None,
+        }]);
+    }
+
+    let mut key = vec![];
+    if let Some(label) = single_label(&node.label, node)? {
+        if let Some(label_column) = node_label_column(schema, table, label)? {
+            key.push(Ident::new(label_column));
+        }
+    }
+    for entry in &node.properties {
+        match table.properties.columns.get(&entry.key.value) {
+            Some(PropertyColumn::Column(name)) => key.push(Ident::new(name)),
+            _ => return merge_without_key(dialect, schema, mc, table, columns, row, assignments),
+        }
+    }
+    let on = if dialect.is::<MySqlDialect>() {
+        if assignments.is_empty() {
+            // the usual no-op, as MySQL cannot do nothing on a duplicate key
+            assignments.push(Assignment {
+                target: AssignmentTarget::ColumnName(ObjectName::from(vec![key[0].clone()])),
+                value: Expr::Identifier(key[0].clone()),
+            });
+        }
+        OnInsert::DuplicateKeyUpdate(assignments)
+    } else {
+        OnInsert::OnConflict(OnConflict {
+            conflict_target: Some(ConflictTarget::Columns(key)),
+            action: if assignments.is_empty() {
+                OnConflictAction::DoNothing
+            } else {
+                OnConflictAction::DoUpdate(DoUpdate {
+                    assignments,
+                    selection: None,
+                })
+            },
+        })
+    };
+    let mut statement = insert(&table.name, columns, values(dialect, row));
+    if let Statement::Insert(insert) = &mut statement {
+        insert.on = Some(on);
+    }
+    Ok(vec![statement])
+}
+
+/// The `UPDATE` of the nodes matching the pattern of `mc` with the ON MATCH
+/// `assignments`, followed by the `INSERT` of `row` unless a node matches,
+/// see [`desugar_merge`]
+fn merge_without_key(
+    dialect: &dyn Dialect,
+    schema: &GraphSchema,
+    mc: &CypherMergeClause,
+    table: &NodeTable,
+    columns: Vec<Ident>,
+    row: Vec<Expr>,
+    assignments: Vec<Assignment>,
+) -> Result<Vec<Statement>, DesugarError> {
+    let node = &mc.pattern.start;
+    for item in &mc.on_match {
+        let key = match item {
+            SetItem::Property { key, .. } => Some(key),
+            SetItem::Properties { .. } | SetItem::Label { .. } => None,
+        };
+        if key.is_none_or(|key| node.properties.iter().any(|entry| entry.key == *key)) {
+            return Err(DesugarError::Unsupported(format!(
+                "ON MATCH SET {item} changing the pattern of {mc}"
+            )));
+        }
+    }
+    let qualifier = Ident::new(&table.name);
+    let conditions = node_conditions(dialect, schema, Some(&qualifier), table, node)?;
+    let matched = and_all(conditions).ok_or_else(|| {
+        DesugarError::Unsupported(format!("MERGE of a node without properties in {mc}"))
+    })?;
+
+    let mut statements = vec![];
+    if !assignments.is_empty() {
+        statements.push(Statement::Update(Update {
+            update_token: AttachedToken::empty(),
+            table: table_with_joins(&table.name, None),
+            assignments,
+            from: None,
+            selection: Some(matched.clone()),
+            returning: None,
+            or: None,
+            limit: None,
+        }));
+    }
+    let existing = select(
+        vec![SelectItem::UnnamedExpr(number(1))],
+        vec![table_with_joins(&table.name, None)],
+        Some(matched),
+    );
+    let not_existing = Expr::Exists {
+        subquery: Box::new(query(SetExpr::Select(Box::new(existing)))),
+        negated: true,
+    };
+    let row = row.into_iter().map(SelectItem::UnnamedExpr).collect();
+    let source = select(row, vec![], Some(not_existing));
+    statements.push(insert(
+        &table.name,
+        columns,
+        query(SetExpr::Select(Box::new(source))),
+    ));
+    Ok(statements)
+}
+
+/// The table to insert `relationship` into, with the columns and values of
+/// its row, given the ids of the nodes before and after it in its pattern
+fn edge_row<'s>(
+    dialect: &dyn Dialect,
+    schema: &'s GraphSchema,
+    relationship: &RelationshipPattern,
+    start_id: Expr,
+    end_id: Expr,
+) -> Result<(&'s EdgeTable, Vec<Ident>, Vec<Expr>), DesugarError> {
+    let Some(rel_type) = single_label(&relationship.rel_type, relationship)? else {
+        return Err(DesugarError::Unsupported(format!(
+            "relationship without a type: {relationship}"
+        )));
+    };
+    let (src_id, dst_id) = match relationship.direction {
+        RelationshipDirection::Outgoing => (start_id, end_id),
+        RelationshipDirection::Incoming => (end_id, start_id),
+        RelationshipDirection::Undirected => {
+            return Err(DesugarError::Unsupported(format!(
+                "relationship without a direction: {relationship}"
+            )))
+        }
+    };
+    let table = edge_table(schema, relationship)?;
+    let mut columns = vec![
+        Ident::new(&table.source_column),
+        Ident::new(&table.target_column),
+    ];
+    let mut row = vec![src_id, dst_id];
+    if let Some(type_column) = edge_type_column(schema, table, rel_type)? {
+        columns.push(Ident::new(type_column));
+        row.push(string_lit(&rel_type.value));
+    }
+    let json = property_values(
+        dialect,
+        &table.properties,
+        &relationship.properties,
+        &mut columns,
+        &mut row,
+    )?;
+    match (&table.properties.json_column, json.first()) {
+        (Some(json_column), _) => {
+            columns.push(Ident::new(json_column));
+            row.push(json_document(dialect, &json)?);
+        }
+        (None, Some(entry)) => return Err(unmapped_property(&entry.key.value)),
+        (None, None) => {}
+    }
+    Ok((table, columns, row))
+}
+
+/// Adds the `properties` stored in columns of their own to `columns` and
+/// `row`, and returns the others, which belong in the JSON column
+fn property_values(
+    dialect: &dyn Dialect,
+    storage: &PropertyColumns,
+    properties: &[PropertyEntry],
+    columns: &mut Vec<Ident>,
+    row: &mut Vec<Expr>,
+) -> Result<Vec<PropertyEntry>, DesugarError> {
+    let mut json = vec![];
+    for entry in properties {
+        match storage.columns.get(&entry.key.value) {
+            Some(PropertyColumn::Column(name)) => {
+                columns.push(Ident::new(name));
+                row.push(match &entry.value {
+                    Expr::Array(_) | Expr::Dictionary(_) => string_lit(&json_value(&entry.value)?),
+                    value => sql_literal(dialect, value),
+                });
+            }
+            Some(PropertyColumn::JsonPath { .. }) => {
+                return Err(json_path_update(&entry.key.value))
+            }
+            None => json.push(entry.clone()),
+        }
+    }
+    Ok(json)
+}
+
+/// The tables joined by one or more `MATCH` clauses, and the table alias
+/// each pattern variable is bound to.
+struct MatchScope<'a> {
+    dialect: &'a dyn Dialect,
+    options: &'a DesugarOptions,
+    ctes: Vec<Cte>,
+    /// Whether any of the `ctes` is recursive
+    recursive: bool,
+    from: Option<TableWithJoins>,
+    conditions: Vec<Expr>,
+    bindings: Vec<Binding<'a>>,
+    anonymous: usize,
+}
+
+/// A pattern variable and the aliased node or relationship row it refers to
+#[derive(Clone)]
+struct Binding<'a> {
+    variable: Ident,
+    alias: Ident,
+    kind: BindingKind<'a>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BindingKind<'a> {
+    Node(&'a NodeTable),
+    Relationship(&'a EdgeTable),
+    /// A row of the recursive CTE computing a variable-length relationship
+    Path,
+    /// A column projected by `WITH`, e.g. `c` in `WITH count(*) AS c`
+    Value,
+    /// An element of the list expanded by `UNWIND`, see [`unwind_table`]
+    Element,
+}
+
+impl<'a> BindingKind<'a> {
+    fn table_name(self) -> Option<&'a str> {
+        match self {
+            BindingKind::Node(table) => Some(&table.name),
+            BindingKind::Relationship(table) => Some(&table.name),
+            BindingKind::Path | BindingKind::Value | BindingKind::Element => None,
+        }
+    }
+
+    fn id_column(self) -> Option<&'a str> {
+        match self {
+            BindingKind::Node(table) => Some(&table.id_column),
+            BindingKind::Relationship(table) => Some(&table.id_column),
+            BindingKind::Path | BindingKind::Value | BindingKind::Element => None,
+        }
+    }
+
+    fn properties(self) -> Option<&'a PropertyColumns> {
+        match self {
+            BindingKind::Node(table) => Some(&table.properties),
+            BindingKind::Relationship(table) => Some(&table.properties),
+            BindingKind::Path | BindingKind::Value | BindingKind::Element => None,
+        }
+    }
+
+    /// The known columns of the row a variable of this kind is bound to
+    fn columns(self) -> Vec<&'a str> {
+        let (fixed, properties) = match self {
+            BindingKind::Node(table) => (
+                [Some(&table.id_column), table.label_column.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect(),
+                &table.properties,
+            ),
+            BindingKind::Relationship(table) => (
+                [
+                    Some(&table.id_column),
+                    Some(&table.source_column),
+                    Some(&table.target_column),
+                    table.type_column.as_ref(),
+                ]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect(),
+                &table.properties,
+            ),
+            BindingKind::Path => return vec!["start_id", "end_id", "hops", "path"],
+            BindingKind::Value | BindingKind::Element => return vec![],
+        };
+        let mut columns: Vec<&str> = fixed;
+        let property_columns =
+            properties
+                .json_column
+                .iter()
+                .chain(properties.columns.values().map(|c| match c {
+                    PropertyColumn::Column(name) => name,
+                    PropertyColumn::JsonPath { column, .. } => column,
+                }));
+        for name in property_columns {
+            if !columns.contains(&name.as_str()) {
+                columns.push(name);
+            }
+        }
+        columns
+    }
+}
+
+/// The alias of a row of a node table
+struct NodeRef<'a> {
+    alias: Ident,
+    table: &'a NodeTable,
+}
+
+impl NodeRef<'_> {
+    fn id(&self) -> Expr {
+        column(Some(&self.alias), &self.table.id_column)
+    }
+}
+
+impl<'a> MatchScope<'a> {
+    fn new(dialect: &'a dyn Dialect, options: &'a DesugarOptions) -> Self {
+        Self {
+            dialect,
+            options,
+            ctes: vec![],
+            recursive: false,
+            from: None,
+            conditions: vec![],
+            bindings: vec![],
+            anonymous: 0,
+        }
+    }
+
+    fn add_match(&mut self, m: &MatchClause) -> Result<(), DesugarError> {
+        let Some(from) = &self.from else {
+            if m.optional {
+                return Err(DesugarError::Unsupported(format!(
+                    "{m} without a preceding MATCH"
+                )));
+            }
+            return self.add_patterns(m);
+        };
+        let (first_join, first_condition) = (from.joins.len(), self.conditions.len());
+        self.add_patterns(m)?;
+        if m.optional {
+            self.make_optional(m, first_join, first_condition)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the paths of `m`, whose relationships are all distinct, as in
+    /// Cypher: `(a)-[r1]->(b)-[r2]->(c)` adds `r1.id <> r2.id` when both are
+    /// stored in the same table.
+    fn add_patterns(&mut self, m: &MatchClause) -> Result<(), DesugarError> {
+        let mut relationships = vec![];
+        for path in &m.pattern {
+            self.add_path(path, &mut relationships)?;
+        }
+        for (i, (edge, table)) in relationships.iter().enumerate() {
+            for (other, other_table) in &relationships[i + 1..] {
+                if core::ptr::eq(*table, *other_table) {
+                    self.conditions.push(Expr::BinaryOp {
+                        left: Box::new(column(Some(edge), &table.id_column)),
+                        op: BinaryOperator::NotEq,
+                        right: Box::new(column(Some(other), &table.id_column)),
+                    });
+                }
+            }
+        }
+        if let Some(selection) = &m.selection {
+            let selection = self.translate_expr(selection)?;
+            self.conditions.push(nested_if(
+                selection,
+                &[BinaryOperator::Or, BinaryOperator::Xor],
+            ));
+        }
+        Ok(())
+    }
+
+    /// Binds the variable of `UNWIND` to the elements of its list, whose
+    /// table is joined to the rows matched so far
+    fn add_unwind(&mut self, u: &UnwindClause) -> Result<(), DesugarError> {
+        if self.bindings.iter().any(|b| b.variable == u.alias) {
+            return Err(DesugarError::Unsupported(format!(
+                "variable `{}` bound more than once in {u}",
+                u.alias
+            )));
+        }
+        // generated, as the variable is often a keyword such as `row`
+        self.anonymous += 1;
+        let alias = Ident::new(format!("_n{}", self.anonymous));
+        let relation = unwind_table(self.dialect, u, &alias)?;
+        self.join_relation(relation, JoinOperator::CrossJoin(JoinConstraint::None));
+        self.bindings.push(Binding {
+            variable: u.alias.clone(),
+            alias,
+            kind: BindingKind::Element,
+        });
+        Ok(())
+    }
+
+    /// Turns the joins and conditions added by `OPTIONAL MATCH` into a
+    /// single `LEFT OUTER JOIN`, e.g. `(a)-[:OWNS]->(c)` becomes
+    /// `LEFT OUTER JOIN (edges AS _r1 JOIN nodes AS c ON _r1.dst_id = c.id)
+    /// ON _r1.src_id = a.id AND _r1.type = 'OWNS'`, so that the rows of the
+    /// tables joined before `first_join` are kept when the pattern has no match.
+    fn make_optional(
+        &mut self,
+        m: &MatchClause,
+        first_join: usize,
+        first_condition: usize,
+    ) -> Result<(), DesugarError> {
+        let Some(from) = &mut self.from else {
+            return Err(DesugarError::Unsupported(m.to_string()));
+        };
+        let outer: Vec<Ident> = core::iter::once(&from.relation)
+            .chain(from.joins[..first_join].iter().map(|join| &join.relation))
+            .filter_map(table_factor_name)
+            .collect();
+        let mut joins = from.joins.split_off(first_join).into_iter();
+        let Some(first) = joins.next() else {
+            return Err(DesugarError::Unsupported(format!(
+                "{m} without new pattern variables"
+            )));
+        };
+
+        let mut constraints = vec![];
+        if let JoinOperator::Join(JoinConstraint::On(constraint)) = first.join_operator {
+            constraints.push(constraint);
+        }
+        // constraints on the outer tables cannot stay inside the parentheses
+        let joins: Vec<Join> = joins
+            .map(|mut join| {
+                if let JoinOperator::Join(JoinConstraint::On(constraint)) = &join.join_operator {
+                    if mentions_any(constraint, &outer) {
+                        constraints.push(constraint.clone());
+                        join.join_operator = JoinOperator::CrossJoin(JoinConstraint::None);
+                    }
+                }
+                join
+            })
+            .collect();
+        constraints.extend(self.conditions.split_off(first_condition));
+
+        let relation = if joins.is_empty() {
+            first.relation
+        } else {
+            TableFactor::NestedJoin {
+                table_with_joins: Box::new(TableWithJoins {
+                    relation: first.relation,
+                    joins,
+                }),
+                alias: None,
+            }
+        };
+        let constraint =
+            and_all(constraints).unwrap_or_else(|| match self.dialect.is::<MsSqlDialect>() {
+                true => eq(number(1), number(1)),
+                false => Expr::value(Value::Boolean(true)),
+            });
+        from.joins.push(Join {
+            relation,
+            global: false,
+            join_operator: JoinOperator::LeftOuter(JoinConstraint::On(constraint)),
+        });
+        Ok(())
+    }
+
+    /// Joins the node and relationship tables along the path, e.g. `(a)-[r]->(b)`
+    /// becomes `nodes AS a JOIN edges AS r ON r.src_id = a.id JOIN nodes AS b ON r.dst_id = b.id`.
+    /// Variables that are already bound reuse their existing alias. The alias
+    /// and table of each relationship joined are added to `relationships`.
+    fn add_path(
+        &mut self,
+        path: &PathPattern,
+        relationships: &mut Vec<(Ident, &'a EdgeTable)>,
+    ) -> Result<(), DesugarError> {
+        let options = self.options;
+        let schema = &options.schema;
+        let mut previous = match self.lookup_node(&path.start.variable)? {
+            Some(node) => node,
+            None => {
+                let table = node_table(schema, &path.start)?;
+                let alias = self.bind(&path.start.variable, BindingKind::Node(table));
+                let operator = JoinOperator::CrossJoin(JoinConstraint::None);
+                self.join(&table.name, &alias, operator);
+                NodeRef { alias, table }
+            }
+        };
+        self.add_node_conditions(&path.start, &previous)?;
+
+        for segment in &path.segments {
+            let relationship = &segment.relationship;
+            if let Some(variable) = &relationship.variable {
+                if self.bindings.iter().any(|b| &b.variable == variable) {
+                    return Err(DesugarError::Unsupported(format!(
+                        "relationship variable bound more than once in {path}"
+                    )));
+                }
+            }
+            if let Some(length) = &relationship.length {
+                previous = self.add_variable_length_segment(segment, length, &previous)?;
+                continue;
+            }
+            let table = edge_table(schema, relationship)?;
+            let edge = self.bind(&relationship.variable, BindingKind::Relationship(table));
+            relationships.push((edge.clone(), table));
+            let direction = relationship.direction;
+
+            let node = match self.lookup_node(&segment.node.variable)? {
+                Some(node) => {
+                    let constraint =
+                        edge_constraint(direction, &edge, table, &previous, Some(&node));
+                    self.join(&table.name, &edge, inner_join(constraint));
+                    node
+                }
+                None => {
+                    let constraint = edge_constraint(direction, &edge, table, &previous, None);
+                    self.join(&table.name, &edge, inner_join(constraint));
+                    let node_table = node_table(schema, &segment.node)?;
+                    let node = NodeRef {
+                        alias: self.bind(&segment.node.variable, BindingKind::Node(node_table)),
+                        table: node_table,
+                    };
+                    let constraint = match direction {
+                        RelationshipDirection::Outgoing => {
+                            endpoint(&edge, &table.target_column, &node)
+                        }
+                        RelationshipDirection::Incoming => {
+                            endpoint(&edge, &table.source_column, &node)
+                        }
+                        RelationshipDirection::Undirected => {
+                            edge_constraint(direction, &edge, table, &previous, Some(&node))
+                        }
+                    };
+                    self.join(&node_table.name, &node.alias, inner_join(constraint));
+                    node
+                }
+            };
+
+            let relationship = RelationshipPattern {
+                properties: self.translate_properties(&relationship.properties)?,
+                ..relationship.clone()
+            };
+            self.conditions.extend(relationship_conditions(
+                self.dialect,
+                schema,
+                Some(&edge),
+                table,
+                &relationship,
+            )?);
+            self.add_node_conditions(&segment.node, &node)?;
+            previous = node;
+        }
+        Ok(())
+    }
+
+    /// Joins the paths of a variable-length relationship such as
+    /// `(a)-[r:KNOWS*1..3]->(b)`, which are computed by a recursive CTE,
+    /// to the node `previous` they start from.
+    fn add_variable_length_segment(
+        &mut self,
+        segment: &PathSegment,
+        length: &RelationshipLength,
+        previous: &NodeRef<'a>,
+    ) -> Result<NodeRef<'a>, DesugarError> {
+        let options = self.options;
+        let schema = &options.schema;
+        // the properties are matched in the CTE, where no variable is bound
+        let unbound = MatchScope::new(self.dialect, options);
+        if let Err(error) = unbound.translate_properties(&segment.relationship.properties) {
+            return Err(match error {
+                DesugarError::UnboundVariable(variable) => DesugarError::Unsupported(format!(
+                    "variable `{variable}` in the properties of {}",
+                    segment.relationship
+                )),
+                error => error,
+            });
+        }
+        let table = edge_table(schema, &segment.relationship)?;
+        self.anonymous += 1;
+        let paths = Ident::new(format!("_paths{}", self.anonymous));
+        self.ctes.push(paths_cte(
+            self.dialect,
+            schema,
+            &paths,
+            &segment.relationship,
+            length,
+            table,
+            previous.table,
+        )?);
+        // MSSQL CTEs refer to themselves without `RECURSIVE`
+        self.recursive = !self.dialect.is::<MsSqlDialect>();
+
+        let path = self.bind(&segment.relationship.variable, BindingKind::Path);
+        let mut constraint = eq(column(Some(&path), "start_id"), previous.id());
+        let node = match self.lookup_node(&segment.node.variable)? {
+            Some(node) => {
+                constraint = and(constraint, eq(column(Some(&path), "end_id"), node.id()));
+                self.join(&paths.value, &path, inner_join(constraint));
+                node
+            }
+            None => {
+                self.join(&paths.value, &path, inner_join(constraint));
+                let node_table = node_table(schema, &segment.node)?;
+                let node = NodeRef {
+                    alias: self.bind(&segment.node.variable, BindingKind::Node(node_table)),
+                    table: node_table,
+                };
+                let constraint = eq(column(Some(&path), "end_id"), node.id());
+                self.join(&node_table.name, &node.alias, inner_join(constraint));
+                node
+            }
+        };
+
+        if length.min_hops() > 0 {
+            self.conditions.push(Expr::BinaryOp {
+                left: Box::new(column(Some(&path), "hops")),
+                op: BinaryOperator::GtEq,
+                right: Box::new(number(length.min_hops())),
+            });
+        }
+        self.add_node_conditions(&segment.node, &node)?;
+        Ok(node)
+    }
+
+    fn add_node_conditions(
+        &mut self,
+        pattern: &NodePattern,
+        node: &NodeRef,
+    ) -> Result<(), DesugarError> {
+        let pattern = NodePattern {
+            properties: self.translate_properties(&pattern.properties)?,
+            ..pattern.clone()
+        };
+        let conditions = node_conditions(
+            self.dialect,
+            &self.options.schema,
+            Some(&node.alias),
+            node.table,
+            &pattern,
+        )?;
+        self.conditions.extend(conditions);
+        Ok(())
+    }
+
+    /// Adds `table AS alias` to the `FROM` clause, joined with `operator`
+    /// unless it is the first table.
+    fn join(&mut self, table: &str, alias: &Ident, operator: JoinOperator) {
+        let relation = table_with_joins(table, Some(alias.clone())).relation;
+        self.join_relation(relation, operator);
+    }
+
+    fn join_relation(&mut self, relation: TableFactor, operator: JoinOperator) {
+        match &mut self.from {
+            None => {
+                // the first table of a subquery can be joined to the rows of
+                // the enclosing query, see [`MatchScope::translate_exists`]
+                if let JoinOperator::Join(JoinConstraint::On(constraint)) = operator {
+                    self.conditions.push(constraint);
+                }
+                self.from = Some(TableWithJoins {
+                    relation,
+                    joins: vec![],
+                })
+            }
+            Some(from) => from.joins.push(Join {
+                relation,
+                global: false,
+                join_operator: operator,
+            }),
+        }
+    }
+
+    /// Binds a new variable, or generates an alias for an anonymous pattern
+    fn bind(&mut self, variable: &Option<Ident>, kind: BindingKind<'a>) -> Ident {
+        let alias = match variable {
+            Some(variable) => sql_ident(self.dialect, variable),
+            None => {
+                let prefix = match kind {
+                    BindingKind::Node(_) | BindingKind::Value | BindingKind::Element => "_n",
+                    BindingKind::Relationship(_) | BindingKind::Path => "_r",
+                };
+                self.anonymous += 1;
+                Ident::new(format!("{prefix}{}", self.anonymous))
+            }
+        };
+        if let Some(variable) = variable {
+            self.bindings.push(Binding {
+                variable: variable.clone(),
+                alias: alias.clone(),
+                kind,
+            });
+        }
+        alias
+    }
+
+    /// Returns the alias and table of an already bound node variable
+    fn lookup_node(&self, variable: &Option<Ident>) -> Result<Option<NodeRef<'a>>, DesugarError> {
+        let Some(variable) = variable else {
+            return Ok(None);
+        };
+        match self.bindings.iter().find(|b| &b.variable == variable) {
+            Some(Binding {
+                alias,
+                kind: BindingKind::Node(table),
+                ..
+            }) => Ok(Some(NodeRef {
+                alias: alias.clone(),
+                table,
+            })),
+            Some(_) => Err(DesugarError::Unsupported(format!(
+                "variable `{variable}` is used as both a node and a relationship"
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    fn resolve(&self, variable: &Ident) -> Result<&Binding<'a>, DesugarError> {
+        self.bindings
+            .iter()
+            .find(|b| &b.variable == variable)
+            .ok_or_else(|| DesugarError::UnboundVariable(variable.value.clone()))
+    }
+
+    /// Translates a Cypher expression over pattern variables into SQL over
+    /// their table aliases: `n.name` becomes
+    /// `json_extract(n.properties, '$.name')` and `n` becomes `n.id`.
+    fn translate_expr(&self, expr: &Expr) -> Result<Expr, DesugarError> {
+        let translate = |expr: &Expr| self.translate_expr(expr).map(Box::new);
+        Ok(match expr {
+            Expr::Identifier(variable) => {
+                let binding = self.resolve(variable)?;
+                match binding.kind {
+                    BindingKind::Value => Expr::CompoundIdentifier(vec![
+                        binding.alias.clone(),
+                        sql_ident(self.dialect, variable),
+                    ]),
+                    BindingKind::Element => element(self.dialect, &binding.alias),
+                    kind => match kind.id_column() {
+                        Some(id_column) => column(Some(&binding.alias), id_column),
+                        None => return Err(DesugarError::Unsupported(expr.to_string())),
+                    },
+                }
+            }
+            Expr::CompoundIdentifier(_) => self.translate_property(expr, None)?,
+            Expr::BinaryOp {
+                left: operand,
+                op,
+                right,
+            } => {
+                let left = Box::new(self.translate_operand(operand, op, right)?);
+                match op {
+                    BinaryOperator::CypherStartsWith
+                    | BinaryOperator::CypherEndsWith
+                    | BinaryOperator::CypherContains => {
+                        string_match(self.dialect, op, *left, self.translate_expr(right)?)?
+                    }
+                    BinaryOperator::CypherRegexMatch => {
+                        regex_match(self.dialect, *left, self.translate_expr(right)?)?
+                    }
+                    BinaryOperator::CypherIn => match right.as_ref() {
+                        Expr::Array(array) => Expr::InList {
+                            expr: left,
+                            list: array
+                                .elem
+                                .iter()
+                                .map(|e| self.translate_expr(e))
+                                .collect::<Result<_, _>>()?,
+                            negated: false,
+                        },
+                        _ => return Err(DesugarError::Unsupported(expr.to_string())),
+                    },
+                    op => Expr::BinaryOp {
+                        left,
+                        op: op.clone(),
+                        right: Box::new(self.translate_operand(right, op, operand)?),
+                    },
+                }
+            }
+            Expr::CypherPattern(_) | Expr::CypherExists(_) => self.translate_exists(expr, false)?,
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr: operand,
+            } if existential_match(operand).is_some() => self.translate_exists(operand, true)?,
+            Expr::UnaryOp {
+                op: op @ (UnaryOperator::Minus | UnaryOperator::Plus),
+                expr: operand,
+            } if matches!(operand.as_ref(), Expr::CompoundIdentifier(_)) => Expr::UnaryOp {
+                op: *op,
+                expr: Box::new(self.translate_property(operand, Some(ScalarType::Number))?),
+            },
+            Expr::UnaryOp { op, expr } => Expr::UnaryOp {
+                op: *op,
+                expr: translate(expr)?,
+            },
+            Expr::Nested(expr) => Expr::Nested(translate(expr)?),
+            Expr::IsNull(expr) => Expr::IsNull(translate(expr)?),
+            Expr::IsNotNull(expr) => Expr::IsNotNull(translate(expr)?),
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => Expr::InList {
+                expr: translate(expr)?,
+                list: list
+                    .iter()
+                    .map(|e| self.translate_expr(e))
+                    .collect::<Result<_, _>>()?,
+                negated: *negated,
+            },
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => Expr::Between {
+                expr: translate(expr)?,
+                negated: *negated,
+                low: translate(low)?,
+                high: translate(high)?,
+            },
+            Expr::Function(function) => {
+                let mut function = function.clone();
+                if is_function(&function, "collect") {
+                    function.name = object_name(&self.options.collect_function);
+                }
+                if let FunctionArguments::List(list) = &mut function.args {
+                    for arg in &mut list.args {
+                        if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+                        | FunctionArg::Named {
+                            arg: FunctionArgExpr::Expr(expr),
+                            ..
+                        } = arg
+                        {
+                            *expr = self.translate_expr(expr)?;
+                        }
+                    }
+                }
+                Expr::Function(function)
+            }
+            Expr::Value(_) => sql_literal(self.dialect, expr),
+            _ => return Err(DesugarError::Unsupported(expr.to_string())),
+        })
+    }
+
+    /// Translates a pattern predicate or `EXISTS { ... }` subquery into an
+    /// `EXISTS` subquery correlated with the variables bound so far, e.g.
+    /// `(a)-[:FRIEND]->(b)` becomes `EXISTS (SELECT 1 FROM edges AS _r1
+    /// WHERE _r1.src_id = a.id AND _r1.dst_id = b.id AND _r1.type = 'FRIEND')`.
+    /// When `negated`, as in `NOT (a)-->(b)`, it is an anti-join.
+    fn translate_exists(&self, expr: &Expr, negated: bool) -> Result<Expr, DesugarError> {
+        let Some(m) = existential_match(expr) else {
+            return Err(DesugarError::Unsupported(expr.to_string()));
+        };
+        let mut scope = MatchScope {
+            bindings: self.bindings.clone(),
+            anonymous: self.anonymous,
+            ..MatchScope::new(self.dialect, self.options)
+        };
+        scope.add_patterns(&m)?;
+        let subquery = scope.into_query(vec![SelectItem::UnnamedExpr(number(1))]);
+        Ok(Expr::Exists {
+            subquery: Box::new(subquery),
+            negated,
+        })
+    }
+
+    /// Translates `n.key` into the value of the property `key` of the row
+    /// bound to `n`. See [`property`] for `used_as`.
+    fn translate_property(
+        &self,
+        expr: &Expr,
+        used_as: Option<ScalarType>,
+    ) -> Result<Expr, DesugarError> {
+        let Expr::CompoundIdentifier(parts) = expr else {
+            return Err(DesugarError::Unsupported(expr.to_string()));
+        };
+        let [variable, key] = parts.as_slice() else {
+            return Err(DesugarError::Unsupported(expr.to_string()));
+        };
+        let binding = self.resolve(variable)?;
+        if let BindingKind::Value | BindingKind::Element = binding.kind {
+            let value = self.translate_expr(&Expr::Identifier(variable.clone()))?;
+            let member = json_extract(self.dialect, value, &[JsonStep::Key(&key.value)]);
+            return Ok(cast_json_text(self.dialect, member, used_as));
+        }
+        let Some(properties) = binding.kind.properties() else {
+            return Err(DesugarError::Unsupported(expr.to_string()));
+        };
+        property(
+            self.dialect,
+            Some(&binding.alias),
+            properties,
+            &key.value,
+            used_as,
+        )
+    }
+
+    /// Translates the operand `expr` of `op`, whose other operand is `other`.
+    /// A property is read as the type it is used as, see [`operand_type`].
+    fn translate_operand(
+        &self,
+        expr: &Expr,
+        op: &BinaryOperator,
+        other: &Expr,
+    ) -> Result<Expr, DesugarError> {
+        let sql = match (expr, operand_type(op, other)) {
+            (Expr::CompoundIdentifier(_), Some(used_as)) => {
+                self.translate_property(expr, Some(used_as))?
+            }
+            _ => self.translate_expr(expr)?,
+        };
+        // PostgreSQL's `->>` binds looser than arithmetic and concatenation
+        let arithmetic = matches!(
+            op,
+            BinaryOperator::Plus
+                | BinaryOperator::Minus
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+                | BinaryOperator::StringConcat
+        );
+        Ok(match arithmetic {
+            true => nested_if(
+                sql,
+                &[BinaryOperator::LongArrow, BinaryOperator::HashLongArrow],
+            ),
+            false => sql,
+        })
+    }
+
+    /// Translates the values of `properties` that are computed, e.g. `row.id`
+    /// or `$age + 1`, and keeps the literals
+    fn translate_properties(
+        &self,
+        properties: &[PropertyEntry],
+    ) -> Result<Vec<PropertyEntry>, DesugarError> {
+        properties
+            .iter()
+            .map(|entry| {
+                let value = match is_computed(&entry.value) {
+                    true => self.translate_expr(&entry.value)?,
+                    false => entry.value.clone(),
+                };
+                Ok(PropertyEntry {
+                    key: entry.key.clone(),
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// Translates the `properties` of a node or relationship to create, like
+    /// [`Self::translate_properties`], but a member of an unwound element
+    /// that goes into the JSON column of `storage` keeps its JSON type, e.g.
+    /// `row.id` is `_n1.value -> 'id'` rather than the text `_n1.value ->> 'id'`
+    fn translate_created_properties(
+        &self,
+        storage: &PropertyColumns,
+        properties: &[PropertyEntry],
+    ) -> Result<Vec<PropertyEntry>, DesugarError> {
+        let mut translated = self.translate_properties(properties)?;
+        for (entry, translated) in properties.iter().zip(&mut translated) {
+            let Expr::CompoundIdentifier(parts) = &entry.value else {
+                continue;
+            };
+            let [variable, key] = parts.as_slice() else {
+                continue;
+            };
+            let binding = self.resolve(variable)?;
+            if binding.kind == BindingKind::Element
+                && !storage.columns.contains_key(&entry.key.value)
+            {
+                let element = element(self.dialect, &binding.alias);
+                translated.value = json_member(self.dialect, element, &key.value);
+            }
+        }
+        Ok(translated)
+    }
+
+    /// `RETURN n` selects all columns of the row bound to `n`, other items
+    /// are translated with [`Self::translate_expr`]. Unaliased expressions
+    /// are named after their Cypher text quoted for the dialect, e.g.
+    /// `"n.name"`, as Cypher does.
+    fn projection_item(&self, item: &ProjectionItem) -> Result<SelectItem, DesugarError> {
+        if let Expr::Identifier(variable) = &item.expr {
+            let binding = self.resolve(variable)?;
+            if let BindingKind::Value | BindingKind::Element = binding.kind {
+                let expr = self.translate_expr(&item.expr)?;
+                let alias = sql_ident(self.dialect, item.alias.as_ref().unwrap_or(variable));
+                return Ok(SelectItem::ExprWithAlias { expr, alias });
+            }
+            if item.alias.is_some() {
+                return Err(DesugarError::Unsupported(format!(
+                    "aliasing a node or relationship in {item}"
+                )));
+            }
+            return Ok(SelectItem::QualifiedWildcard(
+                SelectItemQualifiedWildcardKind::ObjectName(ObjectName::from(vec![binding
+                    .alias
+                    .clone()])),
+                WildcardAdditionalOptions::default(),
+            ));
+        }
+
+        let expr = self.translate_expr(&item.expr)?;
+        let alias = match (&item.alias, &item.expr) {
+            (Some(alias), _) => sql_ident(self.dialect, alias),
+            (None, Expr::Value(_)) => return Ok(SelectItem::UnnamedExpr(expr)),
+            (None, cypher_expr) => {
+                let name = cypher_expr.to_string();
+                Ident::with_quote(identifier_quote(self.dialect, &name), name)
+            }
+        };
+        Ok(SelectItem::ExprWithAlias { expr, alias })
+    }
+
+    /// The `GROUP BY` keys of a non-aggregate `RETURN` item: all columns of
+    /// the row bound to `n` for `RETURN n`, otherwise the translated item.
+    fn grouping_keys(&self, expr: &Expr) -> Result<Vec<Expr>, DesugarError> {
+        if let Expr::Identifier(variable) = expr {
+            let binding = self.resolve(variable)?;
+            if let BindingKind::Value | BindingKind::Element = binding.kind {
+                return Ok(vec![self.translate_expr(expr)?]);
+            }
+            return Ok(binding
+                .kind
+                .columns()
+                .into_iter()
+                .map(|name| column(Some(&binding.alias), name))
+                .collect());
+        }
+        Ok(vec![self.translate_expr(expr)?])
+    }
+
+    /// Translate an `ORDER BY` key, which may also refer to a column
+    /// named by `AS` in the `RETURN` items.
+    fn translate_sort_key(
+        &self,
+        expr: &Expr,
+        items: &[ProjectionItem],
+    ) -> Result<Expr, DesugarError> {
+        if let Expr::Identifier(name) = expr {
+            if items.iter().any(|item| item.alias.as_ref() == Some(name)) {
+                return Ok(Expr::Identifier(sql_ident(self.dialect, name)));
+            }
+        }
+        self.translate_expr(expr)
+    }
+
+    /// Ends the current part of the query with `WITH`: the rows it projects
+    /// become a CTE named `_with1`, and the scope of the next part only
+    /// binds the projected variables. Nodes and relationships are passed on
+    /// by `id` and joined back to their table.
+    fn into_next_part(mut self, w: &WithClause) -> Result<MatchScope<'a>, DesugarError> {
+        self.anonymous += 1;
+        let name = Ident::new(format!("_with{}", self.anonymous));
+        let mut projection = vec![];
+        let mut bindings = vec![];
+        for item in &w.items {
+            let (variable, kind) = match (&item.alias, &item.expr) {
+                (alias, Expr::Identifier(variable)) => {
+                    let kind = self.resolve(variable)?.kind;
+                    (alias.as_ref().unwrap_or(variable), kind)
+                }
+                (Some(alias), _) => (alias, BindingKind::Value),
+                (None, _) => {
+                    return Err(DesugarError::Unsupported(format!(
+                        "expression without an alias in {w}"
+                    )))
+                }
+            };
+            let kind = match kind {
+                BindingKind::Path => return Err(DesugarError::Unsupported(w.to_string())),
+                BindingKind::Element => BindingKind::Value,
+                kind => kind,
+            };
+            let expr = self.translate_expr(&item.expr)?;
+            projection.push(SelectItem::ExprWithAlias {
+                expr,
+                alias: sql_ident(self.dialect, variable),
+            });
+            bindings.push((variable.clone(), kind));
+        }
+
+        let mut next = MatchScope::new(self.dialect, self.options);
+        next.ctes = core::mem::take(&mut self.ctes);
+        next.recursive = self.recursive;
+        next.anonymous = self.anonymous;
+        next.ctes.push(Cte {
+            alias: TableAlias {
+                name: name.clone(),
+                columns: vec![],
+            },
+            query: Box::new(project(self, projection, w.into())?),
+            from: None,
+            materialized: None,
+            closing_paren_token: AttachedToken::empty(),
+        });
+        next.from = Some(table_with_joins(&name.value, None));
+        for (variable, kind) in bindings {
+            let (Some(table), Some(id_column)) = (kind.table_name(), kind.id_column()) else {
+                next.bindings.push(Binding {
+                    variable,
+                    alias: name.clone(),
+                    kind,
+                });
+                continue;
+            };
+            let alias = sql_ident(next.dialect, &variable);
+            let constraint = eq(
+                column(Some(&alias), id_column),
+                Expr::CompoundIdentifier(vec![name.clone(), alias.clone()]),
+            );
+            next.join(table, &alias, inner_join(constraint));
+            next.bindings.push(Binding {
+                alias,
+                variable,
+                kind,
+            });
+        }
+        if let Some(selection) = &w.selection {
+            let selection = next.translate_expr(selection)?;
+            next.conditions.push(nested_if(
+                selection,
+                &[BinaryOperator::Or, BinaryOperator::Xor],
+            ));
+        }
+        Ok(next)
+    }
+
+    fn into_query(self, projection: Vec<SelectItem>) -> Query {
+        let select = select(
+            projection,
+            self.from.into_iter().collect(),
+            and_all(self.conditions),
+        );
+        let mut query = query(SetExpr::Select(Box::new(select)));
+        if !self.ctes.is_empty() {
+            query.with = Some(With {
+                with_token: AttachedToken::empty(),
+                recursive: self.recursive,
+                cte_tables: self.ctes,
+            });
+        }
+        query
+    }
+}
+
+/// The table storing the nodes matched by `node`
+fn node_table<'s>(
+    schema: &'s GraphSchema,
+    node: &NodePattern,
+) -> Result<&'s NodeTable, DesugarError> {
+    let mut labels = vec![];
+    if let Some(label) = &node.label {
+        matched_labels(label, &mut labels);
+    }
+    let mut tables = vec![];
+    if labels.is_empty() {
+        tables.push(schema.node_table(None));
+    }
+    for label in labels {
+        tables.push(schema.node_table(Some(&label.value)));
+    }
+    same_table(tables).ok_or_else(|| {
+        DesugarError::Unsupported(format!("no single table stores the nodes {node}"))
+    })
+}
+
+/// The table storing the relationships matched by `relationship`
+fn edge_table<'s>(
+    schema: &'s GraphSchema,
+    relationship: &RelationshipPattern,
+) -> Result<&'s EdgeTable, DesugarError> {
+    let mut rel_types = vec![];
+    if let Some(rel_type) = &relationship.rel_type {
+        matched_labels(rel_type, &mut rel_types);
+    }
+    let mut tables = vec![];
+    if rel_types.is_empty() {
+        tables.push(schema.edge_table(None));
+    }
+    for rel_type in rel_types {
+        tables.push(schema.edge_table(Some(&rel_type.value)));
+    }
+    same_table(tables).ok_or_else(|| {
+        DesugarError::Unsupported(format!(
+            "no single table stores the relationships {relationship}"
+        ))
+    })
+}
+
+/// The labels a label expression can match, i.e. those not under a `!`
+fn matched_labels<'a>(expression: &'a LabelExpression, labels: &mut Vec<&'a Ident>) {
+    match expression {
+        LabelExpression::Label(label) => labels.push(label),
+        LabelExpression::All(items) | LabelExpression::Any(items) => {
+            for item in items {
+                matched_labels(item, labels);
+            }
+        }
+        LabelExpression::Not(_) => {}
+    }
+}
+
+/// The table all of `tables` are, if they are the same one
+fn same_table<T>(tables: Vec<Option<&T>>) -> Option<&T> {
+    let mut tables = tables.into_iter();
+    let first = tables.next()??;
+    for table in tables {
+        if !core::ptr::eq(table?, first) {
+            return None;
+        }
+    }
+    Some(first)
+}
+
+/// The only label of `label`, for a pattern that creates or merges
+/// `pattern`, which cannot have several labels or a label expression
+fn single_label<'a>(
+    label: &'a Option<LabelExpression>,
+    pattern: &dyn fmt::Display,
+) -> Result<Option<&'a Ident>, DesugarError> {
+    match label {
+        None => Ok(None),
+        Some(expression) => match expression.as_label() {
+            Some(label) => Ok(Some(label)),
+            None => Err(DesugarError::Unsupported(format!(
+                "label expression `{expression}` when writing {pattern}"
+            ))),
+        },
+    }
+}
+
+/// The filter on `column_of(label)` matching the nodes or relationships of
+/// a label expression, e.g. `n.label IN ('Cat', 'Dog')` for `:Cat|Dog`, or
+/// `None` if the table of the pattern only stores matching rows. The column
+/// holds a single label, so `:Cat:Dog` cannot match, and a `NULL` in it is
+/// no label, which `:!Cat` matches with `OR n.label IS NULL`.
+fn label_condition<'c>(
+    expression: &LabelExpression,
+    qualifier: Option<&Ident>,
+    column_of: &dyn Fn(&Ident) -> Result<Option<&'c str>, DesugarError>,
+) -> Result<Option<Expr>, DesugarError> {
+    match expression {
+        LabelExpression::Label(label) => Ok(column_of(label)?
+            .map(|label_column| eq(column(qualifier, label_column), string_lit(&label.value)))),
+        LabelExpression::All(items) => {
+            let mut stored = vec![];
+            for label in items.iter().filter_map(LabelExpression::as_label) {
+                if column_of(label)?.is_some() && !stored.contains(&label) {
+                    stored.push(label);
+                }
+            }
+            if stored.len() > 1 {
+                return Err(DesugarError::Unsupported(format!(
+                    "label expression `{expression}` requiring several labels in one column"
+                )));
+            }
+            let mut conditions = vec![];
+            for item in items {
+                conditions.extend(label_condition(item, qualifier, column_of)?);
+            }
+            Ok(and_all(conditions))
+        }
+        LabelExpression::Any(items) => {
+            let mut conditions = vec![];
+            for item in items {
+                match label_condition(item, qualifier, column_of)? {
+                    Some(condition) => conditions.push(condition),
+                    None => return Ok(None),
+                }
+            }
+            Ok(any_of(conditions))
+        }
+        LabelExpression::Not(item) => {
+            let negated = negated_label_condition(expression, item, qualifier, column_of)?;
+            if matches_unlabeled(item) {
+                return Ok(Some(negated));
+            }
+            let mut labels = vec![];
+            label_leaves(item, &mut labels);
+            let mut label_column = None;
+            for label in labels {
+                label_column = label_column.or(column_of(label)?);
+            }
+            let label_column = label_column.expect("a label of a condition has a column");
+            let unlabeled = Expr::IsNull(Box::new(column(qualifier, label_column)));
+            Ok(any_of(vec![negated, unlabeled]))
+        }
+    }
+}
+
+/// The negation of the condition of `item`, the operand of `expression`
+fn negated_label_condition<'c>(
+    expression: &LabelExpression,
+    item: &LabelExpression,
+    qualifier: Option<&Ident>,
+    column_of: &dyn Fn(&Ident) -> Result<Option<&'c str>, DesugarError>,
+) -> Result<Expr, DesugarError> {
+    match label_condition(item, qualifier, column_of)? {
+        Some(Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        }) => Ok(Expr::BinaryOp {
+            left,
+            op: BinaryOperator::NotEq,
+            right,
+        }),
+        Some(Expr::InList {
+            expr,
+            list,
+            negated,
+        }) => Ok(Expr::InList {
+            expr,
+            list,
+            negated: !negated,
+        }),
+        Some(condition) => Ok(Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr: Box::new(Expr::Nested(Box::new(condition))),
+        }),
+        None => Err(DesugarError::Unsupported(format!(
+            "label expression `{expression}` excluding every row of its table"
+        ))),
+    }
+}
+
+/// Whether a label expression matches what has no label, e.g. `!Cat`
+fn matches_unlabeled(expression: &LabelExpression) -> bool {
+    match expression {
+        LabelExpression::Label(_) => false,
+        LabelExpression::All(items) => items.iter().all(matches_unlabeled),
+        LabelExpression::Any(items) => items.iter().any(matches_unlabeled),
+        LabelExpression::Not(item) => !matches_unlabeled(item),
+    }
+}
+
+/// All labels of a label expression, including those under a `!`
+fn label_leaves<'a>(expression: &'a LabelExpression, labels: &mut Vec<&'a Ident>) {
+    match expression {
+        LabelExpression::Label(label) => labels.push(label),
+        LabelExpression::All(items) | LabelExpression::Any(items) => {
+            for item in items {
+                label_leaves(item, labels);
+            }
+        }
+        LabelExpression::Not(item) => label_leaves(item, labels),
+    }
+}
+
+/// The disjunction of `conditions`: an `IN` list when they all compare the
+/// same column to a value, otherwise `OR`s in parentheses
+fn any_of(conditions: Vec<Expr>) -> Option<Expr> {
+    if conditions.len() < 2 {
+        return conditions.into_iter().next();
+    }
+    let mut target = None;
+    let mut list = vec![];
+    for condition in &conditions {
+        match condition {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Eq,
+                right,
+            } if target.is_none_or(|target| target == left) => {
+                target = Some(left);
+                list.push((**right).clone());
+            }
+            _ => break,
+        }
+    }
+    if let (Some(target), true) = (target, list.len() == conditions.len()) {
+        return Some(Expr::InList {
+            expr: target.clone(),
+            list,
+            negated: false,
+        });
+    }
+    conditions
+        .into_iter()
+        .map(|condition| nested_if(condition, &[BinaryOperator::And]))
+        .reduce(|left, right| Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOperator::Or,
+            right: Box::new(right),
+        })
+        .map(|disjunction| Expr::Nested(Box::new(disjunction)))
+}
+
+/// The column of `table` holding `label`, or `None` if `table` only stores
+/// the nodes with `label`
+fn node_label_column<'s>(
+    schema: &GraphSchema,
+    table: &'s NodeTable,
+    label: &Ident,
+) -> Result<Option<&'s str>, DesugarError> {
+    let mapped = schema.label_tables.get(&label.value);
+    if mapped.is_some_and(|mapped| core::ptr::eq(mapped, table)) {
+        return Ok(None);
+    }
+    match &table.label_column {
+        Some(label_column) => Ok(Some(label_column)),
+        None => Err(DesugarError::Unsupported(format!(
+            "label `{label}` is not stored in table `{}`",
+            table.name
+        ))),
+    }
+}
+
+/// The column of `table` holding `rel_type`, or `None` if `table` only
+/// stores the relationships of type `rel_type`
+fn edge_type_column<'s>(
+    schema: &GraphSchema,
+    table: &'s EdgeTable,
+    rel_type: &Ident,
+) -> Result<Option<&'s str>, DesugarError> {
+    let mapped = schema.relationship_tables.get(&rel_type.value);
+    if mapped.is_some_and(|mapped| core::ptr::eq(mapped, table)) {
+        return Ok(None);
+    }
+    match &table.type_column {
+        Some(type_column) => Ok(Some(type_column)),
+        None => Err(DesugarError::Unsupported(format!(
+            "relationship type `{rel_type}` is not stored in table `{}`",
+            table.name
+        ))),
+    }
+}
+
+/// Label and property filters of a node pattern, e.g. `(n:Bug {name: 'Ant'})`
+/// filters on `n.label = 'Bug' AND json_extract(n.properties, '$.name') = 'Ant'`
+fn node_conditions(
+    dialect: &dyn Dialect,
+    schema: &GraphSchema,
+    qualifier: Option<&Ident>,
+    table: &NodeTable,
+    node: &NodePattern,
+) -> Result<Vec<Expr>, DesugarError> {
+    let mut conditions = vec![];
+    if let Some(label) = &node.label {
+        conditions.extend(label_condition(label, qualifier, &|label| {
+            node_label_column(schema, table, label)
+        })?);
+    }
+    for entry in &node.properties {
+        conditions.push(property_filter(
+            dialect,
+            qualifier,
+            &table.properties,
+            entry,
+        )?);
+    }
+    Ok(conditions)
+}
+
+/// Type and property filters of a relationship pattern, e.g. `[r:KNOWS]`
+/// filters on `r.type = 'KNOWS'`
+fn relationship_conditions(
+    dialect: &dyn Dialect,
+    schema: &GraphSchema,
+    qualifier: Option<&Ident>,
+    table: &EdgeTable,
+    relationship: &RelationshipPattern,
+) -> Result<Vec<Expr>, DesugarError> {
+    let mut conditions = vec![];
+    if let Some(rel_type) = &relationship.rel_type {
+        conditions.extend(label_condition(rel_type, qualifier, &|rel_type| {
+            edge_type_column(schema, table, rel_type)
+        })?);
+    }
+    for entry in &relationship.properties {
+        conditions.push(property_filter(
+            dialect,
+            qualifier,
+            &table.properties,
+            entry,
+        )?);
+    }
+    Ok(conditions)
+}
+
+/// `property = value` for an entry `key: value` of the property map of a
+/// pattern
+fn property_filter(
+    dialect: &dyn Dialect,
+    qualifier: Option<&Ident>,
+    storage: &PropertyColumns,
+    entry: &PropertyEntry,
+) -> Result<Expr, DesugarError> {
+    if let Expr::Array(_) | Expr::Dictionary(_) = entry.value {
+        return Err(DesugarError::Unsupported(format!(
+            "matching a list or map property {{{entry}}}"
+        )));
+    }
+    let property = property(
+        dialect,
+        qualifier,
+        storage,
+        &entry.key.value,
+        literal_type(&entry.value),
+    )?;
+    Ok(eq(property, sql_literal(dialect, &entry.value)))
+}
+
+/// The Cypher literal `value` in `dialect`, where T-SQL has no boolean
+/// literals and writes `true` and `false` as `1` and `0`
+fn sql_literal(dialect: &dyn Dialect, value: &Expr) -> Expr {
+    match value {
+        Expr::Value(literal) if dialect.is::<MsSqlDialect>() => match literal.value {
+            Value::Boolean(boolean) => number(u64::from(boolean)),
+            _ => value.clone(),
+        },
+        _ => value.clone(),
+    }
+}
+
+/// The value of the property `key` of the row `qualifier`, e.g.
+/// `json_extract(n.properties, '$.name')`.
+///
+/// A property stored in JSON and `used_as` a number or boolean is cast to
+/// that type if the JSON accessor of `dialect` returns text, e.g.
+/// `CAST(n.properties ->> 'age' AS NUMERIC)` to compare with `42`.
+fn property(
+    dialect: &dyn Dialect,
+    qualifier: Option<&Ident>,
+    storage: &PropertyColumns,
+    key: &str,
+    used_as: Option<ScalarType>,
+) -> Result<Expr, DesugarError> {
+    let value = match (storage.columns.get(key), &storage.json_column) {
+        (Some(PropertyColumn::Column(name)), _) => return Ok(column(qualifier, name)),
+        (Some(PropertyColumn::JsonPath { column: name, path }), _) => {
+            let path = parse_json_path(path)?;
+            json_extract(dialect, column(qualifier, name), &path)
+        }
+        (None, Some(json_column)) => json_extract(
+            dialect,
+            column(qualifier, json_column),
+            &[JsonStep::Key(key)],
+        ),
+        (None, None) => return Err(unmapped_property(key)),
+    };
+    Ok(cast_json_text(dialect, value, used_as))
+}
+
+/// The type a property is used as, which it is read as when stored in JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScalarType {
+    Number,
+    Boolean,
+}
+
+/// The type a property operand of `op` is used as, given the `other` operand:
+/// the type of a number or boolean literal it is compared to, or of all the
+/// literals of a list it is `IN`, a number when ordered against a parameter,
+/// or a number for arithmetic, except for `+` with a string
+fn operand_type(op: &BinaryOperator, other: &Expr) -> Option<ScalarType> {
+    match op {
+        BinaryOperator::Eq | BinaryOperator::NotEq => literal_type(other),
+        BinaryOperator::CypherIn => match other {
+            Expr::Array(array) => {
+                let mut types = array.elem.iter().map(literal_type);
+                let first = types.next().flatten();
+                types.all(|other| other == first).then_some(first).flatten()
+            }
+            _ => None,
+        },
+        BinaryOperator::Lt | BinaryOperator::LtEq | BinaryOperator::Gt | BinaryOperator::GtEq => {
+            literal_type(other).or(match other {
+                Expr::Value(value) if matches!(value.value, Value::Placeholder(_)) => {
+                    Some(ScalarType::Number)
+                }
+                _ => None,
+            })
+        }
+        BinaryOperator::Plus => match other {
+            Expr::Value(value) if value.value.clone().into_string().is_some() => None,
+            _ => Some(ScalarType::Number),
+        },
+        BinaryOperator::Minus
+        | BinaryOperator::Multiply
+        | BinaryOperator::Divide
+        | BinaryOperator::Modulo => Some(ScalarType::Number),
+        _ => None,
+    }
+}
+
+/// The type of a number or boolean `literal`
+fn literal_type(literal: &Expr) -> Option<ScalarType> {
+    match literal {
+        Expr::Value(value) => match value.value {
+            Value::Number(..) => Some(ScalarType::Number),
+            Value::Boolean(_) => Some(ScalarType::Boolean),
+            _ => None,
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } if matches!(expr.as_ref(), Expr::Value(value) if matches!(value.value, Value::Number(..))) => {
+            Some(ScalarType::Number)
+        }
+        _ => None,
+    }
+}
+
+/// The JSON `value` cast to the type it is `used_as`, see [`json_text_type`]
+fn cast_json_text(dialect: &dyn Dialect, value: Expr, used_as: Option<ScalarType>) -> Expr {
+    match used_as.and_then(|used_as| json_text_type(dialect, used_as)) {
+        Some(data_type) => cast(value, data_type),
+        None => value,
+    }
+}
+
+/// The SQL type of `scalar`, if `dialect` extracts JSON values as text,
+/// which must be cast to it to be used as a number or boolean
+fn json_text_type(dialect: &dyn Dialect, scalar: ScalarType) -> Option<DataType> {
+    let numeric = scalar == ScalarType::Number;
+    if dialect.is::<PostgreSqlDialect>() {
+        Some(match numeric {
+            true => DataType::Numeric(ExactNumberInfo::None),
+            false => DataType::Boolean,
+        })
+    } else if dialect.is::<DuckDbDialect>() {
+        Some(match numeric {
+            true => DataType::Double(ExactNumberInfo::None),
+            false => DataType::Boolean,
+        })
+    } else if dialect.is::<BigQueryDialect>() {
+        Some(match numeric {
+            true => DataType::Float64,
+            false => DataType::Bool,
+        })
+    } else if dialect.is::<MsSqlDialect>() {
+        Some(match numeric {
+            true => DataType::Float(ExactNumberInfo::None),
+            false => DataType::Bit(None),
+        })
+    } else {
+        None
+    }
+}
+
+/// The table of the elements of the list expanded by `UNWIND`, aliased
+/// `alias`, in the syntax of `dialect`:
+///
+/// - PostgreSQL: `unnest(ARRAY[1, 2]) AS _n1 (value)`, with maps and lists
+///   as `JSONB`, or `unnest(CAST($1 AS JSONB[]))` for a parameter
+/// - DuckDB: `unnest([1, 2]) AS _n1 (value)`, or `unnest(CAST(? AS JSON[]))`
+/// - BigQuery: `UNNEST([1, 2]) AS _n1`, or `UNNEST(@rows)`
+/// - Snowflake: `LATERAL FLATTEN(INPUT => PARSE_JSON('[1,2]')) AS _n1`
+/// - others, e.g. SQLite: `json_each('[1,2]') AS _n1`
+///
+/// The list must be a literal or a parameter. The elements of a parameter
+/// are JSON values, and those of a literal are JSON where they are maps or
+/// lists. See [`element`] for the column of the element.
+fn unwind_table(
+    dialect: &dyn Dialect,
+    u: &UnwindClause,
+    alias: &Ident,
+) -> Result<TableFactor, DesugarError> {
+    let parameter = match &u.expr {
+        Expr::Value(v) if matches!(v.value, Value::Placeholder(_)) => Some(u.expr.clone()),
+        Expr::Array(_) => None,
+        _ => {
+            return Err(DesugarError::Unsupported(format!(
+                "UNWIND of an expression other than a list or a parameter in {u}"
+            )))
+        }
+    };
+    let json_array = || match &parameter {
+        Some(parameter) => Ok(parameter.clone()),
+        None => Ok(string_lit(&json_value(&u.expr)?)),
+    };
+    let alias = |columns: Vec<TableAliasColumnDef>| {
+        Some(TableAlias {
+            name: alias.clone(),
+            columns,
+        })
+    };
+    let table_function = |name: &str, lateral: bool, arg: FunctionArg| TableFactor::Function {
+        lateral,
+        name: object_name(name),
+        args: vec![arg],
+        alias: alias(vec![]),
+    };
+
+    if dialect.is::<SnowflakeDialect>() {
+        let input = function("PARSE_JSON", vec![json_array()?]);
+        return Ok(table_function(
+            "FLATTEN",
+            true,
+            FunctionArg::Named {
+                name: Ident::new("INPUT"),
+                arg: FunctionArgExpr::Expr(input),
+                operator: FunctionArgOperator::RightArrow,
+            },
+        ));
+    }
+    if dialect.is::<MySqlDialect>() || dialect.is::<MsSqlDialect>() {
+        return Err(DesugarError::Unsupported(format!(
+            "expanding a list into rows in {u}"
+        )));
+    }
+    let postgres = dialect.is::<PostgreSqlDialect>();
+    let bigquery = dialect.is::<BigQueryDialect>();
+    if !postgres && !bigquery && !dialect.is::<DuckDbDialect>() {
+        let arg = FunctionArg::Unnamed(FunctionArgExpr::Expr(json_array()?));
+        return Ok(table_function("json_each", false, arg));
+    }
+
+    let array = match (&parameter, &u.expr) {
+        (Some(parameter), _) if bigquery => parameter.clone(),
+        (Some(parameter), _) => {
+            let json = if postgres {
+                DataType::JSONB
+            } else {
+                DataType::JSON
+            };
+            Expr::Cast {
+                kind: CastKind::Cast,
+                expr: Box::new(parameter.clone()),
+                data_type: DataType::Array(ArrayElemTypeDef::SquareBracket(Box::new(json), None)),
+                format: None,
+            }
+        }
+        (None, Expr::Array(array)) => {
+            let nested = array
+                .elem
+                .iter()
+                .any(|e| matches!(e, Expr::Array(_) | Expr::Dictionary(_)));
+            let elem = array
+                .elem
+                .iter()
+                .map(|e| match e {
+                    _ if nested => Ok(json_literal(dialect, &json_value(e)?)),
+                    Expr::Value(_) | Expr::UnaryOp { .. } => Ok(e.clone()),
+                    _ => Err(DesugarError::Unsupported(format!(
+                        "list element {e} in {u}"
+                    ))),
+                })
+                .collect::<Result<_, DesugarError>>()?;
+            Expr::Array(crate::ast::Array {
+                elem,
+                named: postgres,
+            })
+        }
+        (None, _) => unreachable!("the list is a literal or a parameter"),
+    };
+    let columns = match bigquery {
+        true => vec![],
+        false => vec![TableAliasColumnDef::from_name("value")],
+    };
+    Ok(TableFactor::UNNEST {
+        alias: alias(columns),
+        array_exprs: vec![array],
+        with_offset: false,
+        with_offset_alias: None,
+        with_ordinality: false,
+    })
+}
+
+/// The element of the list expanded by `UNWIND` in the table aliased
+/// `alias`: the column `value`, or in BigQuery the alias itself
+fn element(dialect: &dyn Dialect, alias: &Ident) -> Expr {
+    if dialect.is::<BigQueryDialect>() {
+        Expr::Identifier(alias.clone())
+    } else {
+        column(Some(alias), "value")
+    }
+}
+
+fn unmapped_property(key: &str) -> DesugarError {
+    DesugarError::Unsupported(format!("property `{key}` is not stored in any column"))
+}
+
+/// `WITH RECURSIVE paths (start_id, end_id, hops, path)` following the
+/// relationships in `edges` matched by `relationship` one hop at a time,
+/// from every node in `start`. `path` lists the ids of the relationships
+/// followed so far, e.g. `,1,4,2,`, so that no relationship is followed twice
+/// and cycles terminate.
+///
+/// `path` is concatenated with `||`, except in MySQL, where `||` is `OR`,
+/// BigQuery, which only concatenates strings, and MSSQL, which use
+/// `CONCAT`. MySQL and MSSQL type the column after the first row, so it is
+/// cast to a long text, and BigQuery names the columns in the first `SELECT`
+/// as its CTEs have no column list. MSSQL CTEs are not declared `RECURSIVE`.
+fn paths_cte(
+    dialect: &dyn Dialect,
+    schema: &GraphSchema,
+    name: &Ident,
+    relationship: &RelationshipPattern,
+    length: &RelationshipLength,
+    edges: &EdgeTable,
+    start: &NodeTable,
+) -> Result<Cte, DesugarError> {
+    let p = Ident::new("p");
+    let e = Ident::new("e");
+    let is_bigquery = dialect.is::<BigQueryDialect>();
+    let concat = |parts: Vec<Expr>| {
+        if is_bigquery || dialect.is::<MySqlDialect>() || dialect.is::<MsSqlDialect>() {
+            return function("CONCAT", parts);
+        }
+        parts
+            .into_iter()
+            .reduce(|left, right| Expr::BinaryOp {
+                left: Box::new(left),
+                op: BinaryOperator::StringConcat,
+                right: Box::new(right),
+            })
+            .expect("concatenating at least one part")
+    };
+    let edge_id = column(Some(&e), &edges.id_column);
+    let edge_text = match is_bigquery {
+        true => cast(edge_id, DataType::String(None)),
+        false => edge_id,
+    };
+    let path_type = if dialect.is::<MySqlDialect>() {
+        Some(DataType::Char(Some(CharacterLength::IntegerLength {
+            length: 65535,
+            unit: None,
+        })))
+    } else if dialect.is::<MsSqlDialect>() {
+        Some(DataType::Varchar(Some(CharacterLength::Max)))
+    } else {
+        None
+    };
+    let typed = |path: Expr| match &path_type {
+        Some(path_type) => cast(path, path_type.clone()),
+        None => path,
+    };
+    let columns = ["start_id", "end_id", "hops", "path"];
+
+    // every node is reachable from itself in zero hops
+    let first_row = [
+        ident(&start.id_column),
+        ident(&start.id_column),
+        number(0),
+        typed(string_lit(",")),
+    ];
+    let start = select(
+        first_row
+            .into_iter()
+            .zip(columns)
+            .map(|(expr, name)| match is_bigquery {
+                true => SelectItem::ExprWithAlias {
+                    expr,
+                    alias: Ident::new(name),
+                },
+                false => SelectItem::UnnamedExpr(expr),
+            })
+            .collect(),
+        vec![table_with_joins(&start.name, None)],
+        None,
+    );
+
+    let end = column(Some(&p), "end_id");
+    let src = column(Some(&e), &edges.source_column);
+    let dst = column(Some(&e), &edges.target_column);
+    let (constraint, next) = match relationship.direction {
+        RelationshipDirection::Outgoing => (eq(src, end), dst),
+        RelationshipDirection::Incoming => (eq(dst, end), src),
+        RelationshipDirection::Undirected => (
+            Expr::Nested(Box::new(Expr::BinaryOp {
+                left: Box::new(eq(src.clone(), end.clone())),
+                op: BinaryOperator::Or,
+                right: Box::new(eq(dst.clone(), end.clone())),
+            })),
+            Expr::Case {
+                case_token: AttachedToken::empty(),
+                end_token: AttachedToken::empty(),
+                operand: None,
+                conditions: vec![CaseWhen {
+                    condition: eq(src.clone(), end),
+                    result: dst,
+                }],
+                else_result: Some(Box::new(src)),
+            },
+        ),
+    };
+
+    let mut conditions = relationship_conditions(dialect, schema, Some(&e), edges, relationship)?;
+    if let Some(max) = length.max_hops() {
+        conditions.push(Expr::BinaryOp {
+            left: Box::new(column(Some(&p), "hops")),
+            op: BinaryOperator::Lt,
+            right: Box::new(number(max)),
+        });
+    }
+    conditions.push(Expr::Like {
+        negated: true,
+        any: false,
+        expr: Box::new(column(Some(&p), "path")),
+        pattern: Box::new(Expr::Nested(Box::new(concat(vec![
+            string_lit("%,"),
+            edge_text.clone(),
+            string_lit(",%"),
+        ])))),
+        escape_char: None,
+    });
+
+    let mut from = table_with_joins(&name.value, Some(p.clone()));
+    from.joins.push(Join {
+        relation: table_with_joins(&edges.name, Some(e.clone())).relation,
+        global: false,
+        join_operator: inner_join(constraint),
+    });
+    let step = select(
+        vec![
+            SelectItem::UnnamedExpr(column(Some(&p), "start_id")),
+            SelectItem::UnnamedExpr(next.clone()),
+            SelectItem::UnnamedExpr(Expr::BinaryOp {
+                left: Box::new(column(Some(&p), "hops")),
+                op: BinaryOperator::Plus,
+                right: Box::new(number(1)),
+            }),
+            SelectItem::UnnamedExpr(typed(concat(vec![
+                column(Some(&p), "path"),
+                edge_text,
+                string_lit(","),
+            ]))),
+        ],
+        vec![from],
+        and_all(conditions),
+    );
+
+    Ok(Cte {
+        alias: TableAlias {
+            name: name.clone(),
+            columns: match is_bigquery {
+                true => vec![],
+                false => columns
+                    .into_iter()
+                    .map(TableAliasColumnDef::from_name)
+                    .collect(),
+            },
+        },
+        query: Box::new(query(SetExpr::SetOperation {
+            op: SetOperator::Union,
+            set_quantifier: SetQuantifier::All,
+            left: Box::new(SetExpr::Select(Box::new(start))),
+            right: Box::new(SetExpr::Select(Box::new(step))),
+        })),
+        from: None,
+        materialized: None,
+        closing_paren_token: AttachedToken::empty(),
+    })
+}
+
+/// `text STARTS WITH pattern`, `ENDS WITH` or `CONTAINS`, with the string
+/// functions of `dialect`. `LIKE` would treat `%` and `_` in `pattern` as
+/// wildcards, and ignores case on SQLite and MySQL, unlike Cypher.
+fn string_match(
+    dialect: &dyn Dialect,
+    op: &BinaryOperator,
+    text: Expr,
+    pattern: Expr,
+) -> Result<Expr, DesugarError> {
+    let found = |position: Expr| Expr::BinaryOp {
+        left: Box::new(position),
+        op: BinaryOperator::Gt,
+        right: Box::new(number(0)),
+    };
+    let length = |name: &str, expr: &Expr| function(name, vec![expr.clone()]);
+    Ok(
+        if dialect.is::<DuckDbDialect>() || dialect.is::<SnowflakeDialect>() {
+            let name = match (op, dialect.is::<DuckDbDialect>()) {
+                (BinaryOperator::CypherStartsWith, true) => "starts_with",
+                (BinaryOperator::CypherEndsWith, true) => "ends_with",
+                (_, true) => "contains",
+                (BinaryOperator::CypherStartsWith, false) => "STARTSWITH",
+                (BinaryOperator::CypherEndsWith, false) => "ENDSWITH",
+                (_, false) => "CONTAINS",
+            };
+            function(name, vec![text, pattern])
+        } else if dialect.is::<BigQueryDialect>() {
+            match op {
+                BinaryOperator::CypherStartsWith => function("STARTS_WITH", vec![text, pattern]),
+                BinaryOperator::CypherEndsWith => function("ENDS_WITH", vec![text, pattern]),
+                _ => found(function("STRPOS", vec![text, pattern])),
+            }
+        } else if dialect.is::<PostgreSqlDialect>() {
+            match op {
+                BinaryOperator::CypherStartsWith => function("starts_with", vec![text, pattern]),
+                BinaryOperator::CypherEndsWith => eq(
+                    function("right", vec![text, length("length", &pattern)]),
+                    pattern,
+                ),
+                _ => found(function("strpos", vec![text, pattern])),
+            }
+        } else if dialect.is::<MySqlDialect>() {
+            // comparing with a binary string is case-sensitive in any collation
+            let binary = cast(pattern.clone(), DataType::Binary(None));
+            match op {
+                BinaryOperator::CypherStartsWith => eq(
+                    function("LEFT", vec![text, length("CHAR_LENGTH", &pattern)]),
+                    binary,
+                ),
+                BinaryOperator::CypherEndsWith => eq(
+                    function("RIGHT", vec![text, length("CHAR_LENGTH", &pattern)]),
+                    binary,
+                ),
+                _ => found(function("LOCATE", vec![binary, text])),
+            }
+        } else if dialect.is::<MsSqlDialect>() {
+            return Err(DesugarError::Unsupported(format!(
+                "case-sensitive string matching with {op} on MSSQL"
+            )));
+        } else {
+            match op {
+                BinaryOperator::CypherStartsWith => eq(
+                    function("substr", vec![text, number(1), length("length", &pattern)]),
+                    pattern,
+                ),
+                BinaryOperator::CypherEndsWith => {
+                    let start = Expr::BinaryOp {
+                        left: Box::new(Expr::BinaryOp {
+                            left: Box::new(length("length", &text)),
+                            op: BinaryOperator::Minus,
+                            right: Box::new(length("length", &pattern)),
+                        }),
+                        op: BinaryOperator::Plus,
+                        right: Box::new(number(1)),
+                    };
+                    eq(function("substr", vec![text, start]), pattern)
+                }
+                _ => found(function("instr", vec![text, pattern])),
+            }
+        },
+    )
+}
+
+/// `text =~ regex`, which is true if `regex` matches the whole of `text`.
+/// On SQLite, `REGEXP` needs an application-defined `regexp()` function.
+fn regex_match(dialect: &dyn Dialect, text: Expr, regex: Expr) -> Result<Expr, DesugarError> {
+    let anchored = |regex| concat_around(dialect, regex, "^(?:", ")$");
+    Ok(if dialect.is::<DuckDbDialect>() {
+        function("regexp_full_match", vec![text, regex])
+    } else if dialect.is::<SnowflakeDialect>() {
+        // Snowflake's REGEXP_LIKE matches the whole string
+        function("REGEXP_LIKE", vec![text, regex])
+    } else if dialect.is::<BigQueryDialect>() {
+        function("REGEXP_CONTAINS", vec![text, anchored(regex)])
+    } else if dialect.is::<MySqlDialect>() {
+        // `c` for case-sensitive matching in any collation
+        function("REGEXP_LIKE", vec![text, anchored(regex), string_lit("c")])
+    } else if dialect.is::<PostgreSqlDialect>() {
+        Expr::BinaryOp {
+            left: Box::new(text),
+            op: BinaryOperator::PGRegexMatch,
+            right: Box::new(anchored(regex)),
+        }
+    } else if dialect.is::<MsSqlDialect>() {
+        return Err(DesugarError::Unsupported(
+            "regular expressions on MSSQL".to_string(),
+        ));
+    } else {
+        Expr::BinaryOp {
+            left: Box::new(text),
+            op: BinaryOperator::Regexp,
+            right: Box::new(anchored(regex)),
+        }
+    })
+}
+
+/// `'<prefix>' || expr || '<suffix>'`, or `CONCAT` on MySQL, where `||` is
+/// a logical OR, folded into a single string when `expr` is a string literal
+fn concat_around(dialect: &dyn Dialect, expr: Expr, prefix: &str, suffix: &str) -> Expr {
+    if let Expr::Value(v) = &expr {
+        if let Value::SingleQuotedString(s) | Value::DoubleQuotedString(s) = &v.value {
+            return string_lit(&format!("{prefix}{s}{suffix}"));
+        }
+    }
+    if dialect.is::<MySqlDialect>() {
+        return function("CONCAT", vec![string_lit(prefix), expr, string_lit(suffix)]);
+    }
+    let mut concat = expr;
+    if !prefix.is_empty() {
+        concat = Expr::BinaryOp {
+            left: Box::new(string_lit(prefix)),
+            op: BinaryOperator::StringConcat,
+            right: Box::new(concat),
+        };
+    }
+    if !suffix.is_empty() {
+        concat = Expr::BinaryOp {
+            left: Box::new(concat),
+            op: BinaryOperator::StringConcat,
+            right: Box::new(string_lit(suffix)),
+        };
+    }
+    Expr::Nested(Box::new(concat))
+}
+
+/// Whether `expr` calls an aggregate function such as `count(*)`
+fn is_aggregate(expr: &Expr) -> bool {
+    match expr {
+        Expr::Function(function) => {
+            AGGREGATE_FUNCTIONS
+                .iter()
+                .any(|name| is_function(function, name))
+                || match &function.args {
+                    FunctionArguments::List(list) => list.args.iter().any(|arg| match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+                        | FunctionArg::Named {
+                            arg: FunctionArgExpr::Expr(expr),
+                            ..
+                        } => is_aggregate(expr),
+                        _ => false,
+                    }),
+                    _ => false,
+                }
+        }
+        Expr::BinaryOp { left, right, .. } => is_aggregate(left) || is_aggregate(right),
+        Expr::UnaryOp { expr, .. }
+        | Expr::Nested(expr)
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr) => is_aggregate(expr),
+        _ => false,
+    }
+}
+
+fn is_function(function: &Function, name: &str) -> bool {
+    matches!(
+        function.name.0.as_slice(),
+        [ObjectNamePart::Identifier(ident)] if ident.value.eq_ignore_ascii_case(name)
+    )
+}
+
+/// The alias of a table in the `FROM` clause, or its name if not aliased
+fn table_factor_name(factor: &TableFactor) -> Option<Ident> {
+    match factor {
+        TableFactor::Table {
+            alias: Some(alias), ..
+        } => Some(alias.name.clone()),
+        TableFactor::Table { name, .. } => match name.0.last() {
+            Some(ObjectNamePart::Identifier(ident)) => Some(ident.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The `MATCH` of a pattern predicate or `EXISTS { ... }` subquery, if
+/// `expr` is one
+fn existential_match(expr: &Expr) -> Option<MatchClause> {
+    match expr {
+        Expr::Nested(expr) => existential_match(expr),
+        Expr::CypherPattern(pattern) => Some(MatchClause {
+            optional: false,
+            pattern: vec![pattern.as_ref().clone()],
+            selection: None,
+        }),
+        Expr::CypherExists(subquery) => Some(subquery.as_ref().clone()),
+        _ => None,
+    }
+}
+
+/// Whether `expr` may refer to a column of one of the `tables`
+fn mentions_any(expr: &Expr, tables: &[Ident]) -> bool {
+    match expr {
+        Expr::CompoundIdentifier(parts) => parts.first().is_some_and(|t| tables.contains(t)),
+        Expr::Identifier(_) | Expr::Value(_) => false,
+        Expr::BinaryOp { left, right, .. } => {
+            mentions_any(left, tables) || mentions_any(right, tables)
+        }
+        Expr::UnaryOp { expr, .. } | Expr::Nested(expr) => mentions_any(expr, tables),
+        _ => true,
+    }
+}
+
+fn inner_join(constraint: Expr) -> JoinOperator {
+    JoinOperator::Join(JoinConstraint::On(constraint))
+}
+
+/// Joins `edge` to the node `from` it is reached from and, if given, the node
+/// `to` it leads to, e.g. `r.src_id = a.id AND r.dst_id = b.id` for
+/// `(a)-[r]->(b)`. Undirected relationships match in either direction.
+fn edge_constraint(
+    direction: RelationshipDirection,
+    edge: &Ident,
+    table: &EdgeTable,
+    from: &NodeRef,
+    to: Option<&NodeRef>,
+) -> Expr {
+    let directed = |from_column, to_column| {
+        let mut constraint = endpoint(edge, from_column, from);
+        if let Some(to) = to {
+            constraint = and(constraint, endpoint(edge, to_column, to));
+        }
+        constraint
+    };
+    let (src, dst) = (&table.source_column, &table.target_column);
+    match direction {
+        RelationshipDirection::Outgoing => directed(src, dst),
+        RelationshipDirection::Incoming => directed(dst, src),
+        RelationshipDirection::Undirected => Expr::Nested(Box::new(Expr::BinaryOp {
+            left: Box::new(nested_if(directed(src, dst), &[BinaryOperator::And])),
+            op: BinaryOperator::Or,
+            right: Box::new(nested_if(directed(dst, src), &[BinaryOperator::And])),
+        })),
+    }
+}
+
+/// `edge.end_column = node.id`
+fn endpoint(edge: &Ident, end_column: &str, node: &NodeRef) -> Expr {
+    eq(column(Some(edge), end_column), node.id())
+}
+
+/// Wraps `expr` in parentheses if it is a binary operation with one of
+/// `ops`, so that it can be an operand of an operator that binds tighter
+fn nested_if(expr: Expr, ops: &[BinaryOperator]) -> Expr {
+    match &expr {
+        Expr::BinaryOp { op, .. } if ops.contains(op) => Expr::Nested(Box::new(expr)),
+        _ => expr,
+    }
+}
+
+/// The JSON object of a property map: a literal, or if any property is a
+/// parameter or a value computed by the query, a call to the JSON object
+/// constructor of `dialect`, e.g. `json_object('id', ?, 'age', 42)` for
+/// `{id: $id, age: 42}`. The literal is a string, except where JSON has a
+/// type of its own: `PARSE_JSON('{"age":42}')` on Snowflake and
+/// `JSON '{"age":42}'` on BigQuery.
+fn json_document(
+    dialect: &dyn Dialect,
+    properties: &[PropertyEntry],
+) -> Result<Expr, DesugarError> {
+    if !properties.iter().any(|p| is_computed(&p.value)) {
+        let json = json_object(properties)?;
+        return Ok(if dialect.is::<SnowflakeDialect>() {
+            function("PARSE_JSON", vec![string_lit(&json)])
+        } else if dialect.is::<BigQueryDialect>() {
+            Expr::TypedString(TypedString {
+                data_type: DataType::JSON,
+                value: Value::SingleQuotedString(json).with_empty_span(),
+                uses_odbc_syntax: false,
+            })
+        } else {
+            string_lit(&json)
+        });
+    }
+    let name = if dialect.is::<PostgreSqlDialect>() {
+        "json_build_object"
+    } else if dialect.is::<SnowflakeDialect>() {
+        "OBJECT_CONSTRUCT"
+    } else if dialect.is::<BigQueryDialect>() {
+        "JSON_OBJECT"
+    } else if dialect.is::<MsSqlDialect>() {
+        return Err(DesugarError::Unsupported(
+            "parameters or variables in the properties of a node or relationship".to_string(),
+        ));
+    } else {
+        "json_object"
+    };
+    let mut args = vec![];
+    for entry in properties {
+        if let Expr::Array(_) | Expr::Dictionary(_) = entry.value {
+            return Err(DesugarError::Unsupported(format!(
+                "list or map property {entry} next to a parameter or variable"
+            )));
+        }
+        args.push(string_lit(&entry.key.value));
+        args.push(entry.value.clone());
+    }
+    Ok(function(name, args))
+}
+
+/// Whether `value` is not a literal, i.e. neither a literal value nor a
+/// list or map of literals
+fn is_computed(value: &Expr) -> bool {
+    match value {
+        Expr::Value(_) => has_parameter(value),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => !matches!(expr.as_ref(), Expr::Value(v) if matches!(v.value, Value::Number(..))),
+        Expr::Array(array) => array.elem.iter().any(is_computed),
+        Expr::Dictionary(fields) => fields.iter().any(|f| is_computed(&f.value)),
+        _ => true,
+    }
+}
+
+/// Whether `value` is or contains a parameter placeholder
+fn has_parameter(value: &Expr) -> bool {
+    match value {
+        Expr::Value(v) => matches!(v.value, Value::Placeholder(_)),
+        Expr::Array(array) => array.elem.iter().any(has_parameter),
+        Expr::Dictionary(fields) => fields.iter().any(|f| has_parameter(&f.value)),
+        _ => false,
+    }
+}
+
+/// Renders a property map as a JSON object literal
+fn json_object(properties: &[PropertyEntry]) -> Result<String, DesugarError> {
+    let members = properties
+        .iter()
+        .map(|p| {
+            Ok(format!(
+                "{}:{}",
+                json_string(&p.key.value),
+                json_value(&p.value)?
+            ))
+        })
+        .collect::<Result<Vec<_>, DesugarError>>()?;
+    Ok(format!("{{{}}}", members.join(",")))
+}
+
+/// Renders a literal property value as JSON, e.g. `[1, -2.5, 'a']` as
+/// `[1,-2.5,"a"]` and `{open: true}` as `{"open":true}`
+fn json_value(value: &Expr) -> Result<String, DesugarError> {
+    let unsupported = || DesugarError::Unsupported(format!("property value {value}"));
+    Ok(match value {
+        Expr::Value(v) => match &v.value {
+            Value::SingleQuotedString(s) | Value::DoubleQuotedString(s) => json_string(s),
+            Value::Number(n, _) => json_number(&n.to_string()),
+            Value::Boolean(b) => b.to_string(),
+            Value::Null => "null".to_string(),
+            _ => return Err(unsupported()),
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => match expr.as_ref() {
+            Expr::Value(v) if matches!(v.value, Value::Number(..)) => {
+                format!("-{}", json_value(expr)?)
+            }
+            _ => return Err(unsupported()),
+        },
+        Expr::Array(array) => {
+            let elements = array
+                .elem
+                .iter()
+                .map(json_value)
+                .collect::<Result<Vec<_>, _>>()?;
+            format!("[{}]", elements.join(","))
+        }
+        Expr::Dictionary(fields) => {
+            let members = fields
+                .iter()
+                .map(|f| {
+                    Ok(format!(
+                        "{}:{}",
+                        json_string(&f.key.value),
+                        json_value(&f.value)?
+                    ))
+                })
+                .collect::<Result<Vec<_>, DesugarError>>()?;
+            format!("{{{}}}", members.join(","))
+        }
+        _ => return Err(unsupported()),
+    })
+}
+
+/// A number as JSON, which has no leading or trailing decimal point
+fn json_number(n: &str) -> String {
+    let n = match n.strip_prefix('.') {
+        Some(fraction) => format!("0.{fraction}"),
+        None => n.to_string(),
+    };
+    match n.strip_suffix('.') {
+        Some(integer) => integer.to_string(),
+        None => n,
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// A step of a JSON path: an object member or an array element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonStep<'p> {
+    Key(&'p str),
+    Index(u64),
+}
+
+impl JsonStep<'_> {
+    /// Whether the step is a key that needs no quoting in a path
+    fn is_plain(&self) -> bool {
+        match self {
+            JsonStep::Key(key) => {
+                !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }
+            JsonStep::Index(_) => true,
+        }
+    }
+}
+
+/// Parses a JSON path such as `$.address.city`, `$."full name"` or `$.tags[0]`
+fn parse_json_path(path: &str) -> Result<Vec<JsonStep<'_>>, DesugarError> {
+    let invalid = || DesugarError::Unsupported(format!("JSON path `{path}`"));
+    let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    let mut steps = vec![];
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix(".\"") {
+            let (key, after) = quoted.split_once('"').ok_or_else(invalid)?;
+            steps.push(JsonStep::Key(key));
+            rest = after;
+        } else if let Some(member) = rest.strip_prefix('.') {
+            let end = member.find(['.', '[']).unwrap_or(member.len());
+            if end == 0 {
+                return Err(invalid());
+            }
+            steps.push(JsonStep::Key(&member[..end]));
+            rest = &member[end..];
+        } else if let Some(element) = rest.strip_prefix('[') {
+            let (index, after) = element.split_once(']').ok_or_else(invalid)?;
+            steps.push(JsonStep::Index(index.parse().map_err(|_| invalid())?));
+            rest = after;
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok(steps)
+}
+
+/// The value at `path` in the JSON document `json` as a scalar, in the syntax
+/// of `dialect`:
+///
+/// - PostgreSQL: `json ->> 'name'`, or `json #>> '{address,city}'`
+/// - DuckDB: `json_extract_string(json, '$.name')`
+/// - Snowflake: `json:name`
+/// - BigQuery and MS SQL: `JSON_VALUE(json, '$.name')`
+/// - others, e.g. SQLite and MySQL: `json_extract(json, '$.name')`
+fn json_extract(dialect: &dyn Dialect, json: Expr, path: &[JsonStep]) -> Expr {
+    if dialect.is::<PostgreSqlDialect>() {
+        let (op, path) = match path {
+            [JsonStep::Key(key)] => (BinaryOperator::LongArrow, string_lit(key)),
+            [JsonStep::Index(index)] => (BinaryOperator::LongArrow, number(*index)),
+            _ => {
+                let steps: Vec<String> = path
+                    .iter()
+                    .map(|step| match step {
+                        JsonStep::Key(key) if step.is_plain() => key.to_string(),
+                        JsonStep::Key(key) => json_string(key),
+                        JsonStep::Index(index) => index.to_string(),
+                    })
+                    .collect();
+                let path = format!("{{{}}}", steps.join(","));
+                (BinaryOperator::HashLongArrow, string_lit(&path))
+            }
+        };
+        return Expr::BinaryOp {
+            left: Box::new(json),
+            op,
+            right: Box::new(path),
+        };
+    }
+    if dialect.is::<SnowflakeDialect>() {
+        let path = path
+            .iter()
+            .map(|step| match step {
+                JsonStep::Key(key) => JsonPathElem::Dot {
+                    key: key.to_string(),
+                    quoted: !step.is_plain(),
+                },
+                JsonStep::Index(index) => JsonPathElem::Bracket {
+                    key: number(*index),
+                },
+            })
+            .collect();
+        return Expr::JsonAccess {
+            value: Box::new(json),
+            path: JsonPath { path },
+        };
+    }
+
+    let name = if dialect.is::<DuckDbDialect>() {
+        "json_extract_string"
+    } else if dialect.is::<BigQueryDialect>() || dialect.is::<MsSqlDialect>() {
+        "JSON_VALUE"
+    } else {
+        "json_extract"
+    };
+    let path: String = path
+        .iter()
+        .map(|step| match step {
+            JsonStep::Key(key) if step.is_plain() => format!(".{key}"),
+            JsonStep::Key(key) => format!(".{}", json_string(key)),
+            JsonStep::Index(index) => format!("[{index}]"),
+        })
+        .collect();
+    function(name, vec![json, string_lit(&format!("${path}"))])
+}
+
+/// The path of the member `key` of a JSON object, e.g. `$.name` or
+/// `$."full name"`
+fn json_member_path(key: &str) -> Expr {
+    if JsonStep::Key(key).is_plain() {
+        string_lit(&format!("$.{key}"))
+    } else {
+        string_lit(&format!("$.{}", json_string(key)))
+    }
+}
+
+/// The JSON object `json` with its member `key` set to `value`, in the
+/// syntax of `dialect`:
+///
+/// - PostgreSQL: `json || jsonb_build_object('key', value)`
+/// - Snowflake: `OBJECT_INSERT(json, 'key', value, TRUE)`
+/// - DuckDB: `json_merge_patch(json, json_object('key', value))`
+/// - MS SQL: `JSON_MODIFY(json, '$.key', value)`
+/// - BigQuery: `JSON_SET(json, '$.key', value)`
+/// - others, e.g. SQLite and MySQL: `json_set(json, '$.key', value)`
+fn json_set(dialect: &dyn Dialect, json: Expr, key: &str, value: Expr) -> Expr {
+    if dialect.is::<PostgreSqlDialect>() {
+        return Expr::BinaryOp {
+            left: Box::new(json),
+            op: BinaryOperator::StringConcat,
+            right: Box::new(function("jsonb_build_object", vec![string_lit(key), value])),
+        };
+    }
+    if dialect.is::<SnowflakeDialect>() {
+        let overwrite = Expr::value(Value::Boolean(true));
+        return function(
+            "OBJECT_INSERT",
+            vec![json, string_lit(key), value, overwrite],
+        );
+    }
+    if dialect.is::<DuckDbDialect>() {
+        let member = function("json_object", vec![string_lit(key), value]);
+        return function("json_merge_patch", vec![json, member]);
+    }
+    let name = if dialect.is::<MsSqlDialect>() {
+        "JSON_MODIFY"
+    } else if dialect.is::<BigQueryDialect>() {
+        "JSON_SET"
+    } else {
+        "json_set"
+    };
+    function(name, vec![json, json_member_path(key), value])
+}
+
+/// The JSON object `json` without its member `key`, in the syntax of
+/// `dialect`, see [`json_set`]
+fn json_remove(dialect: &dyn Dialect, json: Expr, key: &str) -> Expr {
+    if dialect.is::<PostgreSqlDialect>() {
+        return Expr::BinaryOp {
+            left: Box::new(nested_if(json, &[BinaryOperator::StringConcat])),
+            op: BinaryOperator::Minus,
+            right: Box::new(string_lit(key)),
+        };
+    }
+    if dialect.is::<SnowflakeDialect>() {
+        return function("OBJECT_DELETE", vec![json, string_lit(key)]);
+    }
+    if dialect.is::<DuckDbDialect>() {
+        // merging a null member removes it
+        let patch = format!("{{{}:null}}", json_string(key));
+        return function("json_merge_patch", vec![json, string_lit(&patch)]);
+    }
+    if dialect.is::<MsSqlDialect>() {
+        let null = Expr::value(Value::Null);
+        return function("JSON_MODIFY", vec![json, json_member_path(key), null]);
+    }
+    let name = if dialect.is::<BigQueryDialect>() {
+        "JSON_REMOVE"
+    } else {
+        "json_remove"
+    };
+    function(name, vec![json, json_member_path(key)])
+}
+
+/// A JSON value written as `json` text, such as `true` or `[1,2]`, which
+/// would otherwise be stored as a string, e.g. `json('[1,2]')`
+fn json_literal(dialect: &dyn Dialect, json: &str) -> Expr {
+    let cast = |data_type| Expr::Cast {
+        kind: CastKind::Cast,
+        expr: Box::new(string_lit(json)),
+        data_type,
+        format: None,
+    };
+    if dialect.is::<PostgreSqlDialect>() {
+        cast(DataType::JSONB)
+    } else if dialect.is::<MySqlDialect>() {
+        cast(DataType::JSON)
+    } else if dialect.is::<SnowflakeDialect>() || dialect.is::<BigQueryDialect>() {
+        function("PARSE_JSON", vec![string_lit(json)])
+    } else if dialect.is::<MsSqlDialect>() && (json == "true" || json == "false") {
+        // JSON_MODIFY writes a BIT as a JSON boolean
+        Expr::Cast {
+            kind: CastKind::Cast,
+            expr: Box::new(number(u64::from(json == "true"))),
+            data_type: DataType::Bit(None),
+            format: None,
+        }
+    } else if dialect.is::<MsSqlDialect>() {
+        function("JSON_QUERY", vec![string_lit(json)])
+    } else {
+        function("json", vec![string_lit(json)])
+    }
+}
+
+/// The member `key` of the JSON object `json` as JSON, where
+/// [`json_extract`] returns text in some dialects:
+///
+/// - PostgreSQL: `json -> 'key'`
+/// - DuckDB: `json_extract(json, '$.key')`
+/// - BigQuery: `JSON_QUERY(json, '$.key')`
+/// - others: as [`json_extract`]
+fn json_member(dialect: &dyn Dialect, json: Expr, key: &str) -> Expr {
+    if dialect.is::<PostgreSqlDialect>() {
+        return Expr::BinaryOp {
+            left: Box::new(json),
+            op: BinaryOperator::Arrow,
+            right: Box::new(string_lit(key)),
+        };
+    }
+    if dialect.is::<DuckDbDialect>() {
+        return function("json_extract", vec![json, json_member_path(key)]);
+    }
+    if dialect.is::<BigQueryDialect>() {
+        return function("JSON_QUERY", vec![json, json_member_path(key)]);
+    }
+    json_extract(dialect, json, &[JsonStep::Key(key)])
+}
+
+/// `name(args)`
+fn function(name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function(Function {
+        name: object_name(name),
+        uses_odbc_syntax: false,
+        parameters: FunctionArguments::None,
+        args: FunctionArguments::List(FunctionArgumentList {
+            duplicate_treatment: None,
+            args: args
+                .into_iter()
+                .map(|arg| FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)))
+                .collect(),
+            clauses: vec![],
+        }),
+        filter: None,
+        null_treatment: None,
+        over: None,
+        within_group: vec![],
+    })
+}
+
+fn cast(expr: Expr, data_type: DataType) -> Expr {
+    Expr::Cast {
+        kind: CastKind::Cast,
+        expr: Box::new(expr),
+        data_type,
+        format: None,
+    }
+}
+
+fn and_all(conditions: Vec<Expr>) -> Option<Expr> {
+    conditions.into_iter().reduce(and)
+}
+
+fn and(left: Expr, right: Expr) -> Expr {
+    Expr::BinaryOp {
+        left: Box::new(left),
+        op: BinaryOperator::And,
+        right: Box::new(right),
+    }
+}
+
+fn eq(left: Expr, right: Expr) -> Expr {
+    Expr::BinaryOp {
+        left: Box::new(left),
+        op: BinaryOperator::Eq,
+        right: Box::new(right),
+    }
+}
+
+fn ident(name: &str) -> Expr {
+    Expr::Identifier(Ident::new(name))
+}
+
+/// The quote of the identifier `name` in `dialect`, e.g. `"` in PostgreSQL.
+/// MSSQL brackets cannot contain `]`, which is quoted with `"` instead.
+fn identifier_quote(dialect: &dyn Dialect, name: &str) -> char {
+    if dialect.is::<BigQueryDialect>() {
+        return '`';
+    }
+    match dialect.identifier_quote_style(name) {
+        Some('[') if name.contains(']') => '"',
+        quote => quote.unwrap_or('"'),
+    }
+}
+
+/// A Cypher variable or alias as a SQL identifier, quoted when it is a
+/// keyword such as `user` or `order`, or not a plain identifier
+fn sql_ident(dialect: &dyn Dialect, name: &Ident) -> Ident {
+    let plain = name
+        .value
+        .starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name
+            .value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && ALL_KEYWORDS
+            .binary_search(&name.value.to_uppercase().as_str())
+            .is_err();
+    if plain {
+        return Ident::new(&name.value);
+    }
+    Ident::with_quote(identifier_quote(dialect, &name.value), &name.value)
+}
+
+/// `qualifier.name`, or just `name` when there is no qualifier
+fn column(qualifier: Option<&Ident>, name: &str) -> Expr {
+    match qualifier {
+        Some(qualifier) => Expr::CompoundIdentifier(vec![qualifier.clone(), Ident::new(name)]),
+        None => ident(name),
+    }
+}
+
+fn number(n: u64) -> Expr {
+    Expr::value(Value::Number(
+        n.to_string().parse().expect("an integer is a valid number"),
+        false,
+    ))
+}
+
+fn string_lit(s: &str) -> Expr {
+    Expr::value(Value::SingleQuotedString(s.to_string()))
+}
+
+fn object_name(name: &str) -> ObjectName {
+    ObjectName(vec![ObjectNamePart::Identifier(Ident::new(name))])
+}
+
+fn table_with_joins(table: &str, alias: Option<Ident>) -> TableWithJoins {
+    table_named(object_name(table), alias)
+}
+
+fn table_named(name: ObjectName, alias: Option<Ident>) -> TableWithJoins {
+    TableWithJoins {
+        relation: TableFactor::Table {
+            name,
+            alias: alias.map(|name| TableAlias {
+                name,
+                columns: vec![],
+            }),
+            args: None,
+            with_hints: vec![],
+            version: None,
+            with_ordinality: false,
+            partitions: vec![],
+            json_path: None,
+            sample: None,
+            index_hints: vec![],
+        },
+        joins: vec![],
+    }
+}
+
+/// `(SELECT NULL)`, to sort by where T-SQL needs an `ORDER BY` but any
+/// order will do
+fn unordered() -> OrderByExpr {
+    let null = select(
+        vec![SelectItem::UnnamedExpr(Expr::value(Value::Null))],
+        vec![],
+        None,
+    );
+    OrderByExpr {
+        expr: Expr::Subquery(Box::new(query(SetExpr::Select(Box::new(null))))),
+        options: OrderByOptions {
+            asc: None,
+            nulls_first: None,
+        },
+        with_fill: None,
+    }
+}
+
+fn select(
+    projection: Vec<SelectItem>,
+    from: Vec<TableWithJoins>,
+    selection: Option<Expr>,
+) -> Select {
+    Select {
+        select_token: AttachedToken::empty(),
+        distinct: None,
+        top: None,
+        top_before_distinct: false,
+        projection,
+        exclude: None,
+        into: None,
+        from,
+        lateral_views: vec![],
+        prewhere: None,
+        selection,
+        group_by: GroupByExpr::Expressions(vec![], vec![]),
+        cluster_by: vec![],
+        distribute_by: vec![],
+        sort_by: vec![],
+        having: None,
+        named_window: vec![],
+        qualify: None,
+        window_before_qualify: false,
+        value_table_mode: None,
+        connect_by: None,
+        flavor: SelectFlavor::Standard,
+    }
+}
+
+/// The `SELECT` of a query built by [`MatchScope::into_query`]
+fn query_select(query: &mut Query) -> &mut Select {
+    match query.body.as_mut() {
+        SetExpr::Select(select) => select,
+        _ => unreachable!("MatchScope::into_query always builds a SELECT"),
+    }
+}
+
+fn query(body: SetExpr) -> Query {
+    Query {
+        with: None,
+        body: Box::new(body),
+        order_by: None,
+        limit_clause: None,
+        fetch: None,
+        locks: vec![],
+        for_clause: None,
+        settings: None,
+        format_clause: None,
+        pipe_operators: vec![],
+    }
+}
+
+/// `VALUES (row)`, or `SELECT row` on Snowflake, which only accepts
+/// constants in `VALUES`, not e.g. `PARSE_JSON('{}')`
+fn values(dialect: &dyn Dialect, row: Vec<Expr>) -> Query {
+    if dialect.is::<SnowflakeDialect>() {
+        let projection = row.into_iter().map(SelectItem::UnnamedExpr).collect();
+        return query(SetExpr::Select(Box::new(select(projection, vec![], None))));
+    }
+    query(SetExpr::Values(Values {
+        explicit_row: false,
+        value_keyword: false,
+        rows: vec![row],
+    }))
+}
+
+fn delete(table: &str, selection: Expr) -> Statement {
+    Statement::Delete(Delete {
+        delete_token: AttachedToken::empty(),
+        tables: vec![],
+        from: FromTable::WithFromKeyword(vec![table_with_joins(table, None)]),
+        using: None,
+        selection: Some(selection),
+        returning: None,
+        order_by: vec![],
+        limit: None,
+    })
+}
+
+/// `SELECT name FROM (ids) AS _ids`: MySQL cannot select the rows to change
+/// from the table it changes in a subquery (error 1093), unless through a
+/// derived table, which it materializes first
+fn derived_ids(ids: Query, name: &Ident) -> Query {
+    let derived = TableFactor::Derived {
+        lateral: false,
+        subquery: Box::new(ids),
+        alias: Some(TableAlias {
+            name: Ident::new("_ids"),
+            columns: vec![],
+        }),
+    };
+    let select = select(
+        vec![SelectItem::UnnamedExpr(Expr::Identifier(name.clone()))],
+        vec![TableWithJoins {
+            relation: derived,
+            joins: vec![],
+        }],
+        None,
+    );
+    query(SetExpr::Select(Box::new(select)))
+}
+
+/// `CREATE TEMPORARY TABLE name AS source`, which MSSQL writes as
+/// `SELECT ... INTO #name`
+fn temporary_table(dialect: &dyn Dialect, name: &str, mut source: Query) -> Statement {
+    if dialect.is::<MsSqlDialect>() {
+        let SetExpr::Select(select) = source.body.as_mut() else {
+            unreachable!("matched rows are selected with SELECT")
+        };
+        select.into = Some(SelectInto {
+            temporary: false,
+            unlogged: false,
+            table: false,
+            name: object_name(name),
+        });
+        return Statement::Query(Box::new(source));
+    }
+    CreateTableBuilder::new(object_name(name))
+        .temporary(true)
+        .query(Some(Box::new(source)))
+        .build()
+}
+
+fn drop_table(name: &str) -> Statement {
+    Statement::Drop {
+        object_type: ObjectType::Table,
+        if_exists: false,
+        names: vec![object_name(name)],
+        cascade: false,
+        restrict: false,
+        purge: false,
+        temporary: false,
+        table: None,
+    }
+}
+
+fn insert(table: &str, columns: Vec<Ident>, source: Query) -> Statement {
+    Statement::Insert(Insert {
+        insert_token: AttachedToken::empty(),
+        or: None,
+        ignore: false,
+        into: true,
+        table: TableObject::TableName(object_name(table)),
+        table_alias: None,
+        columns,
+        overwrite: false,
+        output: None,
+        source: Some(Box::new(source)),
+        assignments: vec![],
+        partitioned: None,
+        after_columns: vec![],
+        has_table_keyword: false,
+        on: None,
+        returning: None,
+        replace_into: false,
+        priority: None,
+        insert_alias: None,
+        settings: None,
+        format_clause: None,
+    })
+}
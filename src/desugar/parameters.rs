@@ -0,0 +1,684 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Cypher parameters such as `$id`, and the SQL placeholders they become.
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use crate::ast::{
+    AccessExpr, Assignment, CastFormat, CeilFloorKind, Expr, FromTable, Function, FunctionArg,
+    FunctionArgExpr, FunctionArgumentClause, FunctionArguments, GroupByExpr, JoinConstraint,
+    JoinOperator, JsonPathElem, LimitClause, MergeAction, MergeInsertKind, OnConflictAction,
+    OnInsert, OrderBy, OrderByExpr, OrderByKind, PathPattern, Query, Select, SelectItem, SetExpr,
+    Statement, Subscript, TableFactor, TableObject, TableWithJoins, TopQuantity,
+    UpdateTableFromKind, Value, WindowFrameBound, WindowType,
+};
+use crate::dialect::{BigQueryDialect, Dialect, MsSqlDialect, PostgreSqlDialect};
+
+/// How a dialect writes the placeholders of the parameters of a statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ParameterStyle {
+    /// `?`, bound by position
+    Positional,
+    /// `$1`, bound by number
+    Numbered,
+    /// `@name`, bound by name
+    Named,
+}
+
+impl ParameterStyle {
+    pub(super) fn of(dialect: &dyn Dialect) -> Self {
+        if dialect.is::<PostgreSqlDialect>() {
+            ParameterStyle::Numbered
+        } else if dialect.is::<BigQueryDialect>() || dialect.is::<MsSqlDialect>() {
+            ParameterStyle::Named
+        } else {
+            ParameterStyle::Positional
+        }
+    }
+
+    /// The placeholder of the parameter `name`, given the `names` of all
+    /// parameters in the order they are numbered
+    pub(super) fn placeholder(self, name: &str, names: &[String]) -> String {
+        match self {
+            ParameterStyle::Positional => "?".to_string(),
+            ParameterStyle::Numbered => {
+                let number = names.iter().position(|n| n == name).unwrap_or(names.len());
+                format!("${}", number + 1)
+            }
+            ParameterStyle::Named => format!("@{name}"),
+        }
+    }
+}
+
+/// Replaces each Cypher parameter `$name` left in the desugared `statement`
+/// with its placeholder in `style`, and returns the names bound to the
+/// placeholders, see [`super::DesugaredStatement::parameters`]
+pub(super) fn bind_parameters(statement: &mut Statement, style: ParameterStyle) -> Vec<String> {
+    let mut occurrences = vec![];
+    let mut names: Vec<String> = vec![];
+    visit_statement(statement, &mut |placeholder| {
+        let Some(name) = placeholder.strip_prefix('$') else {
+            return;
+        };
+        let name = name.to_string();
+        if !names.contains(&name) {
+            names.push(name.clone());
+        }
+        *placeholder = style.placeholder(&name, &names);
+        occurrences.push(name);
+    });
+    match style {
+        ParameterStyle::Positional => occurrences,
+        ParameterStyle::Numbered | ParameterStyle::Named => names,
+    }
+}
+
+/// The names of the Cypher parameters in the property maps of `patterns`
+pub(super) fn pattern_parameters(patterns: &[PathPattern]) -> Vec<String> {
+    let mut names = vec![];
+    for path in patterns {
+        let properties = path
+            .segments
+            .iter()
+            .flat_map(|segment| [&segment.relationship.properties, &segment.node.properties]);
+        for entry in core::iter::once(&path.start.properties)
+            .chain(properties)
+            .flatten()
+        {
+            visit_expr(&mut entry.value.clone(), &mut |placeholder| {
+                if let Some(name) = placeholder.strip_prefix('$') {
+                    names.push(name.to_string());
+                }
+            });
+        }
+    }
+    names
+}
+
+/// A function called with each placeholder, in the order they are displayed
+type Visitor<'a> = dyn FnMut(&mut String) + 'a;
+
+/// Visits the placeholders of the statements built by desugaring, which are
+/// queries, `INSERT`, `UPDATE`, `DELETE` and `MERGE`
+fn visit_statement(statement: &mut Statement, visit: &mut Visitor) {
+    match statement {
+        Statement::Query(query) => visit_query(query, visit),
+        Statement::Insert(insert) => {
+            if let TableObject::TableFunction(function) = &mut insert.table {
+                visit_function(function, visit);
+            }
+            insert
+                .partitioned
+                .iter_mut()
+                .flatten()
+                .for_each(|e| visit_expr(e, visit));
+            if let Some(source) = &mut insert.source {
+                visit_query(source, visit);
+            }
+            visit_assignments(&mut insert.assignments, visit);
+            match &mut insert.on {
+                Some(OnInsert::DuplicateKeyUpdate(assignments)) => {
+                    visit_assignments(assignments, visit)
+                }
+                Some(OnInsert::OnConflict(on_conflict)) => {
+                    if let OnConflictAction::DoUpdate(update) = &mut on_conflict.action {
+                        visit_assignments(&mut update.assignments, visit);
+                        update
+                            .selection
+                            .iter_mut()
+                            .for_each(|e| visit_expr(e, visit));
+                    }
+                }
+                None => {}
+            }
+            visit_select_items(insert.returning.iter_mut().flatten(), visit);
+        }
+        Statement::Update(update) => {
+            visit_table_with_joins(&mut update.table, visit);
+            if let Some(UpdateTableFromKind::BeforeSet(from)) = &mut update.from {
+                from.iter_mut()
+                    .for_each(|t| visit_table_with_joins(t, visit));
+            }
+            visit_assignments(&mut update.assignments, visit);
+            if let Some(UpdateTableFromKind::AfterSet(from)) = &mut update.from {
+                from.iter_mut()
+                    .for_each(|t| visit_table_with_joins(t, visit));
+            }
+            update
+                .selection
+                .iter_mut()
+                .for_each(|e| visit_expr(e, visit));
+            visit_select_items(update.returning.iter_mut().flatten(), visit);
+            update.limit.iter_mut().for_each(|e| visit_expr(e, visit));
+        }
+        Statement::Delete(delete) => {
+            let (FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from)) =
+                &mut delete.from;
+            from.iter_mut()
+                .for_each(|t| visit_table_with_joins(t, visit));
+            delete
+                .using
+                .iter_mut()
+                .flatten()
+                .for_each(|t| visit_table_with_joins(t, visit));
+            delete
+                .selection
+                .iter_mut()
+                .for_each(|e| visit_expr(e, visit));
+            visit_select_items(delete.returning.iter_mut().flatten(), visit);
+            visit_order_by_exprs(&mut delete.order_by, visit);
+            delete.limit.iter_mut().for_each(|e| visit_expr(e, visit));
+        }
+        Statement::Merge {
+            table,
+            source,
+            on,
+            clauses,
+            ..
+        } => {
+            visit_table_factor(table, visit);
+            visit_table_factor(source, visit);
+            visit_expr(on, visit);
+            for clause in clauses {
+                clause
+                    .predicate
+                    .iter_mut()
+                    .for_each(|e| visit_expr(e, visit));
+                match &mut clause.action {
+                    MergeAction::Insert(insert) => {
+                        if let MergeInsertKind::Values(values) = &mut insert.kind {
+                            values
+                                .rows
+                                .iter_mut()
+                                .flatten()
+                                .for_each(|e| visit_expr(e, visit));
+                        }
+                    }
+                    MergeAction::Update { assignments } => visit_assignments(assignments, visit),
+                    MergeAction::Delete => {}
+                }
+            }
+        }
+        Statement::CreateTable(create) => {
+            if let Some(query) = &mut create.query {
+                visit_query(query, visit);
+            }
+        }
+        // not built by desugaring
+        _ => {}
+    }
+}
+
+fn visit_query(query: &mut Query, visit: &mut Visitor) {
+    if let Some(with) = &mut query.with {
+        for cte in &mut with.cte_tables {
+            visit_query(&mut cte.query, visit);
+        }
+    }
+    visit_set_expr(&mut query.body, visit);
+    if let Some(order_by) = &mut query.order_by {
+        visit_order_by(order_by, visit);
+    }
+    match &mut query.limit_clause {
+        Some(LimitClause::LimitOffset {
+            limit,
+            offset,
+            limit_by,
+        }) => {
+            limit.iter_mut().for_each(|e| visit_expr(e, visit));
+            offset
+                .iter_mut()
+                .for_each(|o| visit_expr(&mut o.value, visit));
+            limit_by.iter_mut().for_each(|e| visit_expr(e, visit));
+        }
+        Some(LimitClause::OffsetCommaLimit { offset, limit }) => {
+            visit_expr(offset, visit);
+            visit_expr(limit, visit);
+        }
+        None => {}
+    }
+    if let Some(fetch) = &mut query.fetch {
+        fetch.quantity.iter_mut().for_each(|e| visit_expr(e, visit));
+    }
+}
+
+fn visit_set_expr(set_expr: &mut SetExpr, visit: &mut Visitor) {
+    match set_expr {
+        SetExpr::Select(select) => visit_select(select, visit),
+        SetExpr::Query(query) => visit_query(query, visit),
+        SetExpr::SetOperation { left, right, .. } => {
+            visit_set_expr(left, visit);
+            visit_set_expr(right, visit);
+        }
+        SetExpr::Values(values) => values
+            .rows
+            .iter_mut()
+            .flatten()
+            .for_each(|e| visit_expr(e, visit)),
+        SetExpr::Insert(statement)
+        | SetExpr::Update(statement)
+        | SetExpr::Delete(statement)
+        | SetExpr::Merge(statement) => visit_statement(statement, visit),
+        SetExpr::Table(_) => {}
+    }
+}
+
+fn visit_select(select: &mut Select, visit: &mut Visitor) {
+    if let Some(crate::ast::Distinct::On(exprs)) = &mut select.distinct {
+        exprs.iter_mut().for_each(|e| visit_expr(e, visit));
+    }
+    if let Some(TopQuantity::Expr(quantity)) = select.top.as_mut().and_then(|t| t.quantity.as_mut())
+    {
+        visit_expr(quantity, visit);
+    }
+    visit_select_items(select.projection.iter_mut(), visit);
+    select
+        .from
+        .iter_mut()
+        .for_each(|t| visit_table_with_joins(t, visit));
+    select
+        .prewhere
+        .iter_mut()
+        .for_each(|e| visit_expr(e, visit));
+    select
+        .selection
+        .iter_mut()
+        .for_each(|e| visit_expr(e, visit));
+    if let GroupByExpr::Expressions(exprs, _) = &mut select.group_by {
+        exprs.iter_mut().for_each(|e| visit_expr(e, visit));
+    }
+    select
+        .cluster_by
+        .iter_mut()
+        .for_each(|e| visit_expr(e, visit));
+    select
+        .distribute_by
+        .iter_mut()
+        .for_each(|e| visit_expr(e, visit));
+    visit_order_by_exprs(&mut select.sort_by, visit);
+    select.having.iter_mut().for_each(|e| visit_expr(e, visit));
+    select.qualify.iter_mut().for_each(|e| visit_expr(e, visit));
+}
+
+fn visit_select_items<'i>(items: impl Iterator<Item = &'i mut SelectItem>, visit: &mut Visitor) {
+    for item in items {
+        if let SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } = item {
+            visit_expr(expr, visit);
+        }
+    }
+}
+
+fn visit_table_with_joins(table: &mut TableWithJoins, visit: &mut Visitor) {
+    visit_table_factor(&mut table.relation, visit);
+    for join in &mut table.joins {
+        visit_table_factor(&mut join.relation, visit);
+        let constraint = match &mut join.join_operator {
+            JoinOperator::AsOf {
+                match_condition,
+                constraint,
+            } => {
+                visit_expr(match_condition, visit);
+                constraint
+            }
+            JoinOperator::Join(constraint)
+            | JoinOperator::Inner(constraint)
+            | JoinOperator::Left(constraint)
+            | JoinOperator::LeftOuter(constraint)
+            | JoinOperator::Right(constraint)
+            | JoinOperator::RightOuter(constraint)
+            | JoinOperator::FullOuter(constraint)
+            | JoinOperator::CrossJoin(constraint)
+            | JoinOperator::Semi(constraint)
+            | JoinOperator::LeftSemi(constraint)
+            | JoinOperator::RightSemi(constraint)
+            | JoinOperator::Anti(constraint)
+            | JoinOperator::LeftAnti(constraint)
+            | JoinOperator::RightAnti(constraint)
+            | JoinOperator::StraightJoin(constraint) => constraint,
+            JoinOperator::CrossApply | JoinOperator::OuterApply => continue,
+        };
+        if let JoinConstraint::On(expr) = constraint {
+            visit_expr(expr, visit);
+        }
+    }
+}
+
+/// Visits the table factors built by desugaring: tables, subqueries, joins
+/// and table functions
+fn visit_table_factor(table: &mut TableFactor, visit: &mut Visitor) {
+    match table {
+        TableFactor::Derived { subquery, .. } => visit_query(subquery, visit),
+        TableFactor::NestedJoin {
+            table_with_joins, ..
+        } => visit_table_with_joins(table_with_joins, visit),
+        TableFactor::Function { args, .. } => visit_function_args(args, visit),
+        TableFactor::UNNEST { array_exprs, .. } => {
+            array_exprs.iter_mut().for_each(|e| visit_expr(e, visit))
+        }
+        TableFactor::TableFunction { expr, .. } => visit_expr(expr, visit),
+        // not built by desugaring, or without expressions as `TableFactor::Table`
+        _ => {}
+    }
+}
+
+fn visit_assignments(assignments: &mut [Assignment], visit: &mut Visitor) {
+    assignments
+        .iter_mut()
+        .for_each(|assignment| visit_expr(&mut assignment.value, visit));
+}
+
+fn visit_order_by(order_by: &mut OrderBy, visit: &mut Visitor) {
+    if let OrderByKind::Expressions(exprs) = &mut order_by.kind {
+        visit_order_by_exprs(exprs, visit);
+    }
+}
+
+fn visit_order_by_exprs(exprs: &mut [OrderByExpr], visit: &mut Visitor) {
+    exprs
+        .iter_mut()
+        .for_each(|e| visit_expr(&mut e.expr, visit));
+}
+
+fn visit_function(function: &mut Function, visit: &mut Visitor) {
+    for arguments in [&mut function.parameters, &mut function.args] {
+        match arguments {
+            FunctionArguments::None => {}
+            FunctionArguments::Subquery(query) => visit_query(query, visit),
+            FunctionArguments::List(list) => {
+                visit_function_args(&mut list.args, visit);
+                for clause in &mut list.clauses {
+                    match clause {
+                        FunctionArgumentClause::OrderBy(exprs) => {
+                            visit_order_by_exprs(exprs, visit)
+                        }
+                        FunctionArgumentClause::Limit(expr) => visit_expr(expr, visit),
+                        FunctionArgumentClause::Separator(value) => visit_value(value, visit),
+                        FunctionArgumentClause::IgnoreOrRespectNulls(_)
+                        | FunctionArgumentClause::OnOverflow(_)
+                        | FunctionArgumentClause::Having(_)
+                        | FunctionArgumentClause::JsonNullClause(_)
+                        | FunctionArgumentClause::JsonReturningClause(_) => {}
+                    }
+                }
+            }
+        }
+    }
+    visit_order_by_exprs(&mut function.within_group, visit);
+    function
+        .filter
+        .iter_mut()
+        .for_each(|e| visit_expr(e, visit));
+    if let Some(WindowType::WindowSpec(spec)) = &mut function.over {
+        spec.partition_by
+            .iter_mut()
+            .for_each(|e| visit_expr(e, visit));
+        visit_order_by_exprs(&mut spec.order_by, visit);
+        if let Some(frame) = &mut spec.window_frame {
+            for bound in core::iter::once(&mut frame.start_bound).chain(&mut frame.end_bound) {
+                if let WindowFrameBound::Preceding(Some(expr))
+                | WindowFrameBound::Following(Some(expr)) = bound
+                {
+                    visit_expr(expr, visit);
+                }
+            }
+        }
+    }
+}
+
+fn visit_function_args(args: &mut [FunctionArg], visit: &mut Visitor) {
+    for arg in args {
+        let arg = match arg {
+            FunctionArg::ExprNamed { name, arg, .. } => {
+                visit_expr(name, visit);
+                arg
+            }
+            FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg) => arg,
+        };
+        if let FunctionArgExpr::Expr(expr) = arg {
+            visit_expr(expr, visit);
+        }
+    }
+}
+
+fn visit_value(value: &mut Value, visit: &mut Visitor) {
+    if let Value::Placeholder(placeholder) = value {
+        visit(placeholder);
+    }
+}
+
+/// Visits every expression that `expr` is made of
+fn visit_expr(expr: &mut Expr, visit: &mut Visitor) {
+    match expr {
+        Expr::Value(value) => visit_value(&mut value.value, visit),
+        Expr::TypedString(typed) => visit_value(&mut typed.value.value, visit),
+        Expr::MatchAgainst { match_value, .. } => visit_value(match_value, visit),
+        Expr::Identifier(_)
+        | Expr::CompoundIdentifier(_)
+        | Expr::Wildcard(_)
+        | Expr::QualifiedWildcard(..) => {}
+        Expr::CompoundFieldAccess { root, access_chain } => {
+            visit_expr(root, visit);
+            for access in access_chain {
+                match access {
+                    AccessExpr::Dot(expr)
+                    | AccessExpr::Subscript(Subscript::Index { index: expr }) => {
+                        visit_expr(expr, visit)
+                    }
+                    AccessExpr::Subscript(Subscript::Slice {
+                        lower_bound,
+                        upper_bound,
+                        stride,
+                    }) => [lower_bound, upper_bound, stride]
+                        .into_iter()
+                        .flatten()
+                        .for_each(|e| visit_expr(e, visit)),
+                }
+            }
+        }
+        Expr::JsonAccess { value, path } => {
+            visit_expr(value, visit);
+            for element in &mut path.path {
+                if let JsonPathElem::Bracket { key } = element {
+                    visit_expr(key, visit);
+                }
+            }
+        }
+        Expr::IsFalse(expr)
+        | Expr::IsNotFalse(expr)
+        | Expr::IsTrue(expr)
+        | Expr::IsNotTrue(expr)
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::IsUnknown(expr)
+        | Expr::IsNotUnknown(expr)
+        | Expr::IsNormalized { expr, .. }
+        | Expr::UnaryOp { expr, .. }
+        | Expr::Extract { expr, .. }
+        | Expr::Collate { expr, .. }
+        | Expr::Nested(expr)
+        | Expr::Prefixed { value: expr, .. }
+        | Expr::Named { expr, .. }
+        | Expr::OuterJoin(expr)
+        | Expr::Prior(expr) => visit_expr(expr, visit),
+        Expr::Interval(interval) => visit_expr(&mut interval.value, visit),
+        Expr::Lambda(lambda) => visit_expr(&mut lambda.body, visit),
+        Expr::Ceil { expr, field } | Expr::Floor { expr, field } => {
+            visit_expr(expr, visit);
+            if let CeilFloorKind::Scale(scale) = field {
+                visit_value(scale, visit);
+            }
+        }
+        Expr::IsDistinctFrom(left, right)
+        | Expr::IsNotDistinctFrom(left, right)
+        | Expr::BinaryOp { left, right, .. }
+        | Expr::AnyOp { left, right, .. }
+        | Expr::AllOp { left, right, .. }
+        | Expr::AtTimeZone {
+            timestamp: left,
+            time_zone: right,
+        }
+        | Expr::Position {
+            expr: left,
+            r#in: right,
+        }
+        | Expr::InUnnest {
+            expr: left,
+            array_expr: right,
+            ..
+        }
+        | Expr::RLike {
+            expr: left,
+            pattern: right,
+            ..
+        } => {
+            visit_expr(left, visit);
+            visit_expr(right, visit);
+        }
+        Expr::MemberOf(member_of) => {
+            visit_expr(&mut member_of.value, visit);
+            visit_expr(&mut member_of.array, visit);
+        }
+        Expr::Like {
+            expr,
+            pattern,
+            escape_char,
+            ..
+        }
+        | Expr::ILike {
+            expr,
+            pattern,
+            escape_char,
+            ..
+        }
+        | Expr::SimilarTo {
+            expr,
+            pattern,
+            escape_char,
+            ..
+        } => {
+            visit_expr(expr, visit);
+            visit_expr(pattern, visit);
+            escape_char.iter_mut().for_each(|v| visit_value(v, visit));
+        }
+        Expr::InList { expr, list, .. } => {
+            visit_expr(expr, visit);
+            list.iter_mut().for_each(|e| visit_expr(e, visit));
+        }
+        Expr::InSubquery { expr, subquery, .. } => {
+            visit_expr(expr, visit);
+            visit_query(subquery, visit);
+        }
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            visit_expr(expr, visit);
+            visit_expr(low, visit);
+            visit_expr(high, visit);
+        }
+        Expr::Convert { expr, styles, .. } => {
+            visit_expr(expr, visit);
+            styles.iter_mut().for_each(|e| visit_expr(e, visit));
+        }
+        Expr::Cast { expr, format, .. } => {
+            visit_expr(expr, visit);
+            match format {
+                Some(CastFormat::Value(value)) => visit_value(value, visit),
+                Some(CastFormat::ValueAtTimeZone(value, time_zone)) => {
+                    visit_value(value, visit);
+                    visit_value(time_zone, visit);
+                }
+                None => {}
+            }
+        }
+        Expr::Substring {
+            expr,
+            substring_from,
+            substring_for,
+            ..
+        } => {
+            visit_expr(expr, visit);
+            [substring_from, substring_for]
+                .into_iter()
+                .flatten()
+                .for_each(|e| visit_expr(e, visit));
+        }
+        Expr::Trim {
+            expr,
+            trim_what,
+            trim_characters,
+            ..
+        } => {
+            trim_what.iter_mut().for_each(|e| visit_expr(e, visit));
+            visit_expr(expr, visit);
+            trim_characters
+                .iter_mut()
+                .flatten()
+                .for_each(|e| visit_expr(e, visit));
+        }
+        Expr::Overlay {
+            expr,
+            overlay_what,
+            overlay_from,
+            overlay_for,
+        } => {
+            visit_expr(expr, visit);
+            visit_expr(overlay_what, visit);
+            visit_expr(overlay_from, visit);
+            overlay_for.iter_mut().for_each(|e| visit_expr(e, visit));
+        }
+        Expr::Function(function) => visit_function(function, visit),
+        Expr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => {
+            operand.iter_mut().for_each(|e| visit_expr(e, visit));
+            for when in conditions {
+                visit_expr(&mut when.condition, visit);
+                visit_expr(&mut when.result, visit);
+            }
+            else_result.iter_mut().for_each(|e| visit_expr(e, visit));
+        }
+        Expr::Exists { subquery, .. } | Expr::Subquery(subquery) => visit_query(subquery, visit),
+        Expr::GroupingSets(sets) | Expr::Cube(sets) | Expr::Rollup(sets) => {
+            sets.iter_mut().flatten().for_each(|e| visit_expr(e, visit))
+        }
+        Expr::Tuple(exprs) | Expr::Struct { values: exprs, .. } => {
+            exprs.iter_mut().for_each(|e| visit_expr(e, visit))
+        }
+        Expr::Array(array) => array.elem.iter_mut().for_each(|e| visit_expr(e, visit)),
+        Expr::Dictionary(fields) => fields
+            .iter_mut()
+            .for_each(|f| visit_expr(&mut f.value, visit)),
+        Expr::Map(map) => {
+            for entry in &mut map.entries {
+                visit_expr(&mut entry.key, visit);
+                visit_expr(&mut entry.value, visit);
+            }
+        }
+        // translated to `EXISTS` subqueries by desugaring
+        Expr::CypherPattern(_) | Expr::CypherExists(_) => {}
+    }
+}
@@ -0,0 +1,270 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Mapping of a property graph onto SQL tables, see [`GraphSchema`].
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// How the nodes and relationships of a graph are stored in SQL tables.
+///
+/// The default schema stores every node in a single
+/// `nodes (id, label, properties)` table and every relationship in a single
+/// `edges (id, src_id, dst_id, type, properties)` table, with the properties
+/// in a JSON column. Labels and relationship types can instead be mapped to
+/// tables of their own:
+///
+/// ```
+/// # use sqlparser::desugar::{GraphSchema, NodeTable, EdgeTable, PropertyColumn};
+/// let schema = GraphSchema::new()
+///     .with_label_table(
+///         "Person",
+///         NodeTable::new("people")
+///             .with_property("name", PropertyColumn::Column("full_name".to_string())),
+///     )
+///     .with_relationship_table("KNOWS", EdgeTable::new("friendships", "person_a", "person_b"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphSchema {
+    /// The table of nodes whose label has no table of its own. If `None`,
+    /// every node pattern must have a label in `label_tables`.
+    pub node_table: Option<NodeTable>,
+    /// The table of the nodes with a given label
+    pub label_tables: BTreeMap<String, NodeTable>,
+    /// The table of relationships whose type has no table of its own. If
+    /// `None`, every relationship pattern must have a type in
+    /// `relationship_tables`.
+    pub edge_table: Option<EdgeTable>,
+    /// The table of the relationships of a given type
+    pub relationship_tables: BTreeMap<String, EdgeTable>,
+}
+
+impl Default for GraphSchema {
+    fn default() -> Self {
+        Self {
+            node_table: Some(
+                NodeTable::new("nodes")
+                    .with_label_column("label")
+                    .with_json_column("properties"),
+            ),
+            label_tables: BTreeMap::new(),
+            edge_table: Some(
+                EdgeTable::new("edges", "src_id", "dst_id")
+                    .with_type_column("type")
+                    .with_json_column("properties"),
+            ),
+            relationship_tables: BTreeMap::new(),
+        }
+    }
+}
+
+impl GraphSchema {
+    /// Create the default [`GraphSchema`]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the table of nodes whose label has no table of its own
+    pub fn with_node_table(mut self, node_table: Option<NodeTable>) -> Self {
+        self.node_table = node_table;
+        self
+    }
+
+    /// Store the nodes with `label` in `table`
+    pub fn with_label_table(mut self, label: &str, table: NodeTable) -> Self {
+        self.label_tables.insert(label.to_string(), table);
+        self
+    }
+
+    /// Set the table of relationships whose type has no table of its own
+    pub fn with_edge_table(mut self, edge_table: Option<EdgeTable>) -> Self {
+        self.edge_table = edge_table;
+        self
+    }
+
+    /// Store the relationships of type `rel_type` in `table`
+    pub fn with_relationship_table(mut self, rel_type: &str, table: EdgeTable) -> Self {
+        self.relationship_tables.insert(rel_type.to_string(), table);
+        self
+    }
+
+    /// The table of the nodes with `label`, if any
+    pub fn node_table(&self, label: Option<&str>) -> Option<&NodeTable> {
+        label
+            .and_then(|label| self.label_tables.get(label))
+            .or(self.node_table.as_ref())
+    }
+
+    /// The table of the relationships of type `rel_type`, if any
+    pub fn edge_table(&self, rel_type: Option<&str>) -> Option<&EdgeTable> {
+        rel_type
+            .and_then(|rel_type| self.relationship_tables.get(rel_type))
+            .or(self.edge_table.as_ref())
+    }
+
+    /// All tables storing relationships, each once
+    pub fn edge_tables(&self) -> Vec<&EdgeTable> {
+        let mut tables: Vec<&EdgeTable> = vec![];
+        for table in self
+            .edge_table
+            .iter()
+            .chain(self.relationship_tables.values())
+        {
+            if !tables.iter().any(|t| t.name == table.name) {
+                tables.push(table);
+            }
+        }
+        tables
+    }
+}
+
+/// A table storing one row per node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeTable {
+    /// The name of the table
+    pub name: String,
+    /// The column identifying a node, referenced by relationships.
+    /// Default is `id`.
+    pub id_column: String,
+    /// The column holding the label of a node. Not needed if the table only
+    /// stores the nodes of the label it is mapped to.
+    pub label_column: Option<String>,
+    /// The columns holding the properties of a node
+    pub properties: PropertyColumns,
+}
+
+impl NodeTable {
+    /// A table `name` with an `id` column and no other known columns
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            id_column: "id".to_string(),
+            label_column: None,
+            properties: PropertyColumns::default(),
+        }
+    }
+
+    /// See [`NodeTable::id_column`]
+    pub fn with_id_column(mut self, id_column: &str) -> Self {
+        self.id_column = id_column.to_string();
+        self
+    }
+
+    /// See [`NodeTable::label_column`]
+    pub fn with_label_column(mut self, label_column: &str) -> Self {
+        self.label_column = Some(label_column.to_string());
+        self
+    }
+
+    /// See [`PropertyColumns::json_column`]
+    pub fn with_json_column(mut self, json_column: &str) -> Self {
+        self.properties.json_column = Some(json_column.to_string());
+        self
+    }
+
+    /// Store the property `name` in `column`
+    pub fn with_property(mut self, name: &str, column: PropertyColumn) -> Self {
+        self.properties.columns.insert(name.to_string(), column);
+        self
+    }
+}
+
+/// A table storing one row per relationship, joining the rows of the nodes
+/// it connects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeTable {
+    /// The name of the table
+    pub name: String,
+    /// The column identifying a relationship. Default is `id`.
+    pub id_column: String,
+    /// The column referencing the node a relationship starts from
+    pub source_column: String,
+    /// The column referencing the node a relationship points to
+    pub target_column: String,
+    /// The column holding the type of a relationship. Not needed if the
+    /// table only stores the relationships of the type it is mapped to.
+    pub type_column: Option<String>,
+    /// The columns holding the properties of a relationship
+    pub properties: PropertyColumns,
+}
+
+impl EdgeTable {
+    /// A table `name` with an `id` column and the given columns referencing
+    /// the start and end node of a relationship
+    pub fn new(name: &str, source_column: &str, target_column: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            id_column: "id".to_string(),
+            source_column: source_column.to_string(),
+            target_column: target_column.to_string(),
+            type_column: None,
+            properties: PropertyColumns::default(),
+        }
+    }
+
+    /// See [`EdgeTable::id_column`]
+    pub fn with_id_column(mut self, id_column: &str) -> Self {
+        self.id_column = id_column.to_string();
+        self
+    }
+
+    /// See [`EdgeTable::type_column`]
+    pub fn with_type_column(mut self, type_column: &str) -> Self {
+        self.type_column = Some(type_column.to_string());
+        self
+    }
+
+    /// See [`PropertyColumns::json_column`]
+    pub fn with_json_column(mut self, json_column: &str) -> Self {
+        self.properties.json_column = Some(json_column.to_string());
+        self
+    }
+
+    /// Store the property `name` in `column`
+    pub fn with_property(mut self, name: &str, column: PropertyColumn) -> Self {
+        self.properties.columns.insert(name.to_string(), column);
+        self
+    }
+}
+
+/// Where the properties of the rows of a [`NodeTable`] or [`EdgeTable`]
+/// are stored
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PropertyColumns {
+    /// Properties stored in a column of their own, or at a path of a JSON column
+    pub columns: BTreeMap<String, PropertyColumn>,
+    /// A column holding a JSON object with all other properties, e.g.
+    /// `properties` holding `{"name": "Ann"}`. If `None`, only the
+    /// properties in `columns` can be used.
+    pub json_column: Option<String>,
+}
+
+/// The storage of a single property
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyColumn {
+    /// A typed column, e.g. `full_name`
+    Column(String),
+    /// A JSON path into a column, e.g. `$.address.city` in `details`
+    JsonPath { column: String, path: String },
+}
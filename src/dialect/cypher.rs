@@ -0,0 +1,169 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+use crate::ast::{BinaryOperator, Expr, Statement};
+use crate::dialect::{Dialect, Precedence};
+use crate::keywords::Keyword;
+use crate::parser::{Parser, ParserError};
+use crate::tokenizer::Token;
+
+/// A [`Dialect`] for [openCypher](https://opencypher.org/) graph queries.
+///
+/// Statements starting with `MATCH`, `OPTIONAL MATCH`, `CREATE (`, `MERGE (`,
+/// `UNWIND`, `DELETE`, `DETACH DELETE` or `RETURN` are parsed as Cypher, everything else falls
+/// back to the generic SQL parser.
+#[derive(Debug, Default)]
+pub struct CypherDialect {}
+
+impl Dialect for CypherDialect {
+    fn parse_statement(&self, parser: &mut Parser) -> Option<Result<Statement, ParserError>> {
+        let is_cypher = match parser.peek_token_ref().token {
+            Token::Word(ref w) => match w.keyword {
+                Keyword::MATCH | Keyword::UNWIND | Keyword::RETURN => true,
+                Keyword::OPTIONAL => {
+                    matches!(&parser.peek_nth_token_ref(1).token, Token::Word(w) if w.keyword == Keyword::MATCH)
+                }
+                Keyword::CREATE | Keyword::MERGE => {
+                    parser.peek_nth_token_ref(1).token == Token::LParen
+                }
+                Keyword::DETACH => {
+                    matches!(&parser.peek_nth_token_ref(1).token, Token::Word(w) if w.keyword == Keyword::DELETE)
+                }
+                Keyword::DELETE => {
+                    !matches!(&parser.peek_nth_token_ref(1).token, Token::Word(w) if w.keyword == Keyword::FROM)
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if is_cypher {
+            Some(parser.parse_cypher_statement())
+        } else {
+            None
+        }
+    }
+
+    /// Parses the pattern predicates, such as `(a)-[:FRIEND]->(b)`, and
+    /// `EXISTS { ... }` subqueries of Cypher expressions
+    fn parse_prefix(&self, parser: &mut Parser) -> Option<Result<Expr, ParserError>> {
+        match &parser.peek_token_ref().token {
+            // a parenthesized expression unless it parses as a pattern
+            Token::LParen => parser
+                .maybe_parse(|parser| parser.parse_cypher_pattern_predicate())
+                .transpose(),
+            Token::Word(w) if w.keyword == Keyword::EXISTS => {
+                if parser.peek_nth_token_ref(1).token == Token::LBrace {
+                    Some(parser.parse_cypher_exists_subquery())
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn parse_infix(
+        &self,
+        parser: &mut Parser,
+        expr: &Expr,
+        precedence: u8,
+    ) -> Option<Result<Expr, ParserError>> {
+        let op = if parser.parse_keywords(&[Keyword::STARTS, Keyword::WITH]) {
+            BinaryOperator::CypherStartsWith
+        } else if parser.parse_keywords(&[Keyword::ENDS, Keyword::WITH]) {
+            BinaryOperator::CypherEndsWith
+        } else if parser.parse_keyword(Keyword::CONTAINS) {
+            BinaryOperator::CypherContains
+        } else if is_regex_match(parser) {
+            parser.advance_token();
+            parser.advance_token();
+            BinaryOperator::CypherRegexMatch
+        } else if is_list_membership(parser) {
+            parser.advance_token();
+            BinaryOperator::CypherIn
+        } else {
+            return None;
+        };
+        Some(
+            parser
+                .parse_subexpr(precedence)
+                .map(|right| Expr::BinaryOp {
+                    left: Box::new(expr.clone()),
+                    op,
+                    right: Box::new(right),
+                }),
+        )
+    }
+
+    fn get_next_precedence(&self, parser: &Parser) -> Option<Result<u8, ParserError>> {
+        // `MATCH` starts the next clause, as in `WITH a WHERE a.x > 1 MATCH ...`,
+        // rather than being SQLite's `MATCH` operator
+        if let Token::Word(w) = &parser.peek_token_ref().token {
+            if w.keyword == Keyword::MATCH {
+                return Some(Ok(self.prec_unknown()));
+            }
+        }
+        let is_string_match = match &parser.peek_token_ref().token {
+            Token::Word(w) => match w.keyword {
+                Keyword::STARTS | Keyword::ENDS => matches!(
+                    &parser.peek_nth_token_ref(1).token,
+                    Token::Word(w) if w.keyword == Keyword::WITH
+                ),
+                Keyword::CONTAINS => true,
+                _ => false,
+            },
+            _ => is_regex_match(parser),
+        };
+        if is_string_match {
+            Some(Ok(self.prec_value(Precedence::Like)))
+        } else {
+            None
+        }
+    }
+
+    fn is_identifier_start(&self, ch: char) -> bool {
+        ch.is_alphabetic() || ch == '_'
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        ch.is_alphanumeric() || ch == '_'
+    }
+
+    /// Cypher quotes identifiers with backticks, e.g. `` (`my node`:Person) ``
+    fn is_delimited_identifier_start(&self, ch: char) -> bool {
+        ch == '`'
+    }
+
+    fn supports_cypher(&self) -> bool {
+        true
+    }
+}
+
+/// `a =~ 'regex'`, which is tokenized as `=` followed by `~`
+fn is_regex_match(parser: &Parser) -> bool {
+    parser.peek_token_ref().token == Token::Eq && parser.peek_nth_token_ref(1).token == Token::Tilde
+}
+
+/// `a IN [1, 2, 3]`, as opposed to SQL's `a IN (1, 2, 3)`
+fn is_list_membership(parser: &Parser) -> bool {
+    matches!(&parser.peek_token_ref().token, Token::Word(w) if w.keyword == Keyword::IN)
+        && parser.peek_nth_token_ref(1).token == Token::LBracket
+}
@@ -18,6 +18,7 @@
 mod ansi;
 mod bigquery;
 mod clickhouse;
+mod cypher;
 mod databricks;
 mod duckdb;
 mod generic;
@@ -39,6 +40,7 @@ use log::debug;
 pub use self::ansi::AnsiDialect;
 pub use self::bigquery::BigQueryDialect;
 pub use self::clickhouse::ClickHouseDialect;
+pub use self::cypher::CypherDialect;
 pub use self::databricks::DatabricksDialect;
 pub use self::duckdb::DuckDbDialect;
 pub use self::generic::GenericDialect;
@@ -957,6 +959,19 @@ pub trait Dialect: Debug + Any {
         false
     }
 
+    /// Returns true if this dialect parses [openCypher] graph queries such as
+    /// `MATCH (n:Person) RETURN n` in addition to SQL.
+    ///
+    /// Besides routing `MATCH`, `CREATE (`, `DELETE`, `DETACH DELETE` and
+    /// `RETURN` to the Cypher parser, this changes tokenization: `--` is part
+    /// of a relationship pattern rather than the start of a comment, and `//`
+    /// starts a single line comment.
+    ///
+    /// [openCypher]: https://opencypher.org/
+    fn supports_cypher(&self) -> bool {
+        false
+    }
+
     /// Returns true if the dialect supports the `CREATE TABLE SELECT` statement
     fn supports_create_table_select(&self) -> bool {
         false
@@ -1260,6 +1275,7 @@ pub fn dialect_from_str(dialect_name: impl AsRef<str>) -> Option<Box<dyn Dialect
         "ansi" => Some(Box::new(AnsiDialect {})),
         "duckdb" => Some(Box::new(DuckDbDialect {})),
         "databricks" => Some(Box::new(DatabricksDialect {})),
+        "cypher" => Some(Box::new(CypherDialect {})),
         _ => None,
     }
 }
@@ -1313,6 +1329,8 @@ mod tests {
         assert!(parse_dialect("DuckDb").is::<DuckDbDialect>());
         assert!(parse_dialect("DataBricks").is::<DatabricksDialect>());
         assert!(parse_dialect("databricks").is::<DatabricksDialect>());
+        assert!(parse_dialect("cypher").is::<CypherDialect>());
+        assert!(parse_dialect("Cypher").is::<CypherDialect>());
 
         // error cases
         assert!(dialect_from_str("Unknown").is_none());
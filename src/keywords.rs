@@ -341,6 +341,7 @@ define_keywords!(
     END,
     END_EXEC = "END-EXEC",
     ENDPOINT,
+    ENDS,
     END_FRAME,
     END_PARTITION,
     ENFORCED,
@@ -693,6 +694,7 @@ define_keywords!(
     OPTIMIZED,
     OPTIMIZER_COSTS,
     OPTION,
+    OPTIONAL,
     OPTIONS,
     OR,
     ORC,
@@ -1054,6 +1056,7 @@ define_keywords!(
     UNSET,
     UNSIGNED,
     UNTIL,
+    UNWIND,
     UPDATE,
     UPPER,
     URL,
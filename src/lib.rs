@@ -165,6 +165,7 @@ extern crate alloc;
 extern crate pretty_assertions;
 
 pub mod ast;
+pub mod desugar;
 #[macro_use]
 pub mod dialect;
 mod display_utils;
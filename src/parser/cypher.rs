@@ -0,0 +1,498 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parser for openCypher graph queries, see [`CypherDialect`]
+//!
+//! [`CypherDialect`]: crate::dialect::CypherDialect
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use super::{Parser, ParserError};
+use crate::{
+    ast::{
+        Array, CreateClause, CypherClause, CypherMergeClause, CypherQuery, CypherUnion,
+        DeleteClause, DictionaryField, Expr, Ident, LabelExpression, MatchClause, NodePattern,
+        OrderByExpr, PathPattern, PathSegment, ProjectionItem, PropertyEntry,
+        RelationshipDirection, RelationshipLength, RelationshipPattern, RemoveClause, RemoveItem,
+        ReturnClause, SetClause, SetItem, Statement, UnwindClause, WithClause,
+    },
+    keywords::Keyword,
+    tokenizer::Token,
+};
+
+impl Parser<'_> {
+    /// Parse a Cypher query into a [`Statement::Cypher`].
+    pub fn parse_cypher_statement(&mut self) -> Result<Statement, ParserError> {
+        Ok(Statement::Cypher(self.parse_cypher_query()?))
+    }
+
+    /// Parse a sequence of Cypher clauses, e.g. `MATCH (n) DETACH DELETE n`,
+    /// and the queries combined with it by `UNION [ALL]`.
+    pub fn parse_cypher_query(&mut self) -> Result<CypherQuery, ParserError> {
+        let clauses = self.parse_cypher_clauses()?;
+        let mut unions = vec![];
+        while self.parse_keyword(Keyword::UNION) {
+            let all = self.parse_keyword(Keyword::ALL);
+            unions.push(CypherUnion {
+                all,
+                clauses: self.parse_cypher_clauses()?,
+            });
+        }
+        Ok(CypherQuery { clauses, unions })
+    }
+
+    fn parse_cypher_clauses(&mut self) -> Result<Vec<CypherClause>, ParserError> {
+        let mut clauses = vec![self.parse_cypher_clause()?];
+        loop {
+            match &self.peek_token_ref().token {
+                Token::EOF | Token::SemiColon => break,
+                Token::Word(w) if w.keyword == Keyword::UNION => break,
+                _ => clauses.push(self.parse_cypher_clause()?),
+            }
+        }
+        Ok(clauses)
+    }
+
+    /// Parse a single Cypher clause.
+    pub fn parse_cypher_clause(&mut self) -> Result<CypherClause, ParserError> {
+        let Some(keyword) = self.parse_one_of_keywords(&[
+            Keyword::OPTIONAL,
+            Keyword::MATCH,
+            Keyword::CREATE,
+            Keyword::MERGE,
+            Keyword::UNWIND,
+            Keyword::DETACH,
+            Keyword::DELETE,
+            Keyword::SET,
+            Keyword::REMOVE,
+            Keyword::WITH,
+            Keyword::RETURN,
+        ]) else {
+            return self.expected(
+                "MATCH, CREATE, MERGE, UNWIND, DELETE, SET, REMOVE, WITH or RETURN",
+                self.peek_token(),
+            );
+        };
+
+        match keyword {
+            Keyword::OPTIONAL | Keyword::MATCH => {
+                let optional = keyword == Keyword::OPTIONAL;
+                if optional {
+                    self.expect_keyword_is(Keyword::MATCH)?;
+                }
+                Ok(CypherClause::Match(self.parse_cypher_match(optional)?))
+            }
+            Keyword::CREATE => Ok(CypherClause::Create(CreateClause {
+                pattern: self.parse_comma_separated(Parser::parse_cypher_path_pattern)?,
+            })),
+            Keyword::MERGE => {
+                let pattern = self.parse_cypher_path_pattern()?;
+                let mut on_create = vec![];
+                let mut on_match = vec![];
+                while self.parse_keyword(Keyword::ON) {
+                    let create = self.parse_keyword(Keyword::CREATE);
+                    if !create {
+                        self.expect_keyword_is(Keyword::MATCH)?;
+                    }
+                    self.expect_keyword_is(Keyword::SET)?;
+                    let items = self.parse_comma_separated(Parser::parse_cypher_set_item)?;
+                    if create {
+                        on_create.extend(items);
+                    } else {
+                        on_match.extend(items);
+                    }
+                }
+                Ok(CypherClause::Merge(CypherMergeClause {
+                    pattern,
+                    on_create,
+                    on_match,
+                }))
+            }
+            Keyword::UNWIND => {
+                let expr = match self.peek_token_ref().token {
+                    Token::LBracket => self.parse_cypher_property_value()?,
+                    _ => self.parse_expr()?,
+                };
+                self.expect_keyword_is(Keyword::AS)?;
+                Ok(CypherClause::Unwind(UnwindClause {
+                    expr,
+                    alias: self.parse_identifier()?,
+                }))
+            }
+            Keyword::DETACH | Keyword::DELETE => {
+                let detach = keyword == Keyword::DETACH;
+                if detach {
+                    self.expect_keyword_is(Keyword::DELETE)?;
+                }
+                Ok(CypherClause::Delete(DeleteClause {
+                    detach,
+                    items: self.parse_comma_separated(Parser::parse_expr)?,
+                }))
+            }
+            Keyword::SET => Ok(CypherClause::Set(SetClause {
+                items: self.parse_comma_separated(Parser::parse_cypher_set_item)?,
+            })),
+            Keyword::REMOVE => Ok(CypherClause::Remove(RemoveClause {
+                items: self.parse_comma_separated(Parser::parse_cypher_remove_item)?,
+            })),
+            Keyword::WITH => {
+                let (distinct, items, order_by, skip, limit) =
+                    self.parse_cypher_projection_body()?;
+                let selection = if self.parse_keyword(Keyword::WHERE) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                Ok(CypherClause::With(WithClause {
+                    distinct,
+                    items,
+                    order_by,
+                    skip,
+                    limit,
+                    selection,
+                }))
+            }
+            Keyword::RETURN => {
+                let (distinct, items, order_by, skip, limit) =
+                    self.parse_cypher_projection_body()?;
+                Ok(CypherClause::Return(ReturnClause {
+                    distinct,
+                    items,
+                    order_by,
+                    skip,
+                    limit,
+                }))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Parse the patterns and `WHERE` predicate following `MATCH`
+    fn parse_cypher_match(&mut self, optional: bool) -> Result<MatchClause, ParserError> {
+        let pattern = self.parse_comma_separated(Parser::parse_cypher_path_pattern)?;
+        let selection = if self.parse_keyword(Keyword::WHERE) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        Ok(MatchClause {
+            optional,
+            pattern,
+            selection,
+        })
+    }
+
+    /// Parse a path pattern used as a predicate, such as
+    /// `(a)-[:FRIEND]->(:Admin)`, which has at least one relationship.
+    pub fn parse_cypher_pattern_predicate(&mut self) -> Result<Expr, ParserError> {
+        let pattern = self.parse_cypher_path_pattern()?;
+        if pattern.segments.is_empty() {
+            return self.expected("a relationship pattern", self.peek_token());
+        }
+        Ok(Expr::CypherPattern(Box::new(pattern)))
+    }
+
+    /// Parse an existential subquery such as
+    /// `EXISTS { MATCH (a)-->(b) WHERE b.x = 1 }`, in which `MATCH` is optional.
+    pub fn parse_cypher_exists_subquery(&mut self) -> Result<Expr, ParserError> {
+        self.expect_keyword_is(Keyword::EXISTS)?;
+        self.expect_token(&Token::LBrace)?;
+        let _ = self.parse_keyword(Keyword::MATCH);
+        let subquery = self.parse_cypher_match(false)?;
+        self.expect_token(&Token::RBrace)?;
+        Ok(Expr::CypherExists(Box::new(subquery)))
+    }
+
+    /// Parse a path pattern such as `(a:Person)-[r:KNOWS]->(b)`.
+    pub fn parse_cypher_path_pattern(&mut self) -> Result<PathPattern, ParserError> {
+        let start = self.parse_cypher_node_pattern()?;
+        let mut segments = vec![];
+        while matches!(self.peek_token_ref().token, Token::Minus | Token::Lt) {
+            let relationship = self.parse_cypher_relationship_pattern()?;
+            let node = self.parse_cypher_node_pattern()?;
+            segments.push(PathSegment { relationship, node });
+        }
+        Ok(PathPattern { start, segments })
+    }
+
+    /// Parse a node pattern such as `(n:Person {name: 'Ann'})`.
+    pub fn parse_cypher_node_pattern(&mut self) -> Result<NodePattern, ParserError> {
+        self.expect_token(&Token::LParen)?;
+        let variable = self.parse_cypher_pattern_variable()?;
+        let label = self.parse_cypher_pattern_label()?;
+        let properties = self.parse_cypher_property_map()?;
+        self.expect_token(&Token::RParen)?;
+        Ok(NodePattern {
+            variable,
+            label,
+            properties,
+        })
+    }
+
+    /// Parse a relationship pattern such as `-[r:KNOWS {since: 2020}]->`,
+    /// `<-[r]-`, `-[r]-` or one of the abbreviated forms `-->`, `<--`, `--`.
+    pub fn parse_cypher_relationship_pattern(
+        &mut self,
+    ) -> Result<RelationshipPattern, ParserError> {
+        let incoming = self.consume_token(&Token::Lt);
+        self.expect_token(&Token::Minus)?;
+        let (variable, rel_type, length, properties) = if self.consume_token(&Token::LBracket) {
+            let variable = self.parse_cypher_pattern_variable()?;
+            let rel_type = self.parse_cypher_pattern_label()?;
+            let length = if self.consume_token(&Token::Mul) {
+                Some(self.parse_cypher_relationship_length()?)
+            } else {
+                None
+            };
+            let properties = self.parse_cypher_property_map()?;
+            self.expect_token(&Token::RBracket)?;
+            (variable, rel_type, length, properties)
+        } else {
+            (None, None, None, vec![])
+        };
+        // a relationship cannot point in both directions, as in `<-[r]->`
+        let outgoing = !incoming && self.consume_token(&Token::Arrow);
+        if !outgoing {
+            self.expect_token(&Token::Minus)?;
+        }
+        let direction = match (incoming, outgoing) {
+            (true, _) => RelationshipDirection::Incoming,
+            (false, true) => RelationshipDirection::Outgoing,
+            (false, false) => RelationshipDirection::Undirected,
+        };
+        Ok(RelationshipPattern {
+            direction,
+            variable,
+            rel_type,
+            length,
+            properties,
+        })
+    }
+
+    /// Parse the hop count following the `*` of a variable-length
+    /// relationship: nothing, `n`, or a `min..max` range.
+    pub fn parse_cypher_relationship_length(&mut self) -> Result<RelationshipLength, ParserError> {
+        let min = self.parse_cypher_hop_count()?;
+        if self.consume_token(&Token::Period) {
+            self.expect_token(&Token::Period)?;
+            let max = self.parse_cypher_hop_count()?;
+            return Ok(RelationshipLength::Range { min, max });
+        }
+        Ok(match min {
+            Some(n) => RelationshipLength::Exactly(n),
+            None => RelationshipLength::Any,
+        })
+    }
+
+    fn parse_cypher_hop_count(&mut self) -> Result<Option<u64>, ParserError> {
+        match self.peek_token_ref().token {
+            Token::Number(..) => Ok(Some(self.parse_literal_uint()?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse an optional property map such as `{name: 'Ann', age: 42}`.
+    /// Returns an empty list if there is no map.
+    pub fn parse_cypher_property_map(&mut self) -> Result<Vec<PropertyEntry>, ParserError> {
+        if !self.consume_token(&Token::LBrace) {
+            return Ok(vec![]);
+        }
+        let entries =
+            self.parse_comma_separated0(Parser::parse_cypher_property_entry, Token::RBrace)?;
+        self.expect_token(&Token::RBrace)?;
+        Ok(entries)
+    }
+
+    fn parse_cypher_property_entry(&mut self) -> Result<PropertyEntry, ParserError> {
+        let key = self.parse_identifier()?;
+        self.expect_token(&Token::Colon)?;
+        let value = self.parse_cypher_property_value()?;
+        Ok(PropertyEntry { key, value })
+    }
+
+    /// Parse the value of a property: a list such as `[1, 2]`, a map such as
+    /// `{city: 'Paris'}`, or any other expression, such as `42`, `$id` or
+    /// `row.id` in `UNWIND $rows AS row`.
+    pub fn parse_cypher_property_value(&mut self) -> Result<Expr, ParserError> {
+        if self.consume_token(&Token::LBracket) {
+            let elem =
+                self.parse_comma_separated0(Parser::parse_cypher_property_value, Token::RBracket)?;
+            self.expect_token(&Token::RBracket)?;
+            return Ok(Expr::Array(Array { elem, named: false }));
+        }
+        if self.peek_token_ref().token == Token::LBrace {
+            let fields = self
+                .parse_cypher_property_map()?
+                .into_iter()
+                .map(|entry| DictionaryField {
+                    key: entry.key,
+                    value: Box::new(entry.value),
+                })
+                .collect();
+            return Ok(Expr::Dictionary(fields));
+        }
+        self.parse_expr()
+    }
+
+    /// Parse an item of a `SET` clause: `n.key = <expr>`, `n = <map>`,
+    /// `n += <map>` or `n:Label`.
+    pub fn parse_cypher_set_item(&mut self) -> Result<SetItem, ParserError> {
+        let variable = self.parse_identifier()?;
+        if self.consume_token(&Token::Colon) {
+            let label = self.parse_identifier()?;
+            return Ok(SetItem::Label { variable, label });
+        }
+        if self.consume_token(&Token::Period) {
+            let key = self.parse_identifier()?;
+            self.expect_token(&Token::Eq)?;
+            let value = match self.peek_token_ref().token {
+                Token::LBracket | Token::LBrace => self.parse_cypher_property_value()?,
+                _ => self.parse_expr()?,
+            };
+            return Ok(SetItem::Property {
+                variable,
+                key,
+                value,
+            });
+        }
+        let merge = self.consume_token(&Token::Plus);
+        self.expect_token(&Token::Eq)?;
+        Ok(SetItem::Properties {
+            variable,
+            value: self.parse_cypher_property_value()?,
+            merge,
+        })
+    }
+
+    /// Parse an item of a `REMOVE` clause: `n.key` or `n:Label`.
+    pub fn parse_cypher_remove_item(&mut self) -> Result<RemoveItem, ParserError> {
+        let variable = self.parse_identifier()?;
+        if self.consume_token(&Token::Colon) {
+            let label = self.parse_identifier()?;
+            return Ok(RemoveItem::Label { variable, label });
+        }
+        self.expect_token(&Token::Period)?;
+        let key = self.parse_identifier()?;
+        Ok(RemoveItem::Property { variable, key })
+    }
+
+    /// Parse what follows `RETURN` or `WITH`:
+    /// `[DISTINCT] <item>, ... [ORDER BY ...] [SKIP <n>] [LIMIT <n>]`
+    #[allow(clippy::type_complexity)]
+    fn parse_cypher_projection_body(
+        &mut self,
+    ) -> Result<
+        (
+            bool,
+            Vec<ProjectionItem>,
+            Vec<OrderByExpr>,
+            Option<Expr>,
+            Option<Expr>,
+        ),
+        ParserError,
+    > {
+        let distinct = self.parse_keyword(Keyword::DISTINCT);
+        let items = self.parse_comma_separated(Parser::parse_cypher_projection_item)?;
+        let order_by = if self.parse_keywords(&[Keyword::ORDER, Keyword::BY]) {
+            self.parse_comma_separated(Parser::parse_order_by_expr)?
+        } else {
+            vec![]
+        };
+        let skip = if self.parse_keyword(Keyword::SKIP) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        let limit = if self.parse_keyword(Keyword::LIMIT) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+        Ok((distinct, items, order_by, skip, limit))
+    }
+
+    fn parse_cypher_projection_item(&mut self) -> Result<ProjectionItem, ParserError> {
+        let expr = self.parse_expr()?;
+        let alias = if self.parse_keyword(Keyword::AS) {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        Ok(ProjectionItem { expr, alias })
+    }
+
+    /// Parse the optional variable at the start of a node or relationship pattern
+    fn parse_cypher_pattern_variable(&mut self) -> Result<Option<Ident>, ParserError> {
+        match self.peek_token_ref().token {
+            Token::Word(_) => Ok(Some(self.parse_identifier()?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse the optional labels of a node, such as `:Person:Employee` or
+    /// `:Cat|Dog`, or types of a relationship, such as `:KNOWS|LIKES`
+    fn parse_cypher_pattern_label(&mut self) -> Result<Option<LabelExpression>, ParserError> {
+        if self.consume_token(&Token::Colon) {
+            Ok(Some(self.parse_cypher_label_expression()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parse a label expression following its `:`, e.g. `A:B`, `A|B`, `!A`
+    /// or `(A|B):C`. The legacy `:KNOWS|:LIKES` is also accepted.
+    pub fn parse_cypher_label_expression(&mut self) -> Result<LabelExpression, ParserError> {
+        let mut all = vec![];
+        loop {
+            let mut any = vec![];
+            loop {
+                match self.parse_cypher_label_term()? {
+                    LabelExpression::Any(items) => any.extend(items),
+                    term => any.push(term),
+                }
+                if !self.consume_token(&Token::Pipe) {
+                    break;
+                }
+                let _ = self.consume_token(&Token::Colon);
+            }
+            match any.len() {
+                1 => match any.remove(0) {
+                    LabelExpression::All(items) => all.extend(items),
+                    term => all.push(term),
+                },
+                _ => all.push(LabelExpression::Any(any)),
+            }
+            if !self.consume_token(&Token::Colon) {
+                break;
+            }
+        }
+        Ok(match all.len() {
+            1 => all.remove(0),
+            _ => LabelExpression::All(all),
+        })
+    }
+
+    fn parse_cypher_label_term(&mut self) -> Result<LabelExpression, ParserError> {
+        if self.consume_token(&Token::ExclamationMark) {
+            return Ok(LabelExpression::Not(Box::new(
+                self.parse_cypher_label_term()?,
+            )));
+        }
+        if self.consume_token(&Token::LParen) {
+            let expression = self.parse_cypher_label_expression()?;
+            self.expect_token(&Token::RParen)?;
+            return Ok(expression);
+        }
+        Ok(LabelExpression::Label(self.parse_identifier()?))
+    }
+}
@@ -46,6 +46,7 @@ use crate::tokenizer::*;
 use sqlparser::parser::ParserState::ColumnDefinition;
 
 mod alter;
+mod cypher;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParserError {
@@ -576,51 +577,17 @@ impl<'a> Parser<'a> {
                         self.parse_attach_database()
                     }
                 }
-                Keyword::DETACH => {
-                    // Check if next token is DELETE (Cypher) or DATABASE (DuckDB SQL)
-                    if self.peek_token().token == Token::Word(Word {
-                        value: "DELETE".to_string(),
-                        keyword: Keyword::DELETE,
-                        quote_style: None,
-                    }) {
-                        // Cypher DETACH DELETE
-                        self.prev_token();
-                        self.parse_cypher_delete()
-                    } else if dialect_of!(self is DuckDbDialect | GenericDialect) {
-                        self.parse_detach_duckdb_database()
-                    } else {
-                        self.expected("DELETE or DATABASE after DETACH", self.peek_token())
-                    }
+                Keyword::DETACH if dialect_of!(self is DuckDbDialect | GenericDialect) => {
+                    self.parse_detach_duckdb_database()
                 }
                 Keyword::MSCK => self.parse_msck(),
-                Keyword::CREATE => {
-                    self.prev_token();
-                    self.parse_create()
-                }
+                Keyword::CREATE => self.parse_create(),
                 Keyword::CACHE => self.parse_cache_table(),
                 Keyword::DROP => self.parse_drop(),
                 Keyword::DISCARD => self.parse_discard(),
                 Keyword::DECLARE => self.parse_declare(),
                 Keyword::FETCH => self.parse_fetch_statement(),
-                Keyword::DELETE => {
-                    // Check if it's preceded by MATCH (Cypher) or standalone DELETE (SQL)
-                    // For now, peek ahead to detect pattern
-                    let checkpoint = self.index;
-                    
-                    // Try Cypher DELETE
-                    let var_result = self.parse_identifier();
-                    
-                    if var_result.is_ok() && matches!(self.peek_token().token, Token::EOF | Token::SemiColon) {
-                        // Looks like Cypher: DELETE n
-                        self.index = checkpoint;
-                        self.prev_token();
-                        self.parse_cypher_delete()
-                    } else {
-                        // SQL DELETE
-                        self.index = checkpoint;
-                        self.parse_delete(next_token)
-                    }
-                }
+                Keyword::DELETE => self.parse_delete(next_token),
                 Keyword::INSERT => self.parse_insert(next_token),
                 Keyword::REPLACE => self.parse_replace(next_token),
                 Keyword::UNCACHE => self.parse_uncache_table(),
@@ -681,14 +648,7 @@ impl<'a> Parser<'a> {
                 // `COMMENT` is snowflake specific https://docs.snowflake.com/en/sql-reference/sql/comment
                 Keyword::COMMENT if self.dialect.supports_comment_on() => self.parse_comment(),
                 Keyword::PRINT => self.parse_print(),
-                Keyword::RETURN => {
-                    self.prev_token();
-                    self.parse_return()
-                }
-                Keyword::MATCH => {
-                    self.prev_token();
-                    self.parse_cypher_match()
-                }
+                Keyword::RETURN => self.parse_return(),
                 Keyword::EXPORT => {
                     self.prev_token();
                     self.parse_export_data()
@@ -2855,7 +2815,11 @@ impl<'a> Parser<'a> {
     pub fn parse_not(&mut self) -> Result<Expr, ParserError> {
         match self.peek_token().token {
             Token::Word(w) => match w.keyword {
-                Keyword::EXISTS => {
+                // Cypher's `NOT EXISTS { ... }` is the negation of a prefix expression
+                Keyword::EXISTS
+                    if !self.dialect.supports_cypher()
+                        || self.peek_nth_token_ref(1).token != Token::LBrace =>
+                {
                     let negated = true;
                     let _ = self.parse_keyword(Keyword::EXISTS);
                     self.parse_exists_expr(negated)
@@ -4762,139 +4726,6 @@ impl<'a> Parser<'a> {
 
     /// Parse a SQL CREATE statement
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
-        // Check if it's Cypher CREATE (starts with parenthesis for node pattern)
-        self.expect_keyword(Keyword::CREATE)?;
-
-        if self.peek_token().token == Token::LParen {
-            self.next_token(); // consume (
-            
-            let node_name = self.parse_identifier()?.to_string();
-            
-            // --- FIXED CYPER PATTERN PARSING START ---
-
-            // Check what comes next: ')' (Simple node/Relationship) or ':'/ '{' (Node with details)
-            if self.consume_token(&Token::RParen) { 
-                // Case 1: We consumed ')' -> Pattern is (a)
-                
-                // Check if followed by relationship: (a)-...
-                // Check if followed by relationship: (a)-...
-                if self.peek_token().token == Token::Minus {
-                    self.next_token(); // consume -
-                    
-                    // --- Relationship parsing starts here ---
-                    // --- DEBUG POINT ADDED HERE ---
-                    let next_token = self.peek_token();
-
-                    // We must see the LBracket token here.
-                    if next_token.token != Token::LBracket {
-                        // If it's not LBracket, we construct a detailed error message
-                        // that includes the actual token found.
-                        let found_token_str = format!("{:?}", next_token.token);
-                        
-                        // Using self.expected to return the error immediately, 
-                        // reporting exactly what was found.
-                        return self.expected(
-                            &format!("relationship type bracket ["), // Expected part
-                            next_token
-                        );
-                    }
-
-                    // If the check passes, consume the token and proceed.
-                    self.next_token(); // Consume [
-                    
-                    // The rest of the parsing, assuming tokens are separated:
-                    self.expect_token(&Token::Colon)?;
-                    let rel_type = self.parse_identifier()?.to_string();
-                    
-                    // Properties (optional)
-                    let properties = if self.consume_token(&Token::LBrace) {
-                        // Property placeholder fix (consumes tokens inside braces):
-                        while !matches!(self.peek_token().token, Token::RBrace) {
-                            self.next_token(); 
-                        }
-                        self.expect_token(&Token::RBrace)?;
-                        None  // placeholder return
-                    } else {
-                        None
-                    };
-                    
-                    self.expect_token(&Token::RBracket)?;
-                    self.expect_token(&Token::Arrow)?;
-                    self.expect_token(&Token::LParen)?;
-                    
-                    let to_node = self.parse_identifier()?.to_string();
-                    self.expect_token(&Token::RParen)?;
-                    
-                    return Ok(Statement::CypherCreateRelationship {
-                        from_node: node_name,
-                        to_node,
-                        rel_type,
-                        properties,
-                        from_label: None,
-                        from_properties: None,
-                        to_label: None,
-                        to_properties: None,
-                    });
-                }
-                
-                // If we get here, it was just (a), so return the simple node creation.
-                return Ok(Statement::CypherCreate {
-                    node_name,
-                    label: None,
-                    properties: None,
-                });
-            }       
-            
-            // Case 2: Didn't consume ')' -> Must be Node creation with label/properties (a:Label)
-            
-            // If the next token is NOT ':' and NOT '{', it's an error.
-            if self.peek_token().token != Token::Colon && self.peek_token().token != Token::LBrace {
-                 return self.expected("closing parenthesis, label, or properties", self.peek_token());
-            }
-
-            // Node creation: (n:Label {props})
-            self.expect_token(&Token::Colon)?;
-            let label = self.parse_identifier()?.to_string();
-            
-            let properties = if self.consume_token(&Token::LBrace) {
-                let mut props = Vec::new(); // Type inference is OK here because of later use
-                
-                while !matches!(self.peek_token().token, Token::RBrace) {
-                    let key = self.parse_identifier()?.to_string();
-                    self.expect_token(&Token::Colon)?;
-                    
-                    let value = match &self.peek_token().token {
-                        Token::SingleQuotedString(s) => {
-                            let v = s.clone();
-                            self.next_token();
-                            v
-                        }
-                        Token::Number(n, _) => {
-                            let v = n.clone();
-                            self.next_token();
-                            v
-                        }
-                        _ => return self.expected("property value", self.peek_token()),
-                    };
-                    
-                    props.push((key, value));
-                    self.consume_token(&Token::Comma);
-                }
-                
-                self.expect_token(&Token::RBrace)?;
-                Some(props)
-            } else {
-                None
-            };
-            
-            self.expect_token(&Token::RParen)?;
-            
-            return Ok(Statement::CypherCreate {
-                node_name,
-                label: Some(label),
-                properties,
-            });
-        }
         let or_replace = self.parse_keywords(&[Keyword::OR, Keyword::REPLACE]);
         let or_alter = self.parse_keywords(&[Keyword::OR, Keyword::ALTER]);
         let local = self.parse_one_of_keywords(&[Keyword::LOCAL]).is_some();
@@ -15731,10 +15562,10 @@ impl<'a> Parser<'a> {
 
             let is_mysql = dialect_of!(self is MySqlDialect);
 
-            let (columns, partitioned, after_columns, source, assignments) = if self
+            let (columns, partitioned, after_columns, output, source, assignments) = if self
                 .parse_keywords(&[Keyword::DEFAULT, Keyword::VALUES])
             {
-                (vec![], None, vec![], None, vec![])
+                (vec![], None, vec![], None, None, vec![])
             } else {
                 let (columns, partitioned, after_columns) = if !self.peek_subquery_start() {
                     let columns = self.parse_parenthesized_column_list(Optional, is_mysql)?;
@@ -15751,8 +15582,18 @@ impl<'a> Parser<'a> {
                     Default::default()
                 };
 
+                let output = if dialect_of!(self is MsSqlDialect | GenericDialect)
+                    && self.parse_keyword(Keyword::OUTPUT)
+                {
+                    Some(self.parse_output(Keyword::OUTPUT)?)
+                } else {
+                    None
+                };
+
                 let (source, assignments) = if self.peek_keyword(Keyword::FORMAT)
                     || self.peek_keyword(Keyword::SETTINGS)
+                    || (output.is_some()
+                        && self.parse_keywords(&[Keyword::DEFAULT, Keyword::VALUES]))
                 {
                     (None, vec![])
                 } else if self.dialect.supports_insert_set() && self.parse_keyword(Keyword::SET) {
@@ -15761,7 +15602,14 @@ impl<'a> Parser<'a> {
                     (Some(self.parse_query()?), vec![])
                 };
 
-                (columns, partitioned, after_columns, source, assignments)
+                (
+                    columns,
+                    partitioned,
+                    after_columns,
+                    output,
+                    source,
+                    assignments,
+                )
             };
 
             let (format_clause, settings) = if self.dialect.supports_insert_format() {
@@ -15857,6 +15705,7 @@ impl<'a> Parser<'a> {
                 partitioned,
                 columns,
                 after_columns,
+                output,
                 source,
                 assignments,
                 has_table_keyword: table,
@@ -17920,22 +17769,6 @@ impl<'a> Parser<'a> {
 
     /// Parse [Statement::Return]
     fn parse_return(&mut self) -> Result<Statement, ParserError> {
-        let checkpoint = self.index;
-        self.expect_keyword(Keyword::RETURN)?;
-        
-        let cur_tok = &self.peek_token().token;
-        let is_cypher = matches!(cur_tok, 
-            Token::SingleQuotedString(_) | 
-            Token::Number(_, _) |
-            Token::Word(_)
-        );
-        
-        if is_cypher {
-            // Reset and parse as Cypher
-            self.index = checkpoint;
-            return self.parse_cypher_return();
-        }
-
         match self.maybe_parse(|p| p.parse_expr())? {
             Some(expr) => Ok(Statement::Return(ReturnStatement {
                 value: Some(ReturnStatementValue::Expr(expr)),
@@ -17944,353 +17777,6 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_cypher_return(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword(Keyword::RETURN)?;
-        
-        let mut items = Vec::new();
-        
-        loop {
-            let mut expr_tokens = Vec::new();
-            
-            while self.peek_token().token != Token::EOF 
-                && self.peek_token().token != Token::SemiColon
-                && self.peek_token().token != Token::Comma
-            {
-                if let Token::Word(w) = &self.peek_token().token {
-                    if w.keyword == Keyword::AS {
-                        break;
-                    }
-                }
-                expr_tokens.push(self.next_token().to_string());
-            }
-            
-            let expression = expr_tokens.join(" ");
-            
-            let alias = if self.parse_keyword(Keyword::AS) {
-                Some(self.parse_identifier()?.to_string())
-            } else {
-                None
-            };
-            
-            items.push((expression, alias));
-            
-            if !self.consume_token(&Token::Comma) {
-                break;
-            }
-        }
-        
-        Ok(Statement::CypherReturn { items })
-    }
-
-    fn parse_cypher_match(&mut self) -> Result<Statement, ParserError> {
-        self.expect_keyword(Keyword::MATCH)?;
-        self.expect_token(&Token::LParen)?;
-        
-        // Check if it's an anonymous node () or a named node
-        let first_var = if matches!(self.peek_token().token, Token::RParen) {
-            "_anonymous".to_string()
-        } else {
-            self.parse_identifier()?.to_string()
-        };
-        
-        if self.consume_token(&Token::RParen) {
-            // Check for edge pattern: )-[
-            if self.consume_token(&Token::Minus) && self.consume_token(&Token::LBracket) {
-                // Edge pattern: (a)-[r]->(b)
-                let edge_name = self.parse_identifier()?.to_string();
-                
-                // Check for relationship type
-                let rel_type = if self.consume_token(&Token::Colon) {
-                    Some(self.parse_identifier()?.to_string())
-                } else {
-                    None
-                };
-                
-                self.expect_token(&Token::RBracket)?;
-                self.expect_token(&Token::Arrow)?;
-                self.expect_token(&Token::LParen)?;
-                
-                // Check if it's an anonymous node () or a named node
-                let to_node = if matches!(self.peek_token().token, Token::RParen) {
-                    "_anonymous".to_string()
-                } else {
-                    self.parse_identifier()?.to_string()
-                };
-                self.expect_token(&Token::RParen)?;
-                
-                // Check for DELETE after edge pattern
-                if self.peek_keyword(Keyword::DELETE) {
-                    self.expect_keyword(Keyword::DELETE)?;
-                    let _delete_var = self.parse_identifier()?.to_string();
-                    return Ok(Statement::CypherDelete {
-                        node_or_edge_name: edge_name,
-                        is_edge: true,
-                        detach: false,
-                        label: None,
-                        rel_type,
-                        properties: None,
-                    });
-                }
-                
-                // Parse RETURN if present
-                let return_for_match = if self.parse_keyword(Keyword::RETURN) {
-                    let mut items = Vec::new();
-                    loop {
-                        items.push(self.parse_identifier()?.to_string());
-                        if !self.consume_token(&Token::Comma) {
-                            break;
-                        }
-                    }
-                    Some(items)
-                } else {
-                    None
-                };
-                
-                return Ok(Statement::CypherMatchEdge {
-                    from_node: first_var,
-                    edge_name,
-                    to_node,
-                    rel_type,
-                    return_for_match,
-                });
-            } else {
-                // Check for DETACH DELETE after simple node pattern
-                if self.peek_keyword(Keyword::DETACH) || self.peek_keyword(Keyword::DELETE) {
-                    let detach = self.parse_keyword(Keyword::DETACH);
-                    self.expect_keyword(Keyword::DELETE)?;
-                    let _delete_var = self.parse_identifier()?.to_string();
-                    return Ok(Statement::CypherDelete {
-                        node_or_edge_name: first_var,
-                        is_edge: false,
-                        detach,
-                        label: None,
-                        rel_type: None,
-                        properties: None,
-                    });
-                }
-                
-                // Simple node pattern without label: (n)
-                let return_for_match = if self.parse_keyword(Keyword::RETURN) {
-                    let mut items = Vec::new();
-                    loop {
-                        items.push(self.parse_identifier()?.to_string());
-                        if !self.consume_token(&Token::Comma) {
-                            break;
-                        }
-                    }
-                    Some(items)
-                } else {
-                    None
-                };
-                
-                return Ok(Statement::CypherMatchNode {
-                    node_name: first_var,
-                    label: None,
-                    properties: None,
-                    return_for_match,
-                });
-            }
-        }
-        
-        // Node pattern with label: (n:Label) or properties
-        let label = if self.consume_token(&Token::Colon) {
-            Some(self.parse_identifier()?.to_string())
-        } else {
-            None
-        };
-        
-        // Parse properties if present
-        let properties = if self.consume_token(&Token::LBrace) {
-            let mut props = Vec::new();
-            
-            while !matches!(self.peek_token().token, Token::RBrace) {
-                let key = self.parse_identifier()?.to_string();
-                self.expect_token(&Token::Colon)?;
-                
-                let value = match &self.peek_token().token {
-                    Token::SingleQuotedString(s) => {
-                        let v = s.clone();
-                        self.next_token();
-                        v
-                    }
-                    Token::Number(n, _) => {
-                        let v = n.clone();
-                        self.next_token();
-                        v
-                    }
-                    _ => return self.expected("property value", self.peek_token()),
-                };
-                
-                props.push((key, value));
-                self.consume_token(&Token::Comma);
-            }
-            
-            self.expect_token(&Token::RBrace)?;
-            Some(props)
-        } else {
-            None
-        };
-        
-        self.expect_token(&Token::RParen)?;
-        
-        // Check for DETACH DELETE after full node pattern
-        if self.peek_keyword(Keyword::DETACH) || self.peek_keyword(Keyword::DELETE) {
-            let detach = self.parse_keyword(Keyword::DETACH);
-            self.expect_keyword(Keyword::DELETE)?;
-            let _delete_var = self.parse_identifier()?.to_string();
-            return Ok(Statement::CypherDelete {
-                node_or_edge_name: first_var,
-                is_edge: false,
-                detach,
-                label,
-                rel_type: None,
-                properties,
-            });
-        }
-        
-        // Check if there's another node pattern after a comma for relationship creation
-        if self.consume_token(&Token::Comma) {
-            // Parse second node pattern
-            self.expect_token(&Token::LParen)?;
-            let second_var = self.parse_identifier()?.to_string();
-            
-            let second_label = if self.consume_token(&Token::Colon) {
-                Some(self.parse_identifier()?.to_string())
-            } else {
-                None
-            };
-            
-            let second_properties = if self.consume_token(&Token::LBrace) {
-                let mut props = Vec::new();
-                while !matches!(self.peek_token().token, Token::RBrace) {
-                    let key = self.parse_identifier()?.to_string();
-                    self.expect_token(&Token::Colon)?;
-                    let value = match &self.peek_token().token {
-                        Token::SingleQuotedString(s) => {
-                            let v = s.clone();
-                            self.next_token();
-                            v
-                        }
-                        Token::Number(n, _) => {
-                            let v = n.clone();
-                            self.next_token();
-                            v
-                        }
-                        _ => return self.expected("property value", self.peek_token()),
-                    };
-                    props.push((key, value));
-                    self.consume_token(&Token::Comma);
-                }
-                self.expect_token(&Token::RBrace)?;
-                Some(props)
-            } else {
-                None
-            };
-            
-            self.expect_token(&Token::RParen)?;
-            
-            // Now check for CREATE relationship: CREATE (first)-[:TYPE]->(second);
-            if self.parse_keyword(Keyword::CREATE) {
-                self.expect_token(&Token::LParen)?;
-                let from_node = self.parse_identifier()?.to_string();
-                self.expect_token(&Token::RParen)?;
-                self.expect_token(&Token::Minus)?;
-                self.expect_token(&Token::LBracket)?;
-                
-                let rel_type = if self.consume_token(&Token::Colon) {
-                    self.parse_identifier()?.to_string()
-                } else {
-                    return self.expected("relationship type", self.peek_token());
-                };
-                
-                let rel_properties = if self.consume_token(&Token::LBrace) {
-                    let mut props = Vec::new();
-                    while !matches!(self.peek_token().token, Token::RBrace) {
-                        let key = self.parse_identifier()?.to_string();
-                        self.expect_token(&Token::Colon)?;
-                        let value = match &self.peek_token().token {
-                            Token::SingleQuotedString(s) => {
-                                let v = s.clone();
-                                self.next_token();
-                                v
-                            }
-                            Token::Number(n, _) => {
-                                let v = n.clone();
-                                self.next_token();
-                                v
-                            }
-                            _ => return self.expected("property value", self.peek_token()),
-                        };
-                        props.push((key, value));
-                        self.consume_token(&Token::Comma);
-                    }
-                    self.expect_token(&Token::RBrace)?;
-                    Some(props)
-                } else {
-                    None
-                };
-                
-                self.expect_token(&Token::RBracket)?;
-                self.expect_token(&Token::Arrow)?;
-                self.expect_token(&Token::LParen)?;
-                let to_node = self.parse_identifier()?.to_string();
-                self.expect_token(&Token::RParen)?;
-                
-                return Ok(Statement::CypherCreateRelationship {
-                    from_node,
-                    from_label: label,
-                    from_properties: properties,
-                    to_node,
-                    to_label: second_label,
-                    to_properties: second_properties,
-                    rel_type,
-                    properties: rel_properties,
-                });
-            }
-        }
-        
-        // Parse RETURN if present
-        let return_for_match = if self.parse_keyword(Keyword::RETURN) {
-            let mut items = Vec::new();
-            loop {
-                let item_str = self.parse_identifier()?.to_string();
-                items.push(item_str);
-                if !self.consume_token(&Token::Comma) {
-                    break;
-                }
-            }
-            Some(items)
-        } else {
-            None
-        };
-        
-        Ok(Statement::CypherMatchNode {
-            node_name: first_var,
-            label,
-            properties,
-            return_for_match,
-        })
-    }
-
-    fn parse_cypher_delete(&mut self) -> Result<Statement, ParserError> {
-        let detach = self.parse_keyword(Keyword::DETACH);
-        self.expect_keyword(Keyword::DELETE)?;
-        
-        let node_or_edge_name = self.parse_identifier()?.to_string();
-        
-        // For now, assume it's a node (is_edge = false)
-        // In a real implementation, you'd track this from MATCH
-        Ok(Statement::CypherDelete {
-            node_or_edge_name,
-            is_edge: false,  // TODO: detect from context
-            detach,
-            label: None,
-            rel_type: None,
-            properties: None,
-        })
-    }
-
-
     /// /// Parse a `EXPORT DATA` statement.
     ///
     /// See [Statement::ExportData]
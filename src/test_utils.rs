@@ -223,6 +223,16 @@ impl TestedDialects {
         }
     }
 
+    /// Ensures that `cypher` parses as a single [CypherQuery], and that
+    /// re-serializing the parse result produces the same `cypher`
+    /// string (is not modified after a serialization round-trip).
+    pub fn verified_cypher_query(&self, cypher: &str) -> CypherQuery {
+        match self.verified_stmt(cypher) {
+            Statement::Cypher(query) => query,
+            _ => panic!("Expected Cypher query"),
+        }
+    }
+
     /// Ensures that `sql` parses as a single [Select], and that
     /// re-serializing the parse result produces the same `sql`
     /// string (is not modified after a serialization round-trip).
@@ -945,8 +945,6 @@ impl<'a> Tokenizer<'a> {
                 ' ' => self.consume_and_return(chars, Token::Whitespace(Whitespace::Space)),
                 '\t' => self.consume_and_return(chars, Token::Whitespace(Whitespace::Tab)),
                 '\n' => self.consume_and_return(chars, Token::Whitespace(Whitespace::Newline)),
-                '[' => self.consume_and_return(chars, Token::LBracket),
-                ']' => self.consume_and_return(chars, Token::RBracket),
                 '\r' => {
                     // Emit a single Whitespace::Newline token for \r and \r\n
                     chars.next();
@@ -1192,6 +1190,15 @@ impl<'a> Tokenizer<'a> {
                     Ok(Some(Token::make_word(&word.concat(), Some(quote_start))))
                 }
                 // numbers and period
+                // Cypher uses `..` in variable-length relationships such as
+                // `-[*1..3]->`, so a period next to another period is never
+                // part of a number
+                '.' if self.dialect.supports_cypher()
+                    && (prev_token == Some(&Token::Period)
+                        || chars.peekable.clone().nth(1) == Some('.')) =>
+                {
+                    self.consume_and_return(chars, Token::Period)
+                }
                 '0'..='9' | '.' => {
                     // special case where if ._ is encountered after a word then that word
                     // is a table and the _ is the start of the col name.
@@ -1230,8 +1237,11 @@ impl<'a> Tokenizer<'a> {
                         return Ok(Some(Token::HexStringLiteral(s2)));
                     }
 
-                    // match one period
-                    if let Some('.') = chars.peek() {
+                    // match one period, unless it starts a Cypher `..` range
+                    if chars.peek() == Some(&'.')
+                        && !(self.dialect.supports_cypher()
+                            && chars.peekable.clone().nth(1) == Some('.'))
+                    {
                         s.push('.');
                         chars.next();
                     }
@@ -1325,31 +1335,24 @@ impl<'a> Tokenizer<'a> {
                     chars.next(); // consume the '-'
 
                     match chars.peek() {
-                        Some('-') => {
-                            // Peek next char
-                            let next = chars.peekable.clone().nth(1);
-
-                            // Only treat as comment if TWO hyphens '--'
-                            if next == Some('-') {
-                                // Optional SQLite rule
-                                let mut is_comment = true;
-                                if self.dialect.requires_single_line_comment_whitespace() {
-                                    is_comment = chars.peekable.clone().nth(2) == Some(' ');
-                                }
+                        // Cypher uses `--` in relationship patterns such as `(a)--(b)`
+                        Some('-') if !self.dialect.supports_cypher() => {
+                            let mut is_comment = true;
+                            if self.dialect.requires_single_line_comment_whitespace() {
+                                is_comment = Some(' ') == chars.peekable.clone().nth(1);
+                            }
 
-                                if is_comment {
-                                    chars.next(); // consume second '-'
-                                    let comment = self.tokenize_single_line_comment(chars);
-                                    return Ok(Some(Token::Whitespace(
-                                        Whitespace::SingleLineComment {
-                                            prefix: "--".to_owned(),
-                                            comment,
-                                        },
-                                    )));
-                                }
+                            if is_comment {
+                                chars.next(); // consume second '-'
+                                let comment = self.tokenize_single_line_comment(chars);
+                                return Ok(Some(Token::Whitespace(
+                                    Whitespace::SingleLineComment {
+                                        prefix: "--".to_owned(),
+                                        comment,
+                                    },
+                                )));
                             }
 
-                            // If not comment, just minus
                             self.start_binop(chars, "-", Token::Minus)
                         }
                         Some('>') => {
@@ -1370,8 +1373,11 @@ impl<'a> Tokenizer<'a> {
                             chars.next(); // consume the '*', starting a multi-line comment
                             self.tokenize_multiline_comment(chars)
                         }
-                        Some('/') if dialect_of!(self is SnowflakeDialect) => {
-                            chars.next(); // consume the second '/', starting a snowflake single-line comment
+                        Some('/')
+                            if dialect_of!(self is SnowflakeDialect)
+                                || self.dialect.supports_cypher() =>
+                        {
+                            chars.next(); // consume the second '/', starting a snowflake or cypher single-line comment
                             let comment = self.tokenize_single_line_comment(chars);
                             Ok(Some(Token::Whitespace(Whitespace::SingleLineComment {
                                 prefix: "//".to_owned(),
@@ -1536,6 +1542,8 @@ impl<'a> Tokenizer<'a> {
                 }
                 ';' => self.consume_and_return(chars, Token::SemiColon),
                 '\\' => self.consume_and_return(chars, Token::Backslash),
+                '[' => self.consume_and_return(chars, Token::LBracket),
+                ']' => self.consume_and_return(chars, Token::RBracket),
                 '&' => {
                     chars.next(); // consume the '&'
                     match chars.peek() {
@@ -0,0 +1,2741 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+#![warn(clippy::all)]
+//! Test openCypher syntax parsed by the [`CypherDialect`].
+
+#[macro_use]
+mod test_utils;
+
+use test_utils::*;
+
+use sqlparser::ast::*;
+use sqlparser::desugar::{
+    DesugarError, DesugarOptions, DesugaredStatement, EdgeTable, GraphSchema, NodeTable,
+    PropertyColumn,
+};
+use sqlparser::dialect::{
+    BigQueryDialect, CypherDialect, Dialect, DuckDbDialect, GenericDialect, MsSqlDialect,
+    MySqlDialect, PostgreSqlDialect, SQLiteDialect, SnowflakeDialect,
+};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+fn desugared(cypher_query: &str) -> String {
+    cypher()
+        .verified_stmt(cypher_query)
+        .desugar_cypher_to_sql(&GenericDialect {})
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn cypher() -> TestedDialects {
+    TestedDialects::new(vec![Box::new(CypherDialect {})])
+}
+
+#[test]
+fn parse_match_with_cypher_dialect() {
+    let stmt = cypher().verified_stmt("MATCH (n:Bug) RETURN n");
+    assert!(matches!(stmt, Statement::Cypher(_)));
+
+    cypher().verified_stmt("MATCH (b:Bug) DETACH DELETE b");
+    cypher().verified_stmt("CREATE (b:Bug {name: 'Ant'})");
+}
+
+#[test]
+fn cypher_display_round_trips() {
+    // Display prints Cypher, never SQL, so each query parses back to the same AST
+    let queries = [
+        "RETURN 1",
+        "MATCH (n) RETURN n",
+        "OPTIONAL MATCH (n:Bug)-[r:EATS]->(m) WHERE n.legs > 4 RETURN n, r, m",
+        "MATCH (a)<-[:EATS]-(b) RETURN a.name AS name, count(*) AS c ORDER BY c DESC, name SKIP 1 LIMIT 10",
+        "MATCH (a)-->(b)<--(c)--(d) RETURN a",
+        "MATCH (a)-[*]->(b) RETURN b",
+        "MATCH (a)-[*2]->(b) RETURN b",
+        "MATCH (a)-[*..3]->(b) RETURN b",
+        "MATCH (a)-[*1..]->(b) RETURN b",
+        "MATCH (a)-[r:KNOWS*1..3]-(b) RETURN DISTINCT b",
+        "MATCH (n) WITH n, count(*) AS c WHERE c > 1 RETURN n",
+        "MATCH (n) WITH DISTINCT n.name AS name ORDER BY name SKIP 1 LIMIT 2 RETURN name",
+        "MATCH (n) RETURN collect(DISTINCT n.name), toUpper(n.name), size(n.tags)",
+        "MATCH (n) WHERE n.name STARTS WITH 'A' AND n.name ENDS WITH 'z' OR n.name CONTAINS 'q' RETURN n",
+        "MATCH (n) WHERE n.name IN ['a', 'b'] AND n.age IS NOT NULL AND NOT n.x IS NULL RETURN n",
+        "MATCH (n) WHERE n.a = $p RETURN n, $q",
+        "MATCH (n {`weird key`: 1}) RETURN n.`weird key`",
+        "MATCH (n:`Odd Label`) RETURN count(n) AS `my count`",
+        "MATCH (n) RETURN 'it''s', -1, 2.5, true, false, NULL",
+        "MATCH (n) RETURN n.a + 1 * 2 - (3 - 4), n.a % 2, n.b / 3, n.a <> 1, n.b <= 2",
+        "MATCH (n) RETURN CASE WHEN n.a = 1 THEN 'x' ELSE 'y' END",
+        "CREATE (n:Bug {name: 'Ant', legs: 6, tags: ['x'], m: {a: 1}})",
+        "CREATE (a)-[:EATS {w: 0.5}]->(b), (c)",
+        "MATCH (a), (b) CREATE (a)-[:EATS]->(b)",
+        "MATCH (n)-[r]->() DELETE r, n",
+        "MATCH (n) DETACH DELETE n",
+        "MATCH (n) SET n.a = 1, n:L, n = {a: 1}, n += $m REMOVE n.a, n:L",
+        "MERGE (n:Bug {name: 'Ant'}) ON CREATE SET n.a = 1 ON MATCH SET n.b = 2",
+        "UNWIND [1, {a: 'x'}, [2]] AS x RETURN x",
+        "UNWIND $rows AS row MATCH (n {id: row.id}) CREATE (:Bug {of: n, name: row.name})",
+        "MATCH (a:Cat) RETURN a.name AS n UNION ALL MATCH (b:Dog) RETURN b.name AS n UNION ALL RETURN 'x' AS n",
+        "MATCH (n:Person:!Employee)-[:KNOWS|LIKES]->(m:(Cat|Dog):!(Old:Sick)) RETURN m",
+        "MATCH (a) WHERE (a)-[:FRIEND]->(:Admin) AND NOT (a)<--() RETURN a, (a)--() AS linked",
+        "MATCH (a) WHERE EXISTS { MATCH (a)-->(b), (b)-->(c) WHERE c.x = 1 } OR NOT EXISTS { MATCH (a)--() } RETURN a",
+    ];
+    for query in queries {
+        let parsed = cypher().verified_cypher_query(query);
+        assert_eq!(
+            cypher().parse_sql_statements(&parsed.to_string()).unwrap(),
+            vec![Statement::Cypher(parsed)]
+        );
+    }
+}
+
+#[test]
+fn parse_sql_with_cypher_dialect() {
+    cypher().verified_stmt("SELECT * FROM nodes");
+    cypher().verified_stmt("DELETE FROM nodes WHERE id = 1");
+    cypher().verified_stmt("CREATE TABLE nodes (id INT)");
+}
+
+#[test]
+fn sql_dialects_do_not_parse_cypher() {
+    let dialects = all_dialects();
+    assert!(dialects.parse_sql_statements("MATCH (n) RETURN n").is_err());
+
+    let stmt = TestedDialects::new(vec![Box::new(PostgreSqlDialect {})])
+        .verified_stmt("DELETE t FROM t JOIN u ON t.id = u.id");
+    assert!(matches!(stmt, Statement::Delete(_)));
+
+    let stmt = TestedDialects::new(vec![Box::new(GenericDialect {})]).verified_stmt("RETURN x");
+    assert!(matches!(stmt, Statement::Return(_)));
+}
+
+#[test]
+fn tokenize_relationship_dashes() {
+    let tokens = Tokenizer::new(&CypherDialect {}, "(a)-->(b) // trailing comment")
+        .tokenize()
+        .unwrap();
+    assert_eq!(tokens[3], Token::Minus);
+    assert_eq!(tokens[4], Token::Arrow);
+
+    let tokens = Tokenizer::new(&GenericDialect {}, "SELECT 1 -- comment")
+        .tokenize()
+        .unwrap();
+    assert!(!tokens.contains(&Token::Minus));
+
+    assert!(Parser::parse_sql(&CypherDialect {}, "MATCH (n) // comment\nRETURN n").is_ok());
+}
+
+#[test]
+fn parse_node_pattern() {
+    let query = cypher().verified_cypher_query("MATCH (n:Bug {name: 'Ant', legs: 6}) RETURN n");
+    assert_eq!(
+        query.clauses[0],
+        CypherClause::Match(MatchClause {
+            optional: false,
+            pattern: vec![PathPattern {
+                start: NodePattern {
+                    variable: Some(Ident::new("n")),
+                    label: Some(LabelExpression::Label(Ident::new("Bug"))),
+                    properties: vec![
+                        PropertyEntry {
+                            key: Ident::new("name"),
+                            value: Expr::value(single_quoted_string("Ant")),
+                        },
+                        PropertyEntry {
+                            key: Ident::new("legs"),
+                            value: Expr::value(number("6")),
+                        },
+                    ],
+                },
+                segments: vec![],
+            }],
+            selection: None,
+        })
+    );
+    assert_eq!(
+        query.clauses[1],
+        CypherClause::Return(ReturnClause {
+            distinct: false,
+            items: vec![ProjectionItem {
+                expr: Expr::Identifier(Ident::new("n")),
+                alias: None,
+            }],
+            order_by: vec![],
+            skip: None,
+            limit: None,
+        })
+    );
+
+    cypher().verified_stmt("MATCH () RETURN 1");
+    cypher().verified_stmt("MATCH (:Bug) RETURN 1");
+    cypher().verified_stmt("MATCH ({name: 'Ant'}) RETURN 1");
+}
+
+#[test]
+fn parse_property_values() {
+    let query = cypher().verified_cypher_query(
+        "CREATE (n {age: -42, score: 1.5, ok: true, gone: NULL, \
+         tags: ['a', [1, 2]], address: {city: 'Paris'}})",
+    );
+    let CypherClause::Create(create) = &query.clauses[0] else {
+        panic!("expected CREATE");
+    };
+    let values: Vec<&Expr> = create.pattern[0]
+        .start
+        .properties
+        .iter()
+        .map(|entry| &entry.value)
+        .collect();
+    assert_eq!(
+        values,
+        [
+            &Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr: Box::new(Expr::value(number("42"))),
+            },
+            &Expr::value(number("1.5")),
+            &Expr::value(Value::Boolean(true)),
+            &Expr::value(Value::Null),
+            &Expr::Array(Array {
+                elem: vec![
+                    Expr::value(single_quoted_string("a")),
+                    Expr::Array(Array {
+                        elem: vec![Expr::value(number("1")), Expr::value(number("2"))],
+                        named: false,
+                    }),
+                ],
+                named: false,
+            }),
+            &Expr::Dictionary(vec![DictionaryField {
+                key: Ident::new("city"),
+                value: Box::new(Expr::value(single_quoted_string("Paris"))),
+            }]),
+        ]
+    );
+
+    // other expressions are left to the desugarer to translate or reject
+    let stmt = cypher().verified_stmt("CREATE (n {age: 1 + 2, name: toUpper($n)})");
+    assert_eq!(
+        stmt.desugar_cypher_to_sql(&GenericDialect {})
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        ["INSERT INTO nodes (properties) VALUES (json_object('age', 1 + 2, 'name', toUpper(?)))"]
+    );
+    assert_eq!(
+        cypher()
+            .verified_stmt("CREATE (n {age: m.age + 1})")
+            .desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::UnboundVariable("m".to_string()))
+    );
+    assert!(matches!(
+        cypher()
+            .verified_stmt("CREATE (n {x: CASE WHEN true THEN 1 END})")
+            .desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn parse_parameters() {
+    let query = cypher().verified_cypher_query(
+        "MATCH (n:User {id: $id}) WHERE n.age > $min RETURN n SKIP $skip LIMIT $1",
+    );
+    let CypherClause::Match(m) = &query.clauses[0] else {
+        panic!("expected MATCH");
+    };
+    assert_eq!(
+        m.pattern[0].start.properties[0].value,
+        Expr::value(Value::Placeholder("$id".to_string()))
+    );
+    let CypherClause::Return(r) = &query.clauses[1] else {
+        panic!("expected RETURN");
+    };
+    assert_eq!(
+        r.limit,
+        Some(Expr::value(Value::Placeholder("$1".to_string())))
+    );
+}
+
+#[test]
+fn parse_relationship_pattern() {
+    let query =
+        cypher().verified_cypher_query("MATCH (a)-[r:EATS {since: 2020}]->(b) RETURN a, r, b");
+    let CypherClause::Match(MatchClause { pattern, .. }) = &query.clauses[0] else {
+        unreachable!()
+    };
+    assert_eq!(
+        pattern[0].segments,
+        vec![PathSegment {
+            relationship: RelationshipPattern {
+                direction: RelationshipDirection::Outgoing,
+                variable: Some(Ident::new("r")),
+                rel_type: Some(LabelExpression::Label(Ident::new("EATS"))),
+                length: None,
+                properties: vec![PropertyEntry {
+                    key: Ident::new("since"),
+                    value: Expr::value(number("2020")),
+                }],
+            },
+            node: NodePattern {
+                variable: Some(Ident::new("b")),
+                label: None,
+                properties: vec![],
+            },
+        }]
+    );
+
+    cypher().one_statement_parses_to("MATCH ()-[]->() RETURN 1", "MATCH ()-->() RETURN 1");
+    cypher().verified_stmt(
+        "MATCH (a:Bug {name: 'Ant'}), (b:Bug {name: 'Bee'}) CREATE (a)-[:EATS]->(b)",
+    );
+    cypher().verified_stmt("RETURN 'hello' AS message");
+}
+
+#[test]
+fn parse_relationship_direction() {
+    let direction = |sql: &str| {
+        let query = cypher().verified_cypher_query(sql);
+        let CypherClause::Match(MatchClause { pattern, .. }) = &query.clauses[0] else {
+            unreachable!()
+        };
+        pattern[0].segments[0].relationship.direction
+    };
+    assert_eq!(
+        direction("MATCH (a)-[r:KNOWS]->(b) RETURN a"),
+        RelationshipDirection::Outgoing
+    );
+    assert_eq!(
+        direction("MATCH (a)<-[r:KNOWS]-(b) RETURN a"),
+        RelationshipDirection::Incoming
+    );
+    assert_eq!(
+        direction("MATCH (a)-[r:KNOWS]-(b) RETURN a"),
+        RelationshipDirection::Undirected
+    );
+    assert_eq!(
+        direction("MATCH (a)-->(b) RETURN a"),
+        RelationshipDirection::Outgoing
+    );
+    assert_eq!(
+        direction("MATCH (a)<--(b) RETURN a"),
+        RelationshipDirection::Incoming
+    );
+    assert_eq!(
+        direction("MATCH (a)--(b) RETURN a"),
+        RelationshipDirection::Undirected
+    );
+
+    cypher().verified_stmt("MATCH (a)<-[:KNOWS]-(b)-[:KNOWS]-(c)-->(d) RETURN a");
+    assert_eq!(
+        cypher()
+            .parse_sql_statements("MATCH (a)<-[r]->(b) RETURN a")
+            .unwrap_err()
+            .to_string(),
+        "sql parser error: Expected: -, found: ->"
+    );
+}
+
+#[test]
+fn parse_variable_length_relationship() {
+    let length = |sql: &str| {
+        let query = cypher().verified_cypher_query(sql);
+        let CypherClause::Match(MatchClause { pattern, .. }) = &query.clauses[0] else {
+            unreachable!()
+        };
+        pattern[0].segments[0].relationship.length.clone()
+    };
+    assert_eq!(
+        length("MATCH (a)-[*]->(b) RETURN b"),
+        Some(RelationshipLength::Any)
+    );
+    assert_eq!(
+        length("MATCH (a)-[:KNOWS*3]->(b) RETURN b"),
+        Some(RelationshipLength::Exactly(3))
+    );
+    assert_eq!(
+        length("MATCH (a)-[r:KNOWS*1..3]->(b) RETURN b"),
+        Some(RelationshipLength::Range {
+            min: Some(1),
+            max: Some(3)
+        })
+    );
+    assert_eq!(
+        length("MATCH (a)-[*..3 {since: 2020}]-(b) RETURN b"),
+        Some(RelationshipLength::Range {
+            min: None,
+            max: Some(3)
+        })
+    );
+    assert_eq!(
+        length("MATCH (a)<-[*2..]-(b) RETURN b"),
+        Some(RelationshipLength::Range {
+            min: Some(2),
+            max: None
+        })
+    );
+    assert_eq!(length("MATCH (a)-[r]->(b) RETURN b"), None);
+
+    // `..` is not read as a decimal point
+    let tokens = Tokenizer::new(&CypherDialect {}, "1..3")
+        .tokenize()
+        .unwrap();
+    assert_eq!(
+        tokens,
+        vec![
+            Token::Number("1".to_string(), false),
+            Token::Period,
+            Token::Period,
+            Token::Number("3".to_string(), false),
+        ]
+    );
+}
+
+#[test]
+fn parse_match_where() {
+    let query = cypher().verified_cypher_query(
+        "MATCH (n:Person) WHERE n.age > 30 AND n.name STARTS WITH 'A' RETURN n",
+    );
+    let CypherClause::Match(MatchClause { selection, .. }) = &query.clauses[0] else {
+        unreachable!()
+    };
+    assert_eq!(
+        selection.as_ref().unwrap(),
+        &Expr::BinaryOp {
+            left: Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::CompoundIdentifier(vec![
+                    Ident::new("n"),
+                    Ident::new("age")
+                ])),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::value(number("30"))),
+            }),
+            op: BinaryOperator::And,
+            right: Box::new(Expr::BinaryOp {
+                left: Box::new(Expr::CompoundIdentifier(vec![
+                    Ident::new("n"),
+                    Ident::new("name")
+                ])),
+                op: BinaryOperator::CypherStartsWith,
+                right: Box::new(Expr::value(single_quoted_string("A"))),
+            }),
+        }
+    );
+
+    cypher().verified_stmt("MATCH (n) WHERE n.name ENDS WITH 'a' OR n.name CONTAINS 'b' RETURN n");
+    cypher().verified_stmt("MATCH (n) WHERE n.name =~ 'A.*' RETURN n");
+    cypher().verified_stmt("MATCH (n) WHERE n.email IS NULL RETURN n");
+    cypher().verified_stmt("MATCH (n) WHERE NOT n.age IN [1, 2, 3] DELETE n");
+    cypher().verified_stmt("MATCH (a)-[r]->(b) WHERE r.since IS NOT NULL RETURN a");
+
+    // SQL `IN` lists are unaffected
+    cypher().verified_stmt("SELECT * FROM nodes WHERE id IN (1, 2)");
+}
+
+#[test]
+fn parse_return_projections() {
+    let query = cypher().verified_cypher_query(
+        "MATCH (n)-[r]->(b) RETURN DISTINCT n.name AS name, r.weight * 2, b",
+    );
+    assert_eq!(
+        query.clauses[1],
+        CypherClause::Return(ReturnClause {
+            distinct: true,
+            items: vec![
+                ProjectionItem {
+                    expr: Expr::CompoundIdentifier(vec![Ident::new("n"), Ident::new("name")]),
+                    alias: Some(Ident::new("name")),
+                },
+                ProjectionItem {
+                    expr: Expr::BinaryOp {
+                        left: Box::new(Expr::CompoundIdentifier(vec![
+                            Ident::new("r"),
+                            Ident::new("weight")
+                        ])),
+                        op: BinaryOperator::Multiply,
+                        right: Box::new(Expr::value(number("2"))),
+                    },
+                    alias: None,
+                },
+                ProjectionItem {
+                    expr: Expr::Identifier(Ident::new("b")),
+                    alias: None,
+                },
+            ],
+            order_by: vec![],
+            skip: None,
+            limit: None,
+        })
+    );
+}
+
+#[test]
+fn parse_return_order_by_skip_limit() {
+    let query =
+        cypher().verified_cypher_query("MATCH (n) RETURN n ORDER BY n.name DESC SKIP 10 LIMIT 5");
+    let CypherClause::Return(ReturnClause {
+        order_by,
+        skip,
+        limit,
+        ..
+    }) = &query.clauses[1]
+    else {
+        panic!("Expected RETURN, got {:?}", query.clauses[1]);
+    };
+    assert_eq!(
+        order_by,
+        &vec![OrderByExpr {
+            expr: Expr::CompoundIdentifier(vec![Ident::new("n"), Ident::new("name")]),
+            options: OrderByOptions {
+                asc: Some(false),
+                nulls_first: None,
+            },
+            with_fill: None,
+        }]
+    );
+    assert_eq!(skip, &Some(Expr::value(number("10"))));
+    assert_eq!(limit, &Some(Expr::value(number("5"))));
+
+    cypher().verified_stmt("MATCH (n) RETURN n.name AS name, n.age ORDER BY name, n.age ASC");
+    cypher().verified_stmt("MATCH (n) RETURN n SKIP 3");
+    cypher().verified_stmt("MATCH (n) RETURN n LIMIT 3");
+}
+
+#[test]
+fn parse_with_clause() {
+    let query = cypher().verified_cypher_query(
+        "MATCH (a) WITH a, count(*) AS c WHERE c > 2 MATCH (a)-->(b) RETURN b",
+    );
+    assert_eq!(query.clauses.len(), 4);
+    assert_eq!(
+        query.clauses[1],
+        CypherClause::With(WithClause {
+            distinct: false,
+            items: vec![
+                ProjectionItem {
+                    expr: Expr::Identifier(Ident::new("a")),
+                    alias: None,
+                },
+                ProjectionItem {
+                    expr: cypher().verified_expr("count(*)"),
+                    alias: Some(Ident::new("c")),
+                },
+            ],
+            order_by: vec![],
+            skip: None,
+            limit: None,
+            selection: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("c"))),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::value(number("2"))),
+            }),
+        })
+    );
+
+    cypher().verified_stmt("MATCH (a) WITH DISTINCT a ORDER BY a.name SKIP 1 LIMIT 2 RETURN a");
+}
+
+#[test]
+fn parse_optional_match() {
+    let query = cypher().verified_cypher_query(
+        "MATCH (a) OPTIONAL MATCH (a)-[:OWNS]->(c) WHERE c.age > 3 RETURN a, c",
+    );
+    let CypherClause::Match(MatchClause {
+        optional, pattern, ..
+    }) = &query.clauses[1]
+    else {
+        panic!("Expected MATCH, got {:?}", query.clauses[1]);
+    };
+    assert!(optional);
+    assert_eq!(pattern[0].to_string(), "(a)-[:OWNS]->(c)");
+
+    let query = cypher().verified_cypher_query("OPTIONAL MATCH (a) RETURN a");
+    assert!(matches!(
+        &query.clauses[0],
+        CypherClause::Match(MatchClause { optional: true, .. })
+    ));
+}
+
+#[test]
+fn parse_delete_clause() {
+    let query = cypher().verified_cypher_query("MATCH (a)-[r]->(b) DETACH DELETE a, r");
+    assert_eq!(
+        query.clauses[1],
+        CypherClause::Delete(DeleteClause {
+            detach: true,
+            items: vec![
+                Expr::Identifier(Ident::new("a")),
+                Expr::Identifier(Ident::new("r"))
+            ],
+        })
+    );
+}
+
+#[test]
+fn parse_set_and_remove_clauses() {
+    let query = cypher().verified_cypher_query(
+        "MATCH (n) SET n.color = 'red', n:Insect, n = {legs: 6}, n += $props REMOVE n.color, n:Bug",
+    );
+    let n = || Ident::new("n");
+    assert_eq!(
+        query.clauses[1],
+        CypherClause::Set(SetClause {
+            items: vec![
+                SetItem::Property {
+                    variable: n(),
+                    key: Ident::new("color"),
+                    value: Expr::value(single_quoted_string("red")),
+                },
+                SetItem::Label {
+                    variable: n(),
+                    label: Ident::new("Insect"),
+                },
+                SetItem::Properties {
+                    variable: n(),
+                    value: Expr::Dictionary(vec![DictionaryField {
+                        key: Ident::new("legs"),
+                        value: Box::new(Expr::value(number("6"))),
+                    }]),
+                    merge: false,
+                },
+                SetItem::Properties {
+                    variable: n(),
+                    value: Expr::value(Value::Placeholder("$props".to_string())),
+                    merge: true,
+                },
+            ],
+        })
+    );
+    assert_eq!(
+        query.clauses[2],
+        CypherClause::Remove(RemoveClause {
+            items: vec![
+                RemoveItem::Property {
+                    variable: n(),
+                    key: Ident::new("color"),
+                },
+                RemoveItem::Label {
+                    variable: n(),
+                    label: Ident::new("Bug"),
+                },
+            ],
+        })
+    );
+
+    cypher().verified_stmt("MATCH (n) SET n.age = n.age + 1, n.tags = ['a', 'b']");
+    assert!(cypher().parse_sql_statements("MATCH (n) REMOVE n").is_err());
+}
+
+#[test]
+fn parse_merge_clause() {
+    let query = cypher().verified_cypher_query(
+        "MERGE (n:Bug {name: 'Ant'}) ON CREATE SET n.legs = 6 ON MATCH SET n:Insect, n += $props",
+    );
+    let n = || Ident::new("n");
+    let [CypherClause::Merge(merge)] = query.clauses.as_slice() else {
+        panic!("Expected a single MERGE clause, got {query:?}");
+    };
+    assert_eq!(merge.pattern.to_string(), "(n:Bug {name: 'Ant'})");
+    assert_eq!(
+        merge.on_create,
+        vec![SetItem::Property {
+            variable: n(),
+            key: Ident::new("legs"),
+            value: Expr::value(number("6")),
+        }]
+    );
+    assert_eq!(
+        merge.on_match,
+        vec![
+            SetItem::Label {
+                variable: n(),
+                label: Ident::new("Insect"),
+            },
+            SetItem::Properties {
+                variable: n(),
+                value: Expr::value(Value::Placeholder("$props".to_string())),
+                merge: true,
+            },
+        ]
+    );
+
+    cypher().verified_stmt("MERGE (n:Bug {name: 'Ant'})");
+    cypher().one_statement_parses_to(
+        "MERGE (n:Bug {name: 'Ant'}) ON MATCH SET n.seen = true ON CREATE SET n.seen = false",
+        "MERGE (n:Bug {name: 'Ant'}) ON CREATE SET n.seen = false ON MATCH SET n.seen = true",
+    );
+    // SQL MERGE is still parsed as such
+    assert!(matches!(
+        cypher().verified_stmt("MERGE INTO t USING s ON t.id = s.id WHEN MATCHED THEN DELETE"),
+        Statement::Merge { .. }
+    ));
+}
+
+#[test]
+fn parse_unwind_clause() {
+    let query =
+        cypher().verified_cypher_query("UNWIND $rows AS row CREATE (:User {id: row.id, all: row})");
+    let [CypherClause::Unwind(unwind), CypherClause::Create(create)] = query.clauses.as_slice()
+    else {
+        panic!("Expected UNWIND followed by CREATE, got {query:?}");
+    };
+    assert_eq!(
+        unwind.expr,
+        Expr::value(Value::Placeholder("$rows".to_string()))
+    );
+    assert_eq!(unwind.alias, Ident::new("row"));
+    assert_eq!(
+        create.pattern[0].start.properties,
+        vec![
+            PropertyEntry {
+                key: Ident::new("id"),
+                value: Expr::CompoundIdentifier(vec![Ident::new("row"), Ident::new("id")]),
+            },
+            PropertyEntry {
+                key: Ident::new("all"),
+                value: Expr::Identifier(Ident::new("row")),
+            },
+        ]
+    );
+
+    let query = cypher().verified_cypher_query("UNWIND [1, 'a', {b: true}] AS x RETURN x");
+    let [CypherClause::Unwind(unwind), _] = query.clauses.as_slice() else {
+        panic!("Expected UNWIND followed by RETURN, got {query:?}");
+    };
+    assert!(matches!(&unwind.expr, Expr::Array(array) if array.elem.len() == 3));
+    assert!(cypher()
+        .parse_sql_statements("UNWIND [1, 2] RETURN 1")
+        .is_err());
+}
+
+#[test]
+fn parse_union() {
+    let query = cypher().verified_cypher_query(
+        "MATCH (a:Cat) RETURN a.name AS n UNION MATCH (b:Dog) RETURN b.name AS n ORDER BY n",
+    );
+    assert_eq!(query.clauses.len(), 2);
+    let [union] = query.unions.as_slice() else {
+        panic!("Expected a single UNION, got {query:?}");
+    };
+    assert!(!union.all);
+    assert_eq!(
+        union.to_string(),
+        "UNION MATCH (b:Dog) RETURN b.name AS n ORDER BY n"
+    );
+
+    let query = cypher().verified_cypher_query("RETURN 1 AS x UNION ALL RETURN 2 AS x");
+    assert!(query.unions[0].all);
+    // SQL UNION is still parsed as such
+    cypher().verified_stmt("SELECT 1 UNION SELECT 2");
+    assert!(cypher()
+        .parse_sql_statements("RETURN 1 AS x UNION")
+        .is_err());
+}
+
+#[test]
+fn parse_label_expressions() {
+    let label = |name: &str| LabelExpression::Label(Ident::new(name));
+    let node_label = |query: &str| {
+        let query = cypher().verified_cypher_query(query);
+        let CypherClause::Match(m) = &query.clauses[0] else {
+            panic!("Expected MATCH, got {query:?}");
+        };
+        m.pattern[0].start.label.clone()
+    };
+
+    assert_eq!(
+        node_label("MATCH (n:Person:Employee) RETURN n"),
+        Some(LabelExpression::All(vec![
+            label("Person"),
+            label("Employee")
+        ]))
+    );
+    assert_eq!(
+        node_label("MATCH (n:Cat|Dog|Bird) RETURN n"),
+        Some(LabelExpression::Any(vec![
+            label("Cat"),
+            label("Dog"),
+            label("Bird")
+        ]))
+    );
+    assert_eq!(
+        node_label("MATCH (n:!Cat) RETURN n"),
+        Some(LabelExpression::Not(Box::new(label("Cat"))))
+    );
+    assert_eq!(
+        node_label("MATCH (n:(Cat|Dog):!Old) RETURN n"),
+        Some(LabelExpression::All(vec![
+            LabelExpression::Any(vec![label("Cat"), label("Dog")]),
+            LabelExpression::Not(Box::new(label("Old"))),
+        ]))
+    );
+    cypher().one_statement_parses_to("MATCH (n:(Cat)) RETURN n", "MATCH (n:Cat) RETURN n");
+
+    // the legacy `:TYPE1|:TYPE2` of relationships
+    let query = cypher().one_statement_parses_to(
+        "MATCH (a)-[r:KNOWS|:LIKES]->(b) RETURN b",
+        "MATCH (a)-[r:KNOWS|LIKES]->(b) RETURN b",
+    );
+    let Statement::Cypher(query) = query else {
+        panic!("Expected a Cypher query, got {query:?}");
+    };
+    let CypherClause::Match(m) = &query.clauses[0] else {
+        panic!("Expected MATCH, got {query:?}");
+    };
+    assert_eq!(
+        m.pattern[0].segments[0].relationship.rel_type,
+        Some(LabelExpression::Any(vec![label("KNOWS"), label("LIKES")]))
+    );
+
+    assert!(cypher()
+        .parse_sql_statements("MATCH (n:Cat|) RETURN n")
+        .is_err());
+    assert!(cypher()
+        .parse_sql_statements("MATCH (n:(Cat) RETURN n")
+        .is_err());
+}
+
+#[test]
+fn parse_pattern_predicates() {
+    let selection = |query: &str| {
+        let query = cypher().verified_cypher_query(query);
+        let CypherClause::Match(m) = &query.clauses[0] else {
+            panic!("Expected MATCH, got {query:?}");
+        };
+        m.selection.clone().expect("a WHERE predicate")
+    };
+
+    let Expr::BinaryOp { left, op, right } =
+        selection("MATCH (a) WHERE (a)-[:FRIEND]->(:Admin) AND NOT (a)-->() RETURN a")
+    else {
+        panic!("Expected AND");
+    };
+    assert_eq!(op, BinaryOperator::And);
+    let Expr::CypherPattern(pattern) = *left else {
+        panic!("Expected a pattern predicate, got {left:?}");
+    };
+    assert_eq!(pattern.to_string(), "(a)-[:FRIEND]->(:Admin)");
+    assert!(matches!(
+        *right,
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr
+        } if matches!(*expr, Expr::CypherPattern(_))
+    ));
+
+    let Expr::CypherExists(subquery) =
+        selection("MATCH (a) WHERE EXISTS { MATCH (a)-->(b) WHERE b.x = 1 } RETURN a")
+    else {
+        panic!("Expected EXISTS");
+    };
+    assert_eq!(subquery.pattern.len(), 1);
+    assert!(subquery.selection.is_some());
+    // MATCH is optional inside EXISTS
+    cypher().one_statement_parses_to(
+        "MATCH (a) WHERE NOT EXISTS { (a)-->() } RETURN a",
+        "MATCH (a) WHERE NOT EXISTS { MATCH (a)-->() } RETURN a",
+    );
+
+    // parenthesized expressions are not patterns
+    assert!(matches!(
+        selection("MATCH (a) WHERE (a.x) - 1 > (a.y) RETURN a"),
+        Expr::BinaryOp { .. }
+    ));
+    // SQL EXISTS is unchanged
+    cypher().verified_stmt("SELECT 1 WHERE NOT EXISTS (SELECT 1)");
+    assert!(cypher()
+        .parse_sql_statements("MATCH (a) WHERE EXISTS { (a)--> } RETURN a")
+        .is_err());
+}
+
+#[test]
+fn desugar_match() {
+    assert_eq!(
+        desugared("MATCH (n) RETURN n"),
+        "SELECT n.* FROM nodes AS n"
+    );
+    assert_eq!(
+        desugared("MATCH (b:Bug {name: 'Ant', legs: 6}) RETURN b"),
+        "SELECT b.* FROM nodes AS b WHERE b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Ant' AND json_extract(b.properties, '$.legs') = 6"
+    );
+    assert_eq!(
+        desugared("MATCH (a)-[r:EATS]->(b) RETURN a, r, b"),
+        "SELECT a.*, r.*, b.* FROM nodes AS a \
+         JOIN edges AS r ON r.src_id = a.id \
+         JOIN nodes AS b ON r.dst_id = b.id \
+         WHERE r.type = 'EATS'"
+    );
+    assert_eq!(
+        desugared("RETURN 'hello' AS message"),
+        "SELECT 'hello' AS \"message\""
+    );
+}
+
+#[test]
+fn desugar_multi_hop_match() {
+    assert_eq!(
+        desugared("MATCH (a:Person)-[r1:KNOWS]->(b)-[r2:KNOWS]->(c) RETURN a, r1, b, r2, c"),
+        "SELECT a.*, r1.*, b.*, r2.*, c.* FROM nodes AS a \
+         JOIN edges AS r1 ON r1.src_id = a.id \
+         JOIN nodes AS b ON r1.dst_id = b.id \
+         JOIN edges AS r2 ON r2.src_id = b.id \
+         JOIN nodes AS c ON r2.dst_id = c.id \
+         WHERE a.label = 'Person' AND r1.type = 'KNOWS' AND r2.type = 'KNOWS' AND r1.id <> r2.id"
+    );
+
+    // anonymous patterns get generated aliases
+    assert_eq!(
+        desugared("MATCH (a)-->()-[:KNOWS]->(c) RETURN c"),
+        "SELECT c.* FROM nodes AS a \
+         JOIN edges AS _r1 ON _r1.src_id = a.id \
+         JOIN nodes AS _n2 ON _r1.dst_id = _n2.id \
+         JOIN edges AS _r3 ON _r3.src_id = _n2.id \
+         JOIN nodes AS c ON _r3.dst_id = c.id \
+         WHERE _r3.type = 'KNOWS' AND _r1.id <> _r3.id"
+    );
+
+    // variables that are already bound are not joined again
+    assert_eq!(
+        desugared("MATCH (a)-[r1]->(b)-[r2]->(a) RETURN b"),
+        "SELECT b.* FROM nodes AS a \
+         JOIN edges AS r1 ON r1.src_id = a.id \
+         JOIN nodes AS b ON r1.dst_id = b.id \
+         JOIN edges AS r2 ON r2.src_id = b.id AND r2.dst_id = a.id \
+         WHERE r1.id <> r2.id"
+    );
+    assert_eq!(
+        desugared("MATCH (a)-[r]->(b), (b)-[s]->(c), (d) RETURN a, d"),
+        "SELECT a.*, d.* FROM nodes AS a \
+         JOIN edges AS r ON r.src_id = a.id \
+         JOIN nodes AS b ON r.dst_id = b.id \
+         JOIN edges AS s ON s.src_id = b.id \
+         JOIN nodes AS c ON s.dst_id = c.id \
+         CROSS JOIN nodes AS d \
+         WHERE r.id <> s.id"
+    );
+
+    let stmt = cypher().verified_stmt("MATCH (a)-[r]->(b)-[r]->(c) RETURN a");
+    assert!(matches!(
+        stmt.desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn desugar_keyword_variables() {
+    let stmt = cypher().verified_stmt(
+        "MATCH (user)-[order]->(b) WITH user, order.total AS total RETURN user, total",
+    );
+    let desugared_for = |dialect: &dyn Dialect| {
+        let sql = stmt.desugar_cypher_to_sql(dialect).unwrap()[0].to_string();
+        assert_eq!(
+            Parser::parse_sql(dialect, &sql).unwrap()[0].to_string(),
+            sql
+        );
+        sql
+    };
+    assert_eq!(
+        desugared_for(&PostgreSqlDialect {}),
+        "WITH _with1 AS (SELECT \"user\".id AS \"user\", \"order\".properties ->> 'total' AS total \
+         FROM nodes AS \"user\" \
+         JOIN edges AS \"order\" ON \"order\".src_id = \"user\".id \
+         JOIN nodes AS b ON \"order\".dst_id = b.id) \
+         SELECT \"user\".*, _with1.total AS total FROM _with1 \
+         JOIN nodes AS \"user\" ON \"user\".id = _with1.\"user\""
+    );
+    assert_eq!(
+        desugared_for(&MySqlDialect {}),
+        "WITH _with1 AS (SELECT `user`.id AS `user`, json_extract(`order`.properties, '$.total') AS total \
+         FROM nodes AS `user` \
+         JOIN edges AS `order` ON `order`.src_id = `user`.id \
+         JOIN nodes AS b ON `order`.dst_id = b.id) \
+         SELECT `user`.*, _with1.total AS total FROM _with1 \
+         JOIN nodes AS `user` ON `user`.id = _with1.`user`"
+    );
+    assert_eq!(
+        desugared_for(&MsSqlDialect {}),
+        "WITH _with1 AS (SELECT [user].id AS [user], JSON_VALUE([order].properties, '$.total') AS total \
+         FROM nodes AS [user] \
+         JOIN edges AS [order] ON [order].src_id = [user].id \
+         JOIN nodes AS b ON [order].dst_id = b.id) \
+         SELECT [user].*, _with1.total AS total FROM _with1 \
+         JOIN nodes AS [user] ON [user].id = _with1.[user]"
+    );
+
+    let stmt = cypher().verified_stmt("CREATE (user:Person)-[:KNOWS]->(order:Person)");
+    assert_eq!(
+        stmt.desugar_cypher_to_sql(&PostgreSqlDialect {}).unwrap()[0].to_string(),
+        "WITH \"user\" AS (INSERT INTO nodes (label) VALUES ('Person') RETURNING id), \
+         \"order\" AS (INSERT INTO nodes (label) VALUES ('Person') RETURNING id) \
+         INSERT INTO edges (src_id, dst_id, type, properties) \
+         VALUES ((SELECT id FROM \"user\"), (SELECT id FROM \"order\"), 'KNOWS', '{}')"
+    );
+}
+
+#[test]
+fn desugar_relationship_direction() {
+    assert_eq!(
+        desugared("MATCH (a)<-[r:EATS]-(b) RETURN a"),
+        "SELECT a.* FROM nodes AS a \
+         JOIN edges AS r ON r.dst_id = a.id \
+         JOIN nodes AS b ON r.src_id = b.id \
+         WHERE r.type = 'EATS'"
+    );
+    assert_eq!(
+        desugared("MATCH (a)-[r:KNOWS]-(b) RETURN b"),
+        "SELECT b.* FROM nodes AS a \
+         JOIN edges AS r ON (r.src_id = a.id OR r.dst_id = a.id) \
+         JOIN nodes AS b ON ((r.src_id = a.id AND r.dst_id = b.id) OR (r.dst_id = a.id AND r.src_id = b.id)) \
+         WHERE r.type = 'KNOWS'"
+    );
+    assert_eq!(
+        desugared("MATCH (a)-->(b)<--(a) RETURN b"),
+        "SELECT b.* FROM nodes AS a \
+         JOIN edges AS _r1 ON _r1.src_id = a.id \
+         JOIN nodes AS b ON _r1.dst_id = b.id \
+         JOIN edges AS _r2 ON _r2.dst_id = b.id AND _r2.src_id = a.id \
+         WHERE _r1.id <> _r2.id"
+    );
+
+    assert_eq!(
+        desugared("MATCH (a:Bug {name: 'Ant'}), (b:Bug {name: 'Bee'}) CREATE (a)<-[:EATS]-(b)"),
+        "INSERT INTO edges (src_id, dst_id, type, properties) \
+         SELECT b.id, a.id, 'EATS', '{}' FROM nodes AS a CROSS JOIN nodes AS b \
+         WHERE a.label = 'Bug' AND json_extract(a.properties, '$.name') = 'Ant' \
+         AND b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Bee'"
+    );
+    assert_eq!(
+        cypher()
+            .verified_stmt("CREATE (:Bug {name: 'Ant'})<-[:EATS]-(:Bug {name: 'Bee'})")
+            .desugar_cypher_to_sql(&PostgreSqlDialect {})
+            .unwrap()[0]
+            .to_string(),
+        "WITH _n1 AS (INSERT INTO nodes (label, properties) VALUES ('Bug', '{\"name\":\"Ant\"}') RETURNING id), \
+         _n2 AS (INSERT INTO nodes (label, properties) VALUES ('Bug', '{\"name\":\"Bee\"}') RETURNING id) \
+         INSERT INTO edges (src_id, dst_id, type, properties) \
+         VALUES ((SELECT id FROM _n2), (SELECT id FROM _n1), 'EATS', '{}')"
+    );
+    let stmt = cypher().verified_stmt("CREATE (a)-[:EATS]-(b)");
+    assert!(matches!(
+        stmt.desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn desugar_variable_length_relationship() {
+    assert_eq!(
+        desugared("MATCH (a:Person {name: 'Ann'})-[:KNOWS*1..3]->(b) RETURN b"),
+        "WITH RECURSIVE _paths1 (start_id, end_id, hops, path) AS (\
+         SELECT id, id, 0, ',' FROM nodes \
+         UNION ALL \
+         SELECT p.start_id, e.dst_id, p.hops + 1, p.path || e.id || ',' \
+         FROM _paths1 AS p JOIN edges AS e ON e.src_id = p.end_id \
+         WHERE e.type = 'KNOWS' AND p.hops < 3 AND p.path NOT LIKE ('%,' || e.id || ',%')\
+         ) \
+         SELECT b.* FROM nodes AS a \
+         JOIN _paths1 AS _r2 ON _r2.start_id = a.id \
+         JOIN nodes AS b ON _r2.end_id = b.id \
+         WHERE a.label = 'Person' AND json_extract(a.properties, '$.name') = 'Ann' AND _r2.hops >= 1"
+    );
+
+    // unbounded, and closing a cycle back to the start node
+    assert_eq!(
+        desugared("MATCH (a)<-[r*0..]-(a) RETURN a"),
+        "WITH RECURSIVE _paths1 (start_id, end_id, hops, path) AS (\
+         SELECT id, id, 0, ',' FROM nodes \
+         UNION ALL \
+         SELECT p.start_id, e.src_id, p.hops + 1, p.path || e.id || ',' \
+         FROM _paths1 AS p JOIN edges AS e ON e.dst_id = p.end_id \
+         WHERE p.path NOT LIKE ('%,' || e.id || ',%')\
+         ) \
+         SELECT a.* FROM nodes AS a \
+         JOIN _paths1 AS r ON r.start_id = a.id AND r.end_id = a.id"
+    );
+
+    assert_eq!(
+        desugared("MATCH (a)-[*2]-(b) RETURN b"),
+        "WITH RECURSIVE _paths1 (start_id, end_id, hops, path) AS (\
+         SELECT id, id, 0, ',' FROM nodes \
+         UNION ALL \
+         SELECT p.start_id, CASE WHEN e.src_id = p.end_id THEN e.dst_id ELSE e.src_id END, p.hops + 1, p.path || e.id || ',' \
+         FROM _paths1 AS p JOIN edges AS e ON (e.src_id = p.end_id OR e.dst_id = p.end_id) \
+         WHERE p.hops < 2 AND p.path NOT LIKE ('%,' || e.id || ',%')\
+         ) \
+         SELECT b.* FROM nodes AS a \
+         JOIN _paths1 AS _r2 ON _r2.start_id = a.id \
+         JOIN nodes AS b ON _r2.end_id = b.id \
+         WHERE _r2.hops >= 2"
+    );
+
+    // the path is concatenated and typed per dialect
+    let stmt = cypher().verified_stmt("MATCH (a)-[*1..2]->(b) RETURN b");
+    let desugared_for = |dialect: &dyn Dialect| {
+        let sql = stmt.desugar_cypher_to_sql(dialect).unwrap()[0].to_string();
+        assert_eq!(
+            Parser::parse_sql(dialect, &sql).unwrap()[0].to_string(),
+            sql
+        );
+        sql
+    };
+    let paths = "JOIN _paths1 AS _r2 ON _r2.start_id = a.id \
+                 JOIN nodes AS b ON _r2.end_id = b.id WHERE _r2.hops >= 1";
+    assert_eq!(
+        desugared_for(&MySqlDialect {}),
+        format!(
+            "WITH RECURSIVE _paths1 (start_id, end_id, hops, path) AS (\
+             SELECT id, id, 0, CAST(',' AS CHAR(65535)) FROM nodes \
+             UNION ALL \
+             SELECT p.start_id, e.dst_id, p.hops + 1, CAST(CONCAT(p.path, e.id, ',') AS CHAR(65535)) \
+             FROM _paths1 AS p JOIN edges AS e ON e.src_id = p.end_id \
+             WHERE p.hops < 2 AND p.path NOT LIKE (CONCAT('%,', e.id, ',%'))\
+             ) \
+             SELECT b.* FROM nodes AS a {paths}"
+        )
+    );
+    assert_eq!(
+        desugared_for(&MsSqlDialect {}),
+        format!(
+            "WITH _paths1 (start_id, end_id, hops, path) AS (\
+             SELECT id, id, 0, CAST(',' AS VARCHAR(MAX)) FROM nodes \
+             UNION ALL \
+             SELECT p.start_id, e.dst_id, p.hops + 1, CAST(CONCAT(p.path, e.id, ',') AS VARCHAR(MAX)) \
+             FROM _paths1 AS p JOIN edges AS e ON e.src_id = p.end_id \
+             WHERE p.hops < 2 AND p.path NOT LIKE (CONCAT('%,', e.id, ',%'))\
+             ) \
+             SELECT b.* FROM nodes AS a {paths}"
+        )
+    );
+    assert_eq!(
+        desugared_for(&BigQueryDialect {}),
+        format!(
+            "WITH RECURSIVE _paths1 AS (\
+             SELECT id AS start_id, id AS end_id, 0 AS hops, ',' AS path FROM nodes \
+             UNION ALL \
+             SELECT p.start_id, e.dst_id, p.hops + 1, CONCAT(p.path, CAST(e.id AS STRING), ',') \
+             FROM _paths1 AS p JOIN edges AS e ON e.src_id = p.end_id \
+             WHERE p.hops < 2 AND p.path NOT LIKE (CONCAT('%,', CAST(e.id AS STRING), ',%'))\
+             ) \
+             SELECT b.* FROM nodes AS a {paths}"
+        )
+    );
+}
+
+#[test]
+fn desugar_match_where() {
+    assert_eq!(
+        desugared("MATCH (n:Person) WHERE n.age > 30 AND n.name STARTS WITH 'A' RETURN n"),
+        "SELECT n.* FROM nodes AS n WHERE n.label = 'Person' \
+         AND json_extract(n.properties, '$.age') > 30 \
+         AND substr(json_extract(n.properties, '$.name'), 1, length('A')) = 'A'"
+    );
+    assert_eq!(
+        desugared("MATCH (n) WHERE n.name ENDS WITH 'a' OR n.name CONTAINS n.nickname RETURN n"),
+        "SELECT n.* FROM nodes AS n WHERE (\
+         substr(json_extract(n.properties, '$.name'), length(json_extract(n.properties, '$.name')) - length('a') + 1) = 'a' \
+         OR instr(json_extract(n.properties, '$.name'), json_extract(n.properties, '$.nickname')) > 0)"
+    );
+    assert_eq!(
+        desugared("MATCH (a)-[r:KNOWS]->(b) WHERE a.name =~ 'A.*' AND r.since IS NULL AND NOT b.age IN [1, 2] RETURN b"),
+        "SELECT b.* FROM nodes AS a \
+         JOIN edges AS r ON r.src_id = a.id \
+         JOIN nodes AS b ON r.dst_id = b.id \
+         WHERE r.type = 'KNOWS' \
+         AND json_extract(a.properties, '$.name') REGEXP '^(?:A.*)$' \
+         AND json_extract(r.properties, '$.since') IS NULL \
+         AND NOT json_extract(b.properties, '$.age') IN (1, 2)"
+    );
+    assert_eq!(
+        desugared("MATCH (a)-[r]->(b) WHERE a = b RETURN r"),
+        "SELECT r.* FROM nodes AS a \
+         JOIN edges AS r ON r.src_id = a.id \
+         JOIN nodes AS b ON r.dst_id = b.id \
+         WHERE a.id = b.id"
+    );
+
+    let stmt = cypher().verified_stmt("MATCH (n) WHERE m.age > 30 RETURN n");
+    assert_eq!(
+        stmt.desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::UnboundVariable("m".to_string()))
+    );
+}
+
+#[test]
+fn desugar_return_projections() {
+    assert_eq!(
+        desugared("MATCH (n:Person)-[r:RATED]->(m) RETURN n.name AS name, r.weight * 2, m"),
+        "SELECT json_extract(n.properties, '$.name') AS \"name\", \
+         json_extract(r.properties, '$.weight') * 2 AS \"r.weight * 2\", m.* \
+         FROM nodes AS n \
+         JOIN edges AS r ON r.src_id = n.id \
+         JOIN nodes AS m ON r.dst_id = m.id \
+         WHERE n.label = 'Person' AND r.type = 'RATED'"
+    );
+    assert_eq!(
+        desugared("MATCH (n)-->(m) RETURN DISTINCT m"),
+        "SELECT DISTINCT m.* FROM nodes AS n \
+         JOIN edges AS _r1 ON _r1.src_id = n.id \
+         JOIN nodes AS m ON _r1.dst_id = m.id"
+    );
+    assert_eq!(
+        desugared("MATCH (n) RETURN DISTINCT n.city"),
+        "SELECT DISTINCT json_extract(n.properties, '$.city') AS \"n.city\" FROM nodes AS n"
+    );
+    assert_eq!(desugared("RETURN 1 + 2"), "SELECT 1 + 2 AS \"1 + 2\"");
+
+    let stmt = cypher().verified_stmt("MATCH (n) RETURN m.name");
+    assert_eq!(
+        stmt.desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::UnboundVariable("m".to_string()))
+    );
+}
+
+#[test]
+fn desugar_return_order_by_skip_limit() {
+    assert_eq!(
+        desugared(
+            "MATCH (n:Person) RETURN n.name AS name ORDER BY name DESC, n.age SKIP 10 LIMIT 5"
+        ),
+        "SELECT json_extract(n.properties, '$.name') AS \"name\" FROM nodes AS n \
+         WHERE n.label = 'Person' \
+         ORDER BY \"name\" DESC, json_extract(n.properties, '$.age') LIMIT 5 OFFSET 10"
+    );
+    assert_eq!(
+        desugared("MATCH (n) RETURN n LIMIT 3"),
+        "SELECT n.* FROM nodes AS n LIMIT 3"
+    );
+    assert_eq!(
+        desugared("MATCH (n) RETURN n SKIP 3"),
+        "SELECT n.* FROM nodes AS n OFFSET 3"
+    );
+
+    // T-SQL has `TOP` and `OFFSET ... FETCH` instead of `LIMIT`
+    let mssql = |cypher_query: &str| {
+        let stmt = cypher().verified_stmt(cypher_query);
+        let [desugared] = &stmt
+            .desugar_cypher(&MsSqlDialect {}, &DesugarOptions::default())
+            .unwrap()[..]
+        else {
+            panic!("Expected a single statement");
+        };
+        (
+            desugared.statement.to_string(),
+            desugared.parameters.clone(),
+        )
+    };
+    assert_eq!(
+        mssql("MATCH (n:Person) RETURN n.name AS name ORDER BY name DESC SKIP 10 LIMIT 5").0,
+        "SELECT JSON_VALUE(n.properties, '$.name') AS [name] FROM nodes AS n \
+         WHERE n.label = 'Person' \
+         ORDER BY [name] DESC OFFSET 10 ROWS FETCH FIRST 5 ROWS ONLY"
+    );
+    assert_eq!(
+        mssql("MATCH (n) RETURN DISTINCT n LIMIT 3").0,
+        "SELECT DISTINCT TOP 3 n.* FROM nodes AS n"
+    );
+    assert_eq!(
+        mssql("MATCH (n) RETURN n LIMIT $limit"),
+        (
+            "SELECT TOP (@limit) n.* FROM nodes AS n".to_string(),
+            vec!["limit".to_string()]
+        )
+    );
+    // `OFFSET` needs an `ORDER BY`, which need not order anything
+    assert_eq!(
+        mssql("MATCH (n) RETURN n SKIP $skip"),
+        (
+            "SELECT n.* FROM nodes AS n ORDER BY (SELECT NULL) OFFSET @skip ROWS".to_string(),
+            vec!["skip".to_string()]
+        )
+    );
+
+    let stmt = cypher().verified_stmt("MATCH (n) RETURN n ORDER BY m.name");
+    assert_eq!(
+        stmt.desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::UnboundVariable("m".to_string()))
+    );
+}
+
+#[test]
+fn desugar_aggregations() {
+    assert_eq!(
+        desugared(
+            "MATCH (n:Person) RETURN n.city AS city, count(*) AS people ORDER BY people DESC"
+        ),
+        "SELECT json_extract(n.properties, '$.city') AS city, count(*) AS people \
+         FROM nodes AS n WHERE n.label = 'Person' \
+         GROUP BY json_extract(n.properties, '$.city') ORDER BY people DESC"
+    );
+    assert_eq!(
+        desugared("MATCH (n)-->(m) RETURN n, collect(m.name), count(DISTINCT m) + 1"),
+        "SELECT n.*, json_group_array(json_extract(m.properties, '$.name')) AS \"collect(m.name)\", \
+         count(DISTINCT m.id) + 1 AS \"count(DISTINCT m) + 1\" \
+         FROM nodes AS n \
+         JOIN edges AS _r1 ON _r1.src_id = n.id \
+         JOIN nodes AS m ON _r1.dst_id = m.id \
+         GROUP BY n.id, n.label, n.properties"
+    );
+    assert_eq!(
+        desugared("MATCH (n) RETURN sum(n.age), avg(n.age), min(n.age), max(n.age)"),
+        "SELECT sum(json_extract(n.properties, '$.age')) AS \"sum(n.age)\", \
+         avg(json_extract(n.properties, '$.age')) AS \"avg(n.age)\", \
+         min(json_extract(n.properties, '$.age')) AS \"min(n.age)\", \
+         max(json_extract(n.properties, '$.age')) AS \"max(n.age)\" \
+         FROM nodes AS n"
+    );
+
+    let stmt = cypher().verified_stmt("MATCH (n) RETURN n.city, collect(n.name)");
+    let options = DesugarOptions::new().with_collect_function("array_agg");
+    assert_eq!(
+        stmt.desugar_cypher_to_sql_with_options(&GenericDialect {}, &options)
+            .unwrap()[0]
+            .to_string(),
+        "SELECT json_extract(n.properties, '$.city') AS \"n.city\", \
+         array_agg(json_extract(n.properties, '$.name')) AS \"collect(n.name)\" \
+         FROM nodes AS n GROUP BY json_extract(n.properties, '$.city')"
+    );
+}
+
+#[test]
+fn desugar_with_clause() {
+    assert_eq!(
+        desugared("MATCH (a) WITH a, count(*) AS c WHERE c > 2 MATCH (a)-->(b) RETURN b"),
+        "WITH _with1 AS (\
+         SELECT a.id AS a, count(*) AS c FROM nodes AS a GROUP BY a.id, a.label, a.properties\
+         ) \
+         SELECT b.* FROM _with1 \
+         JOIN nodes AS a ON a.id = _with1.a \
+         JOIN edges AS _r2 ON _r2.src_id = a.id \
+         JOIN nodes AS b ON _r2.dst_id = b.id \
+         WHERE _with1.c > 2"
+    );
+    assert_eq!(
+        desugared("MATCH (a)-->(b) WITH b.name AS name ORDER BY name LIMIT 3 RETURN name"),
+        "WITH _with2 AS (\
+         SELECT json_extract(b.properties, '$.name') AS \"name\" FROM nodes AS a \
+         JOIN edges AS _r1 ON _r1.src_id = a.id \
+         JOIN nodes AS b ON _r1.dst_id = b.id \
+         ORDER BY \"name\" LIMIT 3\
+         ) \
+         SELECT _with2.\"name\" AS \"name\" FROM _with2"
+    );
+
+    // only the variables projected by WITH are visible after it
+    let stmt = cypher().verified_stmt("MATCH (a)-->(b) WITH b RETURN a");
+    assert_eq!(
+        stmt.desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::UnboundVariable("a".to_string()))
+    );
+}
+
+#[test]
+fn desugar_optional_match() {
+    assert_eq!(
+        desugared("MATCH (a:Person) OPTIONAL MATCH (a)-[:OWNS]->(c:Car) RETURN a.name, c.name"),
+        "SELECT json_extract(a.properties, '$.name') AS \"a.name\", \
+         json_extract(c.properties, '$.name') AS \"c.name\" \
+         FROM nodes AS a \
+         LEFT OUTER JOIN (edges AS _r1 JOIN nodes AS c ON _r1.dst_id = c.id) \
+         ON _r1.src_id = a.id AND _r1.type = 'OWNS' AND c.label = 'Car' \
+         WHERE a.label = 'Person'"
+    );
+    assert_eq!(
+        desugared("MATCH (a) OPTIONAL MATCH (a)-->(b) WHERE b.age > 3 OR b.x = 1 RETURN b"),
+        "SELECT b.* FROM nodes AS a \
+         LEFT OUTER JOIN (edges AS _r1 JOIN nodes AS b ON _r1.dst_id = b.id) \
+         ON _r1.src_id = a.id AND (json_extract(b.properties, '$.age') > 3 \
+         OR json_extract(b.properties, '$.x') = 1)"
+    );
+    assert_eq!(
+        desugared("MATCH (a), (b) OPTIONAL MATCH (a)-[r]->(b) RETURN r"),
+        "SELECT r.* FROM nodes AS a CROSS JOIN nodes AS b \
+         LEFT OUTER JOIN edges AS r ON r.src_id = a.id AND r.dst_id = b.id"
+    );
+
+    let stmt = cypher().verified_stmt("OPTIONAL MATCH (a) RETURN a");
+    assert!(matches!(
+        stmt.desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn desugar_create_and_delete() {
+    assert_eq!(
+        desugared("CREATE (b:Bug {name: 'Ant', legs: 6})"),
+        r#"INSERT INTO nodes (label, properties) VALUES ('Bug', '{"name":"Ant","legs":6}')"#
+    );
+    assert_eq!(
+        desugared("MATCH (a:Bug {name: 'Ant'}), (b:Bug {name: 'Bee'}) CREATE (a)-[:EATS]->(b)"),
+        "INSERT INTO edges (src_id, dst_id, type, properties) \
+         SELECT a.id, b.id, 'EATS', '{}' FROM nodes AS a CROSS JOIN nodes AS b \
+         WHERE a.label = 'Bug' AND json_extract(a.properties, '$.name') = 'Ant' \
+         AND b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Bee'"
+    );
+    assert_eq!(
+        desugared("MATCH (a:Bug), (b:Bug) WHERE a.name = 'Ant' AND b.legs > 4 CREATE (a)-[:EATS]->(b)"),
+        "INSERT INTO edges (src_id, dst_id, type, properties) \
+         SELECT a.id, b.id, 'EATS', '{}' FROM nodes AS a CROSS JOIN nodes AS b \
+         WHERE a.label = 'Bug' AND b.label = 'Bug' \
+         AND json_extract(a.properties, '$.name') = 'Ant' AND json_extract(b.properties, '$.legs') > 4"
+    );
+    // relationship properties are stored in the JSON column of the edge
+    assert_eq!(
+        desugared("CREATE (a:Bug {name: 'Ant'})-[:EATS {weight: 0.5, tags: ['x'], ok: true}]->(b:Bug {name: 'Bee'})"),
+        "INSERT INTO nodes (label, properties) VALUES ('Bug', '{\"name\":\"Ant\"}') RETURNING id AS a_id; \
+         INSERT INTO nodes (label, properties) VALUES ('Bug', '{\"name\":\"Bee\"}') RETURNING id AS b_id; \
+         INSERT INTO edges (src_id, dst_id, type, properties) \
+         VALUES (?, ?, 'EATS', '{\"weight\":0.5,\"tags\":[\"x\"],\"ok\":true}')"
+    );
+    assert_eq!(
+        desugared("MATCH (a:Bug {name: 'Ant'}), (b:Bug {name: 'Bee'}) CREATE (a)-[:EATS {since: 2020, meal: {size: 'small'}}]->(b)"),
+        "INSERT INTO edges (src_id, dst_id, type, properties) \
+         SELECT a.id, b.id, 'EATS', '{\"since\":2020,\"meal\":{\"size\":\"small\"}}' FROM nodes AS a CROSS JOIN nodes AS b \
+         WHERE a.label = 'Bug' AND json_extract(a.properties, '$.name') = 'Ant' \
+         AND b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Bee'"
+    );
+    assert_eq!(
+        desugared("MATCH (b:Bug {name: 'Moth'}) DELETE b"),
+        "DELETE FROM nodes WHERE id IN (SELECT b.id FROM nodes AS b WHERE b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Moth') \
+         AND NOT EXISTS (SELECT 1 FROM edges WHERE edges.src_id = nodes.id OR edges.dst_id = nodes.id)"
+    );
+    assert_eq!(
+        desugared("MATCH ()-[r:EATS]->() DELETE r"),
+        "DELETE FROM edges WHERE id IN (\
+         SELECT r.id FROM nodes AS _n1 \
+         JOIN edges AS r ON r.src_id = _n1.id \
+         JOIN nodes AS _n2 ON r.dst_id = _n2.id \
+         WHERE r.type = 'EATS')"
+    );
+    // the ids are selected before deleting, relationships first
+    assert_eq!(
+        desugared("MATCH (n:Bug)-[r]->() DELETE n, r"),
+        "CREATE TEMPORARY TABLE _deleted AS SELECT r.id AS r, n.id AS n FROM nodes AS n \
+         JOIN edges AS r ON r.src_id = n.id \
+         JOIN nodes AS _n1 ON r.dst_id = _n1.id \
+         WHERE n.label = 'Bug'; \
+         DELETE FROM edges WHERE id IN (SELECT r FROM _deleted); \
+         DELETE FROM nodes WHERE id IN (SELECT n FROM _deleted) \
+         AND NOT EXISTS (SELECT 1 FROM edges WHERE edges.src_id = nodes.id OR edges.dst_id = nodes.id); \
+         DROP TABLE _deleted"
+    );
+
+    let stmt = cypher().verified_stmt("MATCH (n) DELETE m");
+    assert_eq!(
+        stmt.desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::UnboundVariable("m".to_string()))
+    );
+}
+
+#[test]
+fn desugar_create_patterns() {
+    let desugared_for = |sql: &str, dialect: &dyn Dialect| {
+        cypher()
+            .verified_stmt(sql)
+            .desugar_cypher(dialect, &DesugarOptions::default())
+            .map(|statements| {
+                statements
+                    .into_iter()
+                    .map(
+                        |DesugaredStatement {
+                             statement,
+                             parameters,
+                         }| { (statement.to_string(), parameters) },
+                    )
+                    .collect::<Vec<_>>()
+            })
+    };
+    let strings = |names: &[&str]| names.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    // the ids returned by the node inserts are parameters of the edge inserts
+    let sql = "CREATE (a:Bug {name: 'Ant'})-[:EATS]->(b:Leaf {kind: 'oak'}), (c:Bug)";
+    assert_eq!(
+        desugared_for(sql, &GenericDialect {}),
+        Ok(vec![
+            (
+                r#"INSERT INTO nodes (label, properties) VALUES ('Bug', '{"name":"Ant"}') RETURNING id AS a_id"#
+                    .to_string(),
+                vec![]
+            ),
+            (
+                r#"INSERT INTO nodes (label, properties) VALUES ('Leaf', '{"kind":"oak"}') RETURNING id AS b_id"#
+                    .to_string(),
+                vec![]
+            ),
+            ("INSERT INTO nodes (label) VALUES ('Bug')".to_string(), vec![]),
+            (
+                "INSERT INTO edges (src_id, dst_id, type, properties) VALUES (?, ?, 'EATS', '{}')"
+                    .to_string(),
+                strings(&["a_id", "b_id"])
+            ),
+        ])
+    );
+    // PostgreSQL chains the inserts in CTEs
+    assert_eq!(
+        desugared_for(sql, &PostgreSqlDialect {}),
+        Ok(vec![(
+            "WITH a AS (INSERT INTO nodes (label, properties) VALUES ('Bug', '{\"name\":\"Ant\"}') RETURNING id), \
+             b AS (INSERT INTO nodes (label, properties) VALUES ('Leaf', '{\"kind\":\"oak\"}') RETURNING id), \
+             c AS (INSERT INTO nodes (label) VALUES ('Bug')) \
+             INSERT INTO edges (src_id, dst_id, type, properties) \
+             VALUES ((SELECT id FROM a), (SELECT id FROM b), 'EATS', '{}')"
+                .to_string(),
+            vec![]
+        )])
+    );
+
+    // a variable occurring again refers to the same node
+    let sql = "CREATE (a:Bug {name: $name}), (a)-[:EATS {times: $times}]->(:Leaf)<-[:EATS]-(a)";
+    assert_eq!(
+        desugared_for(sql, &GenericDialect {}),
+        Ok(vec![
+            (
+                "INSERT INTO nodes (label, properties) VALUES ('Bug', json_object('name', ?)) RETURNING id AS a_id"
+                    .to_string(),
+                strings(&["name"])
+            ),
+            (
+                "INSERT INTO nodes (label) VALUES ('Leaf') RETURNING id AS _n1_id".to_string(),
+                vec![]
+            ),
+            (
+                "INSERT INTO edges (src_id, dst_id, type, properties) VALUES (?, ?, 'EATS', json_object('times', ?))"
+                    .to_string(),
+                strings(&["a_id", "_n1_id", "times"])
+            ),
+            (
+                "INSERT INTO edges (src_id, dst_id, type, properties) VALUES (?, ?, 'EATS', '{}')"
+                    .to_string(),
+                strings(&["a_id", "_n1_id"])
+            ),
+        ])
+    );
+    assert_eq!(
+        desugared_for(sql, &PostgreSqlDialect {}),
+        Ok(vec![(
+            "WITH a AS (INSERT INTO nodes (label, properties) VALUES ('Bug', json_build_object('name', $1)) RETURNING id), \
+             _n1 AS (INSERT INTO nodes (label) VALUES ('Leaf') RETURNING id), \
+             _r2 AS (INSERT INTO edges (src_id, dst_id, type, properties) \
+             VALUES ((SELECT id FROM a), (SELECT id FROM _n1), 'EATS', json_build_object('times', $2))) \
+             INSERT INTO edges (src_id, dst_id, type, properties) \
+             VALUES ((SELECT id FROM a), (SELECT id FROM _n1), 'EATS', '{}')"
+                .to_string(),
+            strings(&["name", "times"])
+        )])
+    );
+
+    // nodes without relationships need no ids
+    assert_eq!(
+        desugared("CREATE (a:Bug), (b)"),
+        "INSERT INTO nodes (label) VALUES ('Bug'); INSERT INTO nodes DEFAULT VALUES"
+    );
+    assert_eq!(
+        desugared_for("CREATE (a:Bug), (b)", &MySqlDialect {}),
+        Ok(vec![
+            (
+                "INSERT INTO nodes (label) VALUES ('Bug')".to_string(),
+                vec![]
+            ),
+            ("INSERT INTO nodes VALUES ()".to_string(), vec![]),
+        ])
+    );
+
+    // MySQL selects the id of each connected node after inserting it
+    let sql = "CREATE (a:Bug)-[:EATS]->(b:Leaf)";
+    assert_eq!(
+        desugared_for(sql, &MySqlDialect {}),
+        Ok(vec![
+            (
+                "INSERT INTO nodes (label) VALUES ('Bug')".to_string(),
+                vec![]
+            ),
+            ("SELECT LAST_INSERT_ID() AS a_id".to_string(), vec![]),
+            (
+                "INSERT INTO nodes (label) VALUES ('Leaf')".to_string(),
+                vec![]
+            ),
+            ("SELECT LAST_INSERT_ID() AS b_id".to_string(), vec![]),
+            (
+                "INSERT INTO edges (src_id, dst_id, type, properties) VALUES (?, ?, 'EATS', '{}')"
+                    .to_string(),
+                strings(&["a_id", "b_id"])
+            ),
+        ])
+    );
+    // and MSSQL outputs it from the insert
+    assert_eq!(
+        desugared_for(sql, &MsSqlDialect {}),
+        Ok(vec![
+            (
+                "INSERT INTO nodes (label) OUTPUT INSERTED.id AS a_id VALUES ('Bug')".to_string(),
+                vec![]
+            ),
+            (
+                "INSERT INTO nodes (label) OUTPUT INSERTED.id AS b_id VALUES ('Leaf')".to_string(),
+                vec![]
+            ),
+            (
+                "INSERT INTO edges (src_id, dst_id, type, properties) \
+                 VALUES (@a_id, @b_id, 'EATS', '{}')"
+                    .to_string(),
+                strings(&["a_id", "b_id"])
+            ),
+        ])
+    );
+    // Snowflake and BigQuery store JSON values, which Snowflake only
+    // accepts from a query
+    let sql = "CREATE (b:Bug {name: 'Ant', legs: 6})";
+    assert_eq!(
+        desugared_for(sql, &SnowflakeDialect {}),
+        Ok(vec![(
+            r#"INSERT INTO nodes (label, properties) SELECT 'Bug', PARSE_JSON('{"name":"Ant","legs":6}')"#
+                .to_string(),
+            vec![]
+        )])
+    );
+    assert_eq!(
+        desugared_for(sql, &BigQueryDialect {}),
+        Ok(vec![(
+            r#"INSERT INTO nodes (label, properties) VALUES ('Bug', JSON '{"name":"Ant","legs":6}')"#
+                .to_string(),
+            vec![]
+        )])
+    );
+    // and cannot return generated ids
+    let sql = "CREATE (a:Bug)-[:EATS]->(b:Leaf)";
+    for dialect in [&SnowflakeDialect {} as &dyn Dialect, &BigQueryDialect {}] {
+        assert!(matches!(
+            desugared_for(sql, dialect),
+            Err(DesugarError::Unsupported(_))
+        ));
+    }
+    assert!(matches!(
+        desugared_for("CREATE (a:Bug), (a:Leaf)", &GenericDialect {}),
+        Err(DesugarError::Unsupported(_))
+    ));
+    assert!(matches!(
+        desugared_for("CREATE (a)-[:EATS]->(b {id: $a_id})", &GenericDialect {}),
+        Err(DesugarError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn desugar_detach_delete() {
+    // the relationships of the node are deleted first
+    assert_eq!(
+        desugared("MATCH (b:Bug {name: 'Moth'}) DETACH DELETE b"),
+        "DELETE FROM edges WHERE src_id IN (\
+         SELECT b.id FROM nodes AS b WHERE b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Moth'\
+         ) OR dst_id IN (\
+         SELECT b.id FROM nodes AS b WHERE b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Moth'\
+         ); \
+         DELETE FROM nodes WHERE id IN (\
+         SELECT b.id FROM nodes AS b WHERE b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Moth')"
+    );
+    // a relationship has nothing to detach
+    assert_eq!(
+        desugared("MATCH ()-[r:EATS]->() DETACH DELETE r"),
+        desugared("MATCH ()-[r:EATS]->() DELETE r")
+    );
+    // deleting the relationships would change what the MATCH selects, so
+    // the ids are selected before
+    assert_eq!(
+        desugared("MATCH (a)-[:EATS]->(b) DETACH DELETE b"),
+        "CREATE TEMPORARY TABLE _deleted AS SELECT b.id AS b FROM nodes AS a \
+         JOIN edges AS _r1 ON _r1.src_id = a.id \
+         JOIN nodes AS b ON _r1.dst_id = b.id \
+         WHERE _r1.type = 'EATS'; \
+         DELETE FROM edges WHERE src_id IN (SELECT b FROM _deleted) OR dst_id IN (SELECT b FROM _deleted); \
+         DELETE FROM nodes WHERE id IN (SELECT b FROM _deleted); \
+         DROP TABLE _deleted"
+    );
+
+    // with every table that may reference the node
+    let schema = GraphSchema::new()
+        .with_relationship_table("KNOWS", EdgeTable::new("friendships", "a", "b"));
+    let options = DesugarOptions::new().with_schema(schema);
+    let stmt = cypher().verified_stmt("MATCH (n {id: $id}) DETACH DELETE n");
+    let desugared = stmt
+        .desugar_cypher(&PostgreSqlDialect {}, &options)
+        .unwrap();
+    assert_eq!(
+        desugared
+            .iter()
+            .map(|d| (d.statement.to_string(), d.parameters.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "DELETE FROM edges WHERE \
+                 src_id IN (SELECT n.id FROM nodes AS n WHERE n.properties ->> 'id' = $1) \
+                 OR dst_id IN (SELECT n.id FROM nodes AS n WHERE n.properties ->> 'id' = $1)"
+                    .to_string(),
+                vec!["id".to_string()]
+            ),
+            (
+                "DELETE FROM friendships WHERE \
+                 a IN (SELECT n.id FROM nodes AS n WHERE n.properties ->> 'id' = $1) \
+                 OR b IN (SELECT n.id FROM nodes AS n WHERE n.properties ->> 'id' = $1)"
+                    .to_string(),
+                vec!["id".to_string()]
+            ),
+            (
+                "DELETE FROM nodes WHERE \
+                 id IN (SELECT n.id FROM nodes AS n WHERE n.properties ->> 'id' = $1)"
+                    .to_string(),
+                vec!["id".to_string()]
+            ),
+        ]
+    );
+
+    let stmt = cypher().verified_stmt("MATCH (a)-[r]->(b {id: $id}) DETACH DELETE b, r");
+    assert_eq!(
+        stmt.desugar_cypher_to_sql(&MsSqlDialect {})
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        [
+            "SELECT r.id AS r, b.id AS b INTO #_deleted FROM nodes AS a \
+             JOIN edges AS r ON r.src_id = a.id \
+             JOIN nodes AS b ON r.dst_id = b.id \
+             WHERE JSON_VALUE(b.properties, '$.id') = @id",
+            "DELETE FROM edges WHERE id IN (SELECT r FROM #_deleted)",
+            "DELETE FROM edges WHERE src_id IN (SELECT b FROM #_deleted) \
+             OR dst_id IN (SELECT b FROM #_deleted)",
+            "DELETE FROM nodes WHERE id IN (SELECT b FROM #_deleted)",
+            "DROP TABLE #_deleted",
+        ]
+    );
+
+    // MySQL cannot select from the table it deletes from in a subquery,
+    // nor refer to a temporary table twice in a query
+    let mysql = |cypher_query: &str| {
+        cypher()
+            .verified_stmt(cypher_query)
+            .desugar_cypher_to_sql(&MySqlDialect {})
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        mysql("MATCH (b:Bug {name: 'Moth'}) DETACH DELETE b"),
+        [
+            "DELETE FROM edges WHERE src_id IN (SELECT b FROM (\
+             SELECT b.id AS b FROM nodes AS b WHERE b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Moth'\
+             ) AS _ids) OR dst_id IN (SELECT b FROM (\
+             SELECT b.id AS b FROM nodes AS b WHERE b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Moth'\
+             ) AS _ids)",
+            "DELETE FROM nodes WHERE id IN (SELECT b FROM (\
+             SELECT b.id AS b FROM nodes AS b WHERE b.label = 'Bug' AND json_extract(b.properties, '$.name') = 'Moth'\
+             ) AS _ids)",
+        ]
+    );
+    assert_eq!(
+        mysql("MATCH ()-[r:EATS]->() DELETE r"),
+        ["DELETE FROM edges WHERE id IN (SELECT r FROM (\
+          SELECT r.id AS r FROM nodes AS _n1 JOIN edges AS r ON r.src_id = _n1.id \
+          JOIN nodes AS _n2 ON r.dst_id = _n2.id WHERE r.type = 'EATS'\
+          ) AS _ids)"]
+    );
+    assert_eq!(
+        mysql("MATCH (a)-[:EATS]->(b) DETACH DELETE b"),
+        [
+            "CREATE TEMPORARY TABLE _deleted AS SELECT b.id AS b FROM nodes AS a \
+             JOIN edges AS _r1 ON _r1.src_id = a.id \
+             JOIN nodes AS b ON _r1.dst_id = b.id \
+             WHERE _r1.type = 'EATS'",
+            "DELETE FROM edges WHERE EXISTS (SELECT 1 FROM _deleted WHERE b IN (src_id, dst_id))",
+            "DELETE FROM nodes WHERE id IN (SELECT b FROM _deleted)",
+            "DROP TABLE _deleted",
+        ]
+    );
+}
+
+#[test]
+fn desugar_set_and_remove() {
+    assert_eq!(
+        desugared(
+            "MATCH (n:Bug {name: 'Ant'}) SET n.color = 'red', n.tags = ['a'], n.age = NULL"
+        ),
+        "UPDATE nodes SET properties = \
+         json_remove(json_set(json_set(properties, '$.color', 'red'), '$.tags', json('[\"a\"]')), '$.age') \
+         WHERE id IN (SELECT n.id FROM nodes AS n WHERE n.label = 'Bug' AND json_extract(n.properties, '$.name') = 'Ant')"
+    );
+    // the label column holds a single label, which adding one would replace
+    assert!(matches!(
+        cypher()
+            .verified_stmt("MATCH (n:Bug) SET n:Insect")
+            .desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::Unsupported(_))
+    ));
+    assert_eq!(
+        desugared("MATCH (n) REMOVE n.color, n:Bug"),
+        "UPDATE nodes SET label = CASE WHEN label = 'Bug' THEN NULL ELSE label END, \
+         properties = json_remove(properties, '$.color') \
+         WHERE id IN (SELECT n.id FROM nodes AS n)"
+    );
+    // new values can refer to the properties of the updated row
+    assert_eq!(
+        desugared("MATCH ()-[r:EATS]->() SET r.times = r.times + 1"),
+        "UPDATE edges SET properties = json_set(properties, '$.times', json_extract(edges.properties, '$.times') + 1) \
+         WHERE id IN (SELECT r.id FROM nodes AS _n1 JOIN edges AS r ON r.src_id = _n1.id \
+         JOIN nodes AS _n2 ON r.dst_id = _n2.id WHERE r.type = 'EATS')"
+    );
+    assert_eq!(
+        desugared("MATCH (n) SET n = {name: 'Moth', legs: NULL}"),
+        r#"UPDATE nodes SET properties = '{"name":"Moth"}' WHERE id IN (SELECT n.id FROM nodes AS n)"#
+    );
+    assert_eq!(
+        desugared("MATCH (n) SET n += {name: 'Moth', legs: NULL}"),
+        "UPDATE nodes SET properties = json_remove(json_set(properties, '$.name', 'Moth'), '$.legs') \
+         WHERE id IN (SELECT n.id FROM nodes AS n)"
+    );
+
+    // the JSON functions of the target dialect
+    let stmt = cypher().verified_stmt("MATCH (n) SET n.ok = true REMOVE n.color");
+    let desugared_for = |dialect: &dyn Dialect| {
+        let sql = stmt.desugar_cypher_to_sql(dialect).unwrap()[0].to_string();
+        assert_eq!(
+            Parser::parse_sql(dialect, &sql).unwrap()[0].to_string(),
+            sql
+        );
+        sql
+    };
+    assert_eq!(
+        desugared_for(&PostgreSqlDialect {}),
+        "UPDATE nodes SET properties = (properties || jsonb_build_object('ok', CAST('true' AS JSONB))) - 'color' \
+         WHERE id IN (SELECT n.id FROM nodes AS n)"
+    );
+    assert_eq!(
+        desugared_for(&SnowflakeDialect {}),
+        "UPDATE nodes SET properties = OBJECT_DELETE(OBJECT_INSERT(properties, 'ok', PARSE_JSON('true'), true), 'color') \
+         WHERE id IN (SELECT n.id FROM nodes AS n)"
+    );
+    assert_eq!(
+        desugared_for(&MsSqlDialect {}),
+        "UPDATE nodes SET properties = JSON_MODIFY(JSON_MODIFY(properties, '$.ok', CAST(1 AS BIT)), '$.color', NULL) \
+         WHERE id IN (SELECT n.id FROM nodes AS n)"
+    );
+    // MySQL cannot select from the table it updates in a subquery
+    assert_eq!(
+        desugared_for(&MySqlDialect {}),
+        "UPDATE nodes SET properties = json_remove(json_set(properties, '$.ok', CAST('true' AS JSON)), '$.color') \
+         WHERE id IN (SELECT `id` FROM (SELECT n.id AS `id` FROM nodes AS n) AS _ids)"
+    );
+
+    // properties in columns of their own are assigned directly
+    let schema = GraphSchema::new().with_label_table(
+        "Person",
+        NodeTable::new("people")
+            .with_json_column("details")
+            .with_property("name", PropertyColumn::Column("full_name".to_string())),
+    );
+    let options = DesugarOptions::new().with_schema(schema);
+    let desugared = |sql: &str| {
+        cypher()
+            .verified_stmt(sql)
+            .desugar_cypher_to_sql_with_options(&GenericDialect {}, &options)
+            .map(|statements| {
+                statements
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            })
+    };
+    assert_eq!(
+        desugared("MATCH (p:Person) SET p = {name: 'Ann', age: 42}, p:Person"),
+        Ok(vec![
+            r#"UPDATE people SET full_name = 'Ann', details = '{"age":42}' WHERE id IN (SELECT p.id FROM people AS p)"#
+                .to_string()
+        ])
+    );
+    assert_eq!(
+        desugared("MATCH (p:Person) REMOVE p.name"),
+        Ok(vec![
+            "UPDATE people SET full_name = NULL WHERE id IN (SELECT p.id FROM people AS p)"
+                .to_string()
+        ])
+    );
+    assert!(matches!(
+        desugared("MATCH (p:Person) REMOVE p:Person"),
+        Err(DesugarError::Unsupported(_))
+    ));
+    assert!(matches!(
+        desugared("MATCH (p:Person), (q:Person) SET p.age = q.age"),
+        Err(DesugarError::UnboundVariable(_))
+    ));
+    assert!(matches!(
+        desugared("MATCH (p:Person), (q:Person) SET p.age = 1, q.age = 2"),
+        Err(DesugarError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn desugar_merge() {
+    // INSERT upserts find an existing node by the unique key of its table
+    let schema = GraphSchema::new().with_label_table(
+        "Person",
+        NodeTable::new("people")
+            .with_json_column("details")
+            .with_property("email", PropertyColumn::Column("email".to_string())),
+    );
+    let options = DesugarOptions::new().with_schema(schema);
+    let stmt = cypher().verified_stmt(
+        "MERGE (p:Person {email: 'ann@example.com'}) ON CREATE SET p.name = 'Ann' ON MATCH SET p.visits = 2",
+    );
+    let desugared_for = |stmt: &Statement, dialect: &dyn Dialect, options: &DesugarOptions| {
+        let sql = stmt
+            .desugar_cypher_to_sql_with_options(dialect, options)
+            .map(|statements| statements[0].to_string())?;
+        assert_eq!(
+            Parser::parse_sql(dialect, &sql).unwrap()[0].to_string(),
+            sql
+        );
+        Ok::<_, DesugarError>(sql)
+    };
+    assert_eq!(
+        desugared_for(&stmt, &SQLiteDialect {}, &options).unwrap(),
+        r#"INSERT INTO people (email, details) VALUES ('ann@example.com', '{"name":"Ann"}') "#
+            .to_string()
+            + "ON CONFLICT(email) DO UPDATE SET details = json_set(people.details, '$.visits', 2)"
+    );
+    assert_eq!(
+        desugared_for(&stmt, &PostgreSqlDialect {}, &options).unwrap(),
+        r#"INSERT INTO people (email, details) VALUES ('ann@example.com', '{"name":"Ann"}') "#.to_string()
+            + "ON CONFLICT(email) DO UPDATE SET details = people.details || jsonb_build_object('visits', 2)"
+    );
+    assert_eq!(
+        desugared_for(&stmt, &MySqlDialect {}, &options).unwrap(),
+        r#"INSERT INTO people (email, details) VALUES ('ann@example.com', '{"name":"Ann"}') "#
+            .to_string()
+            + "ON DUPLICATE KEY UPDATE details = json_set(people.details, '$.visits', 2)"
+    );
+    let stmt = cypher().verified_stmt("MERGE (p:Person {email: 'ann@example.com'})");
+    assert_eq!(
+        desugared_for(&stmt, &SQLiteDialect {}, &options).unwrap(),
+        "INSERT INTO people (email) VALUES ('ann@example.com') ON CONFLICT(email) DO NOTHING"
+    );
+    assert_eq!(
+        desugared_for(&stmt, &MySqlDialect {}, &options).unwrap(),
+        "INSERT INTO people (email) VALUES ('ann@example.com') ON DUPLICATE KEY UPDATE email = email"
+    );
+    // properties stored in the JSON column cannot be part of a unique key, so
+    // the matching nodes are updated, then the node is inserted unless one matches
+    let all_desugared_for = |sql: &str, dialect: &dyn Dialect| {
+        cypher()
+            .verified_stmt(sql)
+            .desugar_cypher_to_sql(dialect)
+            .map(|statements| {
+                statements
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            })
+    };
+    let sql = "MERGE (b:Bug {name: 'Ant'}) ON CREATE SET b.legs = 6 ON MATCH SET b.seen = 2";
+    assert_eq!(
+        all_desugared_for(sql, &SQLiteDialect {}).unwrap(),
+        "UPDATE nodes SET properties = json_set(nodes.properties, '$.seen', 2) \
+         WHERE nodes.label = 'Bug' AND json_extract(nodes.properties, '$.name') = 'Ant'; \
+         INSERT INTO nodes (label, properties) SELECT 'Bug', '{\"name\":\"Ant\",\"legs\":6}' \
+         WHERE NOT EXISTS (SELECT 1 FROM nodes \
+         WHERE nodes.label = 'Bug' AND json_extract(nodes.properties, '$.name') = 'Ant')"
+    );
+    assert_eq!(
+        all_desugared_for("MERGE (b:Bug {name: 'Ant'})", &PostgreSqlDialect {}).unwrap(),
+        "INSERT INTO nodes (label, properties) SELECT 'Bug', '{\"name\":\"Ant\"}' \
+         WHERE NOT EXISTS (SELECT 1 FROM nodes \
+         WHERE nodes.label = 'Bug' AND nodes.properties ->> 'name' = 'Ant')"
+    );
+    // updating first, a node whose pattern property changed would not match
+    assert!(matches!(
+        all_desugared_for(
+            "MERGE (b:Bug {name: 'Ant'}) ON MATCH SET b.name = 'Bee'",
+            &MySqlDialect {}
+        ),
+        Err(DesugarError::Unsupported(_))
+    ));
+
+    // MERGE statements match on the label and properties of the pattern
+    let stmt = cypher().verified_stmt(
+        "MERGE (b:Bug {name: 'Ant'}) ON CREATE SET b.legs = 6 ON MATCH SET b.seen = true",
+    );
+    assert_eq!(
+        desugared_for(&stmt, &SnowflakeDialect {}, &DesugarOptions::default()).unwrap(),
+        "MERGE INTO nodes USING (SELECT 1 AS one) AS _merge \
+         ON nodes.label = 'Bug' AND nodes.properties:name = 'Ant' \
+         WHEN MATCHED THEN UPDATE SET properties = OBJECT_INSERT(nodes.properties, 'seen', PARSE_JSON('true'), true) \
+         WHEN NOT MATCHED THEN INSERT (label, properties) VALUES ('Bug', PARSE_JSON('{\"name\":\"Ant\",\"legs\":6}'))"
+    );
+    assert_eq!(
+        desugared_for(&stmt, &MsSqlDialect {}, &DesugarOptions::default()).unwrap(),
+        "MERGE INTO nodes USING (SELECT 1 AS one) AS _merge \
+         ON nodes.label = 'Bug' AND JSON_VALUE(nodes.properties, '$.name') = 'Ant' \
+         WHEN MATCHED THEN UPDATE SET properties = JSON_MODIFY(nodes.properties, '$.seen', CAST(1 AS BIT)) \
+         WHEN NOT MATCHED THEN INSERT (label, properties) VALUES ('Bug', '{\"name\":\"Ant\",\"legs\":6}')"
+    );
+    let stmt = cypher().verified_stmt("MERGE (b:Bug {name: $name}) ON MATCH SET b.legs = $legs");
+    let desugared = stmt
+        .desugar_cypher(&BigQueryDialect {}, &DesugarOptions::default())
+        .unwrap();
+    assert_eq!(
+        desugared[0].statement.to_string(),
+        "MERGE INTO nodes USING (SELECT 1 AS one) AS _merge \
+         ON nodes.label = 'Bug' AND JSON_VALUE(nodes.properties, '$.name') = @name \
+         WHEN MATCHED THEN UPDATE SET properties = JSON_SET(nodes.properties, '$.legs', @legs) \
+         WHEN NOT MATCHED THEN INSERT (label, properties) VALUES ('Bug', JSON_OBJECT('name', @name))"
+    );
+    assert_eq!(desugared[0].parameters, vec!["name", "legs"]);
+
+    assert!(matches!(
+        desugared_for(
+            &cypher().verified_stmt("MERGE (a:Bug {name: 'Ant'})-[:EATS]->(b:Bug {name: 'Moth'})"),
+            &SnowflakeDialect {},
+            &DesugarOptions::default()
+        ),
+        Err(DesugarError::Unsupported(_))
+    ));
+    assert!(matches!(
+        desugared_for(
+            &cypher().verified_stmt("MERGE (b:Bug {name: 'Ant'}) ON CREATE SET c.legs = 6"),
+            &SnowflakeDialect {},
+            &DesugarOptions::default()
+        ),
+        Err(DesugarError::UnboundVariable(_))
+    ));
+    // a created node has a single label
+    for sql in [
+        "MERGE (b:Bug {name: 'Ant'}) ON CREATE SET b:Insect",
+        "MERGE (b:Bug {name: 'Ant'}) ON MATCH SET b:Insect",
+    ] {
+        assert!(matches!(
+            desugared_for(
+                &cypher().verified_stmt(sql),
+                &SnowflakeDialect {},
+                &DesugarOptions::default()
+            ),
+            Err(DesugarError::Unsupported(_))
+        ));
+    }
+}
+
+#[test]
+fn desugar_unwind() {
+    let desugared_for = |cypher_query: &str, dialect: &dyn Dialect| {
+        let desugared = cypher()
+            .verified_stmt(cypher_query)
+            .desugar_cypher(dialect, &DesugarOptions::default())?;
+        let sql = desugared[0].statement.to_string();
+        assert_eq!(
+            Parser::parse_sql(dialect, &sql).unwrap()[0].to_string(),
+            sql
+        );
+        Ok::<_, DesugarError>((sql, desugared[0].parameters.clone()))
+    };
+
+    // a node for each element of the list, whose members are kept as JSON
+    let bulk_load = "UNWIND $rows AS row CREATE (:User {id: row.id, name: row.name})";
+    assert_eq!(
+        desugared_for(bulk_load, &SQLiteDialect {}).unwrap(),
+        (
+            "INSERT INTO nodes (label, properties) SELECT 'User', \
+             json_object('id', json_extract(_n1.value, '$.id'), 'name', json_extract(_n1.value, '$.name')) \
+             FROM json_each(?) AS _n1"
+                .to_string(),
+            vec!["rows".to_string()]
+        )
+    );
+    assert_eq!(
+        desugared_for(bulk_load, &PostgreSqlDialect {}).unwrap().0,
+        "INSERT INTO nodes (label, properties) SELECT 'User', \
+         json_build_object('id', _n1.value -> 'id', 'name', _n1.value -> 'name') \
+         FROM UNNEST(CAST($1 AS JSONB[])) AS _n1 (value)"
+    );
+    assert_eq!(
+        desugared_for(bulk_load, &DuckDbDialect {}).unwrap().0,
+        "INSERT INTO nodes (label, properties) SELECT 'User', \
+         json_object('id', json_extract(_n1.value, '$.id'), 'name', json_extract(_n1.value, '$.name')) \
+         FROM UNNEST(CAST(? AS JSON[])) AS _n1 (value)"
+    );
+    assert_eq!(
+        desugared_for(bulk_load, &SnowflakeDialect {}).unwrap().0,
+        "INSERT INTO nodes (label, properties) SELECT 'User', \
+         OBJECT_CONSTRUCT('id', _n1.value:id, 'name', _n1.value:name) \
+         FROM LATERAL FLATTEN(INPUT => PARSE_JSON(?)) AS _n1"
+    );
+    assert_eq!(
+        desugared_for(bulk_load, &BigQueryDialect {}).unwrap().0,
+        "INSERT INTO nodes (label, properties) SELECT 'User', \
+         JSON_OBJECT('id', JSON_QUERY(_n1, '$.id'), 'name', JSON_QUERY(_n1, '$.name')) \
+         FROM UNNEST(@rows) AS _n1"
+    );
+    assert!(matches!(
+        desugared_for(bulk_load, &MySqlDialect {}),
+        Err(DesugarError::Unsupported(_))
+    ));
+
+    // literal lists, with maps and lists as JSON
+    let query = "UNWIND [1, 2, 3] AS x RETURN x";
+    assert_eq!(
+        desugared_for(query, &PostgreSqlDialect {}).unwrap().0,
+        "SELECT _n1.value AS x FROM UNNEST(ARRAY[1, 2, 3]) AS _n1 (value)"
+    );
+    assert_eq!(
+        desugared_for(query, &SQLiteDialect {}).unwrap().0,
+        "SELECT _n1.value AS x FROM json_each('[1,2,3]') AS _n1"
+    );
+    assert_eq!(
+        desugared_for(query, &BigQueryDialect {}).unwrap().0,
+        "SELECT _n1 AS x FROM UNNEST([1, 2, 3]) AS _n1"
+    );
+    assert_eq!(
+        desugared_for(
+            "UNWIND [{id: 1}, {id: 2}] AS r MATCH (u:User {id: r.id}) WHERE r.id > 1 RETURN u.name",
+            &PostgreSqlDialect {}
+        )
+        .unwrap()
+        .0,
+        "SELECT u.properties ->> 'name' AS \"u.name\" \
+         FROM UNNEST(ARRAY[CAST('{\"id\":1}' AS JSONB), CAST('{\"id\":2}' AS JSONB)]) AS _n1 (value) \
+         CROSS JOIN nodes AS u \
+         WHERE u.label = 'User' AND u.properties ->> 'id' = _n1.value ->> 'id' \
+         AND CAST(_n1.value ->> 'id' AS NUMERIC) > 1"
+    );
+    assert_eq!(
+        desugared_for(
+            "UNWIND [1, 2] AS x WITH x WHERE x > 1 RETURN x",
+            &SQLiteDialect {}
+        )
+        .unwrap()
+        .0,
+        "WITH _with2 AS (SELECT _n1.value AS x FROM json_each('[1,2]') AS _n1) \
+         SELECT _with2.x AS x FROM _with2 WHERE _with2.x > 1"
+    );
+
+    // relationships between the nodes matched for each element
+    assert_eq!(
+        desugared_for(
+            "UNWIND $pairs AS p MATCH (a:User {id: p.a}), (b:User {id: p.b}) \
+             CREATE (a)-[:KNOWS {since: p.since}]->(b)",
+            &SQLiteDialect {}
+        )
+        .unwrap()
+        .0,
+        "INSERT INTO edges (src_id, dst_id, type, properties) \
+         SELECT a.id, b.id, 'KNOWS', json_object('since', json_extract(_n1.value, '$.since')) \
+         FROM json_each(?) AS _n1 CROSS JOIN nodes AS a CROSS JOIN nodes AS b \
+         WHERE a.label = 'User' AND json_extract(a.properties, '$.id') = json_extract(_n1.value, '$.a') \
+         AND b.label = 'User' AND json_extract(b.properties, '$.id') = json_extract(_n1.value, '$.b')"
+    );
+
+    for unsupported in [
+        // the list must be a literal or a parameter
+        "MATCH (n) UNWIND n.tags AS t RETURN t",
+        "UNWIND $rows AS row CREATE (a:User {id: row.a})-[:KNOWS]->(b:User {id: row.b})",
+        "UNWIND $rows AS row MERGE (:User {id: row.id})",
+    ] {
+        assert!(matches!(
+            desugared_for(unsupported, &SQLiteDialect {}),
+            Err(DesugarError::Unsupported(_))
+        ));
+    }
+    assert!(matches!(
+        desugared_for("CREATE (:User {id: row.id})", &SQLiteDialect {}),
+        Err(DesugarError::UnboundVariable(_))
+    ));
+}
+
+#[test]
+fn desugar_union() {
+    assert_eq!(
+        desugared("MATCH (a:Cat) RETURN a.name AS n UNION MATCH (b:Dog) RETURN b.name AS n"),
+        "SELECT json_extract(a.properties, '$.name') AS n FROM nodes AS a WHERE a.label = 'Cat' \
+         UNION SELECT json_extract(b.properties, '$.name') AS n FROM nodes AS b WHERE b.label = 'Dog'"
+    );
+    assert_eq!(
+        desugared("RETURN 1 AS x UNION ALL RETURN 2 AS x UNION ALL RETURN 1 AS x"),
+        "SELECT 1 AS x UNION ALL SELECT 2 AS x UNION ALL SELECT 1 AS x"
+    );
+    // the CTEs of all queries precede the UNION, with distinct names
+    assert_eq!(
+        desugared(
+            "MATCH (a)-->(b) WITH b, count(*) AS c RETURN b.name AS name \
+             UNION MATCH (a)<--(b) WITH b, count(*) AS c RETURN b.name AS name"
+        ),
+        "WITH _with2 AS (SELECT b.id AS b, count(*) AS c FROM nodes AS a \
+         JOIN edges AS _r1 ON _r1.src_id = a.id JOIN nodes AS b ON _r1.dst_id = b.id \
+         GROUP BY b.id, b.label, b.properties), \
+         _with4 AS (SELECT b.id AS b, count(*) AS c FROM nodes AS a \
+         JOIN edges AS _r3 ON _r3.dst_id = a.id JOIN nodes AS b ON _r3.src_id = b.id \
+         GROUP BY b.id, b.label, b.properties) \
+         SELECT json_extract(b.properties, '$.name') AS \"name\" FROM _with2 JOIN nodes AS b ON b.id = _with2.b \
+         UNION SELECT json_extract(b.properties, '$.name') AS \"name\" FROM _with4 JOIN nodes AS b ON b.id = _with4.b"
+    );
+    // ORDER BY and LIMIT apply to their own query
+    assert_eq!(
+        desugared(
+            "MATCH (a:Cat) RETURN a.name AS n ORDER BY n LIMIT 2 UNION ALL MATCH (b:Dog) RETURN b.name AS n"
+        ),
+        "SELECT * FROM (SELECT json_extract(a.properties, '$.name') AS n FROM nodes AS a \
+         WHERE a.label = 'Cat' ORDER BY n LIMIT 2) AS _union1 \
+         UNION ALL SELECT json_extract(b.properties, '$.name') AS n FROM nodes AS b WHERE b.label = 'Dog'"
+    );
+
+    for invalid in [
+        "MATCH (a) RETURN a.name UNION MATCH (b) RETURN b.name",
+        "RETURN 1 AS x, 2 AS y UNION RETURN 1 AS x",
+        "RETURN 1 AS x UNION ALL RETURN 2 AS x UNION RETURN 3 AS x",
+        "MATCH (a) RETURN a UNION MATCH (a) DELETE a",
+    ] {
+        assert!(
+            matches!(
+                cypher()
+                    .verified_stmt(invalid)
+                    .desugar_cypher(&SQLiteDialect {}, &DesugarOptions::default()),
+                Err(DesugarError::InvalidQuery(_))
+            ),
+            "{invalid}"
+        );
+    }
+}
+
+#[test]
+fn desugar_label_expressions() {
+    assert_eq!(
+        desugared("MATCH (n:Cat|Dog) RETURN n"),
+        "SELECT n.* FROM nodes AS n WHERE n.label IN ('Cat', 'Dog')"
+    );
+    assert_eq!(
+        desugared("MATCH (n:Person:!Employee) RETURN n"),
+        "SELECT n.* FROM nodes AS n \
+         WHERE n.label = 'Person' AND (n.label <> 'Employee' OR n.label IS NULL)"
+    );
+    // a node without a label is none of the labels
+    assert_eq!(
+        desugared("MATCH (n:!(Cat|Dog)) RETURN n"),
+        "SELECT n.* FROM nodes AS n WHERE (n.label NOT IN ('Cat', 'Dog') OR n.label IS NULL)"
+    );
+    assert_eq!(
+        desugared("MATCH (n:Cat|(Dog:!Old)) RETURN n"),
+        "SELECT n.* FROM nodes AS n \
+         WHERE (n.label = 'Cat' OR (n.label = 'Dog' AND (n.label <> 'Old' OR n.label IS NULL)))"
+    );
+    assert_eq!(
+        desugared("MATCH (a)-[r:KNOWS|LIKES]->(b) RETURN b"),
+        "SELECT b.* FROM nodes AS a JOIN edges AS r ON r.src_id = a.id \
+         JOIN nodes AS b ON r.dst_id = b.id WHERE r.type IN ('KNOWS', 'LIKES')"
+    );
+
+    // a label stored in a table of its own needs no filter, and all labels of
+    // an expression must be stored in the same table
+    let options = DesugarOptions::new()
+        .with_schema(GraphSchema::new().with_label_table("Person", NodeTable::new("people")));
+    let desugar = |sql: &str| {
+        cypher()
+            .verified_stmt(sql)
+            .desugar_cypher_to_sql_with_options(&GenericDialect {}, &options)
+            .map(|statements| statements[0].to_string())
+    };
+    assert_eq!(
+        desugar("MATCH (n:Person|(Person:Person)) RETURN n"),
+        Ok("SELECT n.* FROM people AS n".to_string())
+    );
+    for unsupported in [
+        "MATCH (n:Person|Robot) RETURN n",
+        // the label column holds a single label
+        "MATCH (n:Robot:Android) RETURN n",
+        "MATCH (n:Robot|(Cat:Dog)) RETURN n",
+        "MATCH (n:!(Cat:Dog)) RETURN n",
+        "CREATE (n:Cat:Dog)",
+        "MERGE (n:Cat|Dog {name: 'Rex'})",
+        "MATCH (a), (b) CREATE (a)-[:KNOWS|LIKES]->(b)",
+    ] {
+        assert!(
+            matches!(desugar(unsupported), Err(DesugarError::Unsupported(_))),
+            "{unsupported}"
+        );
+    }
+}
+
+#[test]
+fn desugar_pattern_predicates() {
+    assert_eq!(
+        desugared("MATCH (a:Person) WHERE (a)-[:FRIEND]->(:Admin) RETURN a.name"),
+        "SELECT json_extract(a.properties, '$.name') AS \"a.name\" FROM nodes AS a \
+         WHERE a.label = 'Person' AND EXISTS (SELECT 1 FROM edges AS _r1 \
+         JOIN nodes AS _n2 ON _r1.dst_id = _n2.id \
+         WHERE _r1.src_id = a.id AND _r1.type = 'FRIEND' AND _n2.label = 'Admin')"
+    );
+    // NOT is an anti-join
+    assert_eq!(
+        desugared("MATCH (a), (b) WHERE NOT (a)-[:FRIEND]-(b) RETURN a, b"),
+        "SELECT a.*, b.* FROM nodes AS a CROSS JOIN nodes AS b \
+         WHERE NOT EXISTS (SELECT 1 FROM edges AS _r1 \
+         WHERE ((_r1.src_id = a.id AND _r1.dst_id = b.id) OR (_r1.dst_id = a.id AND _r1.src_id = b.id)) \
+         AND _r1.type = 'FRIEND')"
+    );
+    assert_eq!(
+        desugared(
+            "MATCH (a) WHERE EXISTS { MATCH (a)-->(b) WHERE b.x = 1 } \
+             AND NOT EXISTS { MATCH (a)<-[:OWNS]-() } RETURN a"
+        ),
+        "SELECT a.* FROM nodes AS a \
+         WHERE EXISTS (SELECT 1 FROM edges AS _r1 JOIN nodes AS b ON _r1.dst_id = b.id \
+         WHERE _r1.src_id = a.id AND json_extract(b.properties, '$.x') = 1) \
+         AND NOT EXISTS (SELECT 1 FROM edges AS _r1 JOIN nodes AS _n2 ON _r1.src_id = _n2.id \
+         WHERE _r1.dst_id = a.id AND _r1.type = 'OWNS')"
+    );
+    assert_eq!(
+        desugared("MATCH (a) RETURN (a)-->() AS has_out"),
+        "SELECT EXISTS (SELECT 1 FROM edges AS _r1 JOIN nodes AS _n2 ON _r1.dst_id = _n2.id \
+         WHERE _r1.src_id = a.id) AS has_out FROM nodes AS a"
+    );
+}
+
+#[test]
+fn desugar_graph_schema() {
+    let schema = GraphSchema::new()
+        .with_label_table(
+            "Person",
+            NodeTable::new("people")
+                .with_property("name", PropertyColumn::Column("full_name".to_string())),
+        )
+        .with_relationship_table(
+            "KNOWS",
+            EdgeTable::new("friendships", "person_a", "person_b")
+                .with_property("since", PropertyColumn::Column("since".to_string())),
+        );
+    let options = DesugarOptions::new().with_schema(schema);
+    let desugared = |sql: &str| {
+        cypher()
+            .verified_stmt(sql)
+            .desugar_cypher_to_sql_with_options(&GenericDialect {}, &options)
+            .map(|statements| statements[0].to_string())
+    };
+
+    assert_eq!(
+        desugared("MATCH (a:Person)-[r:KNOWS]->(b:Person) WHERE r.since > 2000 RETURN a.name"),
+        Ok("SELECT a.full_name AS \"a.name\" FROM people AS a \
+            JOIN friendships AS r ON r.person_a = a.id \
+            JOIN people AS b ON r.person_b = b.id WHERE r.since > 2000"
+            .to_string())
+    );
+    // Labels and types without a table of their own use the default tables
+    assert_eq!(
+        desugared("MATCH (a:Person)-[:LIKES]->(m:Movie) RETURN m.title"),
+        Ok(
+            "SELECT json_extract(m.properties, '$.title') AS \"m.title\" FROM people AS a \
+            JOIN edges AS _r1 ON _r1.src_id = a.id JOIN nodes AS m ON _r1.dst_id = m.id \
+            WHERE _r1.type = 'LIKES' AND m.label = 'Movie'"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        desugared("CREATE (a:Person {name: 'Ann'})"),
+        Ok("INSERT INTO people (full_name) VALUES ('Ann')".to_string())
+    );
+    assert_eq!(
+        desugared(
+            "MATCH (a:Person {name: 'Ann'}), (b:Person {name: 'Bob'}) \
+             CREATE (a)-[:KNOWS {since: 2020}]->(b)"
+        ),
+        Ok(
+            "INSERT INTO friendships (person_a, person_b, since) SELECT a.id, b.id, 2020 \
+            FROM people AS a CROSS JOIN people AS b \
+            WHERE a.full_name = 'Ann' AND b.full_name = 'Bob'"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        desugared("CREATE (a:Person {name: 'Ann', age: 3})"),
+        Err(DesugarError::Unsupported(
+            "property `age` is not stored in any column".to_string()
+        ))
+    );
+
+    let options = DesugarOptions::new().with_schema(GraphSchema::new().with_node_table(None));
+    assert!(matches!(
+        cypher()
+            .verified_stmt("MATCH (n) RETURN n")
+            .desugar_cypher_to_sql_with_options(&GenericDialect {}, &options),
+        Err(DesugarError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn desugar_json_access_per_dialect() {
+    let stmt = cypher().verified_stmt("MATCH (n:Person {name: 'Ann'}) RETURN n.age");
+    let desugared_for = |dialect: &dyn Dialect| {
+        let sql = stmt.desugar_cypher_to_sql(dialect).unwrap()[0].to_string();
+        // the generated SQL is valid in the target dialect
+        assert_eq!(
+            Parser::parse_sql(dialect, &sql).unwrap()[0].to_string(),
+            sql
+        );
+        sql
+    };
+
+    assert_eq!(
+        desugared_for(&PostgreSqlDialect {}),
+        "SELECT n.properties ->> 'age' AS \"n.age\" FROM nodes AS n \
+         WHERE n.label = 'Person' AND n.properties ->> 'name' = 'Ann'"
+    );
+    assert_eq!(
+        desugared_for(&DuckDbDialect {}),
+        "SELECT json_extract_string(n.properties, '$.age') AS \"n.age\" FROM nodes AS n \
+         WHERE n.label = 'Person' AND json_extract_string(n.properties, '$.name') = 'Ann'"
+    );
+    assert_eq!(
+        desugared_for(&SnowflakeDialect {}),
+        "SELECT n.properties:age AS \"n.age\" FROM nodes AS n \
+         WHERE n.label = 'Person' AND n.properties:name = 'Ann'"
+    );
+    assert_eq!(
+        desugared_for(&BigQueryDialect {}),
+        "SELECT JSON_VALUE(n.properties, '$.age') AS `n.age` FROM nodes AS n \
+         WHERE n.label = 'Person' AND JSON_VALUE(n.properties, '$.name') = 'Ann'"
+    );
+    assert_eq!(
+        desugared_for(&MsSqlDialect {}),
+        "SELECT JSON_VALUE(n.properties, '$.age') AS [n.age] FROM nodes AS n \
+         WHERE n.label = 'Person' AND JSON_VALUE(n.properties, '$.name') = 'Ann'"
+    );
+    for dialect in [&SQLiteDialect {} as &dyn Dialect, &MySqlDialect {}] {
+        assert_eq!(
+            desugared_for(dialect),
+            "SELECT json_extract(n.properties, '$.age') AS `n.age` FROM nodes AS n \
+             WHERE n.label = 'Person' AND json_extract(n.properties, '$.name') = 'Ann'"
+        );
+    }
+
+    // nested paths and keys that need quoting
+    let options = DesugarOptions::new().with_schema(
+        GraphSchema::new().with_label_table(
+            "Person",
+            NodeTable::new("people")
+                .with_json_column("doc")
+                .with_property(
+                    "city",
+                    PropertyColumn::JsonPath {
+                        column: "details".to_string(),
+                        path: "$.address.city".to_string(),
+                    },
+                ),
+        ),
+    );
+    let stmt = cypher().verified_stmt("MATCH (n:Person) RETURN n.city, n.`full name`");
+    let desugared_for = |dialect: &dyn Dialect| {
+        stmt.desugar_cypher_to_sql_with_options(dialect, &options)
+            .unwrap()[0]
+            .to_string()
+    };
+    assert_eq!(
+        desugared_for(&PostgreSqlDialect {}),
+        "SELECT n.details #>> '{address,city}' AS \"n.city\", \
+         n.doc ->> 'full name' AS \"n.`full name`\" FROM people AS n"
+    );
+    assert_eq!(
+        desugared_for(&SnowflakeDialect {}),
+        "SELECT n.details:address.city AS \"n.city\", \
+         n.doc:\"full name\" AS \"n.`full name`\" FROM people AS n"
+    );
+    assert_eq!(
+        desugared_for(&MsSqlDialect {}),
+        "SELECT JSON_VALUE(n.details, '$.address.city') AS [n.city], \
+         JSON_VALUE(n.doc, '$.\"full name\"') AS [n.`full name`] FROM people AS n"
+    );
+    assert_eq!(
+        desugared_for(&SQLiteDialect {}),
+        "SELECT json_extract(n.details, '$.address.city') AS `n.city`, \
+         json_extract(n.doc, '$.\"full name\"') AS `n.``full name``` FROM people AS n"
+    );
+}
+
+#[test]
+fn desugar_string_matching_per_dialect() {
+    // `%` and `_` are not wildcards, and matching is case-sensitive
+    let stmt = cypher().verified_stmt(
+        "MATCH (n) WHERE n.a STARTS WITH '5%_' AND n.b ENDS WITH 'x' AND n.c CONTAINS 'y' \
+         AND n.d =~ 'A.*' RETURN n",
+    );
+    let where_clause = |dialect: &dyn Dialect| {
+        let sql = stmt.desugar_cypher_to_sql(dialect).unwrap()[0].to_string();
+        sql.split_once(" WHERE ").unwrap().1.to_string()
+    };
+    assert_eq!(
+        where_clause(&PostgreSqlDialect {}),
+        "starts_with(n.properties ->> 'a', '5%_') \
+         AND right(n.properties ->> 'b', length('x')) = 'x' \
+         AND strpos(n.properties ->> 'c', 'y') > 0 \
+         AND n.properties ->> 'd' ~ '^(?:A.*)$'"
+    );
+    assert_eq!(
+        where_clause(&MySqlDialect {}),
+        "LEFT(json_extract(n.properties, '$.a'), CHAR_LENGTH('5%_')) = CAST('5%_' AS BINARY) \
+         AND RIGHT(json_extract(n.properties, '$.b'), CHAR_LENGTH('x')) = CAST('x' AS BINARY) \
+         AND LOCATE(CAST('y' AS BINARY), json_extract(n.properties, '$.c')) > 0 \
+         AND REGEXP_LIKE(json_extract(n.properties, '$.d'), '^(?:A.*)$', 'c')"
+    );
+    assert_eq!(
+        where_clause(&DuckDbDialect {}),
+        "starts_with(json_extract_string(n.properties, '$.a'), '5%_') \
+         AND ends_with(json_extract_string(n.properties, '$.b'), 'x') \
+         AND contains(json_extract_string(n.properties, '$.c'), 'y') \
+         AND regexp_full_match(json_extract_string(n.properties, '$.d'), 'A.*')"
+    );
+    assert_eq!(
+        where_clause(&SnowflakeDialect {}),
+        "STARTSWITH(n.properties:a, '5%_') AND ENDSWITH(n.properties:b, 'x') \
+         AND CONTAINS(n.properties:c, 'y') AND REGEXP_LIKE(n.properties:d, 'A.*')"
+    );
+    assert_eq!(
+        where_clause(&BigQueryDialect {}),
+        "STARTS_WITH(JSON_VALUE(n.properties, '$.a'), '5%_') \
+         AND ENDS_WITH(JSON_VALUE(n.properties, '$.b'), 'x') \
+         AND STRPOS(JSON_VALUE(n.properties, '$.c'), 'y') > 0 \
+         AND REGEXP_CONTAINS(JSON_VALUE(n.properties, '$.d'), '^(?:A.*)$')"
+    );
+    for unsupported in [
+        "MATCH (n) WHERE n.a CONTAINS 'y' RETURN n",
+        "MATCH (n) WHERE n.a =~ 'y' RETURN n",
+    ] {
+        assert!(matches!(
+            cypher()
+                .verified_stmt(unsupported)
+                .desugar_cypher_to_sql(&MsSqlDialect {}),
+            Err(DesugarError::Unsupported(_))
+        ));
+    }
+}
+
+#[test]
+fn desugar_typed_property_values() {
+    assert_eq!(
+        desugared(
+            "CREATE (n:Person {name: 'Ann \"A\"', age: -42, score: 1.5, ok: true, gone: NULL, \
+             tags: ['a', [1, 2]], address: {city: 'Paris'}})"
+        ),
+        "INSERT INTO nodes (label, properties) VALUES ('Person', \
+         '{\"name\":\"Ann \\\"A\\\"\",\"age\":-42,\"score\":1.5,\"ok\":true,\"gone\":null,\
+         \"tags\":[\"a\",[1,2]],\"address\":{\"city\":\"Paris\"}}')"
+    );
+    assert_eq!(
+        desugared("MATCH (n {age: 42, ok: false}) WHERE n.score >= -1.5 RETURN n"),
+        "SELECT n.* FROM nodes AS n WHERE json_extract(n.properties, '$.age') = 42 \
+         AND json_extract(n.properties, '$.ok') = false \
+         AND json_extract(n.properties, '$.score') >= -1.5"
+    );
+
+    // where JSON values are extracted as text, they are cast to compare as numbers
+    let stmt = cypher().verified_stmt(
+        "MATCH (n {age: 42, ok: false}) WHERE n.score >= -1.5 AND n.name = 'Ann' RETURN n",
+    );
+    assert_eq!(
+        stmt.desugar_cypher_to_sql(&PostgreSqlDialect {}).unwrap()[0].to_string(),
+        "SELECT n.* FROM nodes AS n WHERE CAST(n.properties ->> 'age' AS NUMERIC) = 42 \
+         AND CAST(n.properties ->> 'ok' AS BOOLEAN) = false \
+         AND CAST(n.properties ->> 'score' AS NUMERIC) >= -1.5 \
+         AND n.properties ->> 'name' = 'Ann'"
+    );
+    // and as operands of arithmetic, which binds tighter than `->>`
+    let desugared = |sql: &str| {
+        cypher()
+            .verified_stmt(sql)
+            .desugar_cypher_to_sql(&PostgreSqlDialect {})
+            .unwrap()[0]
+            .to_string()
+    };
+    assert_eq!(
+        desugared("MATCH (n) SET n.age = n.age + 1"),
+        "UPDATE nodes SET properties = properties || \
+         jsonb_build_object('age', CAST(nodes.properties ->> 'age' AS NUMERIC) + 1) \
+         WHERE id IN (SELECT n.id FROM nodes AS n)"
+    );
+    assert_eq!(
+        desugared("MATCH (n) RETURN -n.age, n.age % 2, n.name + 'x'"),
+        "SELECT -CAST(n.properties ->> 'age' AS NUMERIC) AS \"-n.age\", \
+         CAST(n.properties ->> 'age' AS NUMERIC) % 2 AS \"n.age % 2\", \
+         (n.properties ->> 'name') + 'x' AS \"n.name + 'x'\" FROM nodes AS n"
+    );
+    assert_eq!(
+        cypher()
+            .verified_stmt("MATCH (n) RETURN n.age * 2")
+            .desugar_cypher_to_sql(&BigQueryDialect {})
+            .unwrap()[0]
+            .to_string(),
+        "SELECT CAST(JSON_VALUE(n.properties, '$.age') AS FLOAT64) * 2 AS `n.age * 2` \
+         FROM nodes AS n"
+    );
+    // and when ordered against a parameter
+    assert_eq!(
+        desugared("MATCH (n) WHERE n.age > $min RETURN n"),
+        "SELECT n.* FROM nodes AS n WHERE CAST(n.properties ->> 'age' AS NUMERIC) > $1"
+    );
+    // but not when compared for equality, as the parameter may be a string
+    assert_eq!(
+        desugared("MATCH (n) WHERE n.age = $age RETURN n"),
+        "SELECT n.* FROM nodes AS n WHERE n.properties ->> 'age' = $1"
+    );
+    // and when in a list of literals of one type
+    assert_eq!(
+        desugared("MATCH (n) WHERE n.age IN [1, 2] AND n.name IN ['a', 1] RETURN n"),
+        "SELECT n.* FROM nodes AS n WHERE CAST(n.properties ->> 'age' AS NUMERIC) IN (1, 2) \
+         AND n.properties ->> 'name' IN ('a', 1)"
+    );
+
+    // T-SQL has no boolean literals, and JSON_MODIFY writes a BIT as a JSON boolean
+    let mssql = |sql: &str| {
+        cypher()
+            .verified_stmt(sql)
+            .desugar_cypher_to_sql(&MsSqlDialect {})
+            .unwrap()[0]
+            .to_string()
+    };
+    assert_eq!(
+        mssql("MATCH (n {ok: true}) WHERE n.age > $min RETURN n, false"),
+        "SELECT n.*, 0 FROM nodes AS n \
+         WHERE CAST(JSON_VALUE(n.properties, '$.ok') AS BIT) = 1 \
+         AND CAST(JSON_VALUE(n.properties, '$.age') AS FLOAT) > @min"
+    );
+    assert_eq!(
+        mssql("MATCH (n) SET n.ok = true"),
+        "UPDATE nodes SET properties = JSON_MODIFY(properties, '$.ok', CAST(1 AS BIT)) \
+         WHERE id IN (SELECT n.id FROM nodes AS n)"
+    );
+
+    assert!(matches!(
+        cypher()
+            .verified_stmt("MATCH (n {tags: ['a']}) RETURN n")
+            .desugar_cypher_to_sql(&GenericDialect {}),
+        Err(DesugarError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn desugar_parameters() {
+    fn desugared(stmt: &Statement, dialect: &dyn Dialect) -> (String, Vec<String>) {
+        let [DesugaredStatement {
+            statement,
+            parameters,
+        }] = &stmt
+            .desugar_cypher(dialect, &DesugarOptions::default())
+            .unwrap()[..]
+        else {
+            panic!("Expected a single statement");
+        };
+        (statement.to_string(), parameters.clone())
+    }
+
+    let stmt = cypher().verified_stmt(
+        "MATCH (n:User {id: $id}) WHERE n.age > $min OR n.boss = $id \
+         RETURN n.name, n.age + $k LIMIT $limit",
+    );
+
+    // `?` placeholders are bound in the order they occur in the SQL
+    assert_eq!(
+        desugared(&stmt, &SQLiteDialect {}),
+        (
+            "SELECT json_extract(n.properties, '$.name') AS `n.name`, \
+             json_extract(n.properties, '$.age') + ? AS `n.age + $k` FROM nodes AS n \
+             WHERE n.label = 'User' AND json_extract(n.properties, '$.id') = ? \
+             AND (json_extract(n.properties, '$.age') > ? OR json_extract(n.properties, '$.boss') = ?) \
+             LIMIT ?"
+                .to_string(),
+            vec!["k", "id", "min", "id", "limit"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        )
+    );
+    assert_eq!(
+        desugared(&stmt, &PostgreSqlDialect {}),
+        (
+            "SELECT n.properties ->> 'name' AS \"n.name\", \
+             CAST(n.properties ->> 'age' AS NUMERIC) + $1 AS \"n.age + $k\" FROM nodes AS n \
+             WHERE n.label = 'User' AND n.properties ->> 'id' = $2 \
+             AND (CAST(n.properties ->> 'age' AS NUMERIC) > $3 OR n.properties ->> 'boss' = $2) \
+             LIMIT $4"
+                .to_string(),
+            vec!["k", "id", "min", "limit"]
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        )
+    );
+    assert_eq!(
+        desugared(&stmt, &BigQueryDialect {}).1,
+        vec!["k", "id", "min", "limit"]
+    );
+    assert!(desugared(&stmt, &BigQueryDialect {})
+        .0
+        .ends_with("JSON_VALUE(n.properties, '$.boss') = @id) LIMIT @limit"));
+
+    // parameters in the properties of a new node are passed to a JSON constructor
+    let stmt = cypher().verified_stmt("CREATE (n:User {id: $id, active: true})");
+    assert_eq!(
+        desugared(&stmt, &SQLiteDialect {}),
+        (
+            "INSERT INTO nodes (label, properties) \
+             VALUES ('User', json_object('id', ?, 'active', true))"
+                .to_string(),
+            vec!["id".to_string()]
+        )
+    );
+    assert_eq!(
+        desugared(&stmt, &PostgreSqlDialect {}).0,
+        "INSERT INTO nodes (label, properties) \
+         VALUES ('User', json_build_object('id', $1, 'active', true))"
+    );
+
+    // parameters of pattern predicates and EXISTS subqueries
+    let stmt = cypher().verified_stmt(
+        "MATCH (n) WHERE (n)-->({id: $friend}) OR EXISTS { MATCH (n)<--(m) WHERE m.age > $age } \
+         RETURN n",
+    );
+    assert_eq!(desugared(&stmt, &SQLiteDialect {}).1, vec!["friend", "age"]);
+
+    // parameters the translation repeats or wraps are bound where they end up
+    let stmt = cypher().verified_stmt("MATCH (n) WHERE n.name ENDS WITH $suffix RETURN n");
+    assert_eq!(
+        desugared(&stmt, &SQLiteDialect {}),
+        (
+            "SELECT n.* FROM nodes AS n WHERE substr(json_extract(n.properties, '$.name'), \
+             length(json_extract(n.properties, '$.name')) - length(?) + 1) = ?"
+                .to_string(),
+            vec!["suffix".to_string(), "suffix".to_string()]
+        )
+    );
+    assert_eq!(
+        desugared(&stmt, &PostgreSqlDialect {}),
+        (
+            "SELECT n.* FROM nodes AS n WHERE right(n.properties ->> 'name', length($1)) = $1"
+                .to_string(),
+            vec!["suffix".to_string()]
+        )
+    );
+
+    // queries without parameters have none
+    let stmt = cypher().verified_stmt("MATCH (n) RETURN n");
+    assert_eq!(desugared(&stmt, &SQLiteDialect {}).1, Vec::<String>::new());
+}
@@ -2375,6 +2375,18 @@ fn parse_mssql_merge_with_output() {
     ms_and_generic().verified_stmt(stmt);
 }
 
+#[test]
+fn parse_mssql_insert_with_output() {
+    ms_and_generic().verified_stmt(
+        "INSERT INTO dbo.products (ProductName) OUTPUT INSERTED.ProductID AS id VALUES ('Nut')",
+    );
+    ms_and_generic()
+        .verified_stmt("INSERT INTO dbo.log OUTPUT INSERTED.* INTO dbo.log_copy DEFAULT VALUES");
+    ms_and_generic().verified_stmt(
+        "INSERT INTO dbo.products (ProductName) OUTPUT INSERTED.ProductID SELECT name FROM dbo.nuts",
+    );
+}
+
 #[test]
 fn parse_create_trigger() {
     let create_trigger = "\
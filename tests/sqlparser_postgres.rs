@@ -5167,6 +5167,7 @@ fn test_simple_postgres_insert_with_alias() {
                 }
             ],
             overwrite: false,
+            output: None,
             source: Some(Box::new(Query {
                 with: None,
                 body: Box::new(SetExpr::Values(Values {
@@ -5238,6 +5239,7 @@ fn test_simple_postgres_insert_with_alias() {
                 }
             ],
             overwrite: false,
+            output: None,
             source: Some(Box::new(Query {
                 with: None,
                 body: Box::new(SetExpr::Values(Values {
@@ -5311,6 +5313,7 @@ fn test_simple_insert_with_quoted_alias() {
                 }
             ],
             overwrite: false,
+            output: None,
             source: Some(Box::new(Query {
                 with: None,
                 body: Box::new(SetExpr::Values(Values {